@@ -0,0 +1,162 @@
+use kodecks::{
+    action::PlayerAvailableActions,
+    archetype::ArchetypeId,
+    deck::DeckList,
+    env::{CardPrior, EndgameState, Environment},
+    log::GameLog,
+    player::PlayerConfig,
+    profile::{BotConfig, GameProfile},
+    zone::MoveReason,
+};
+use kodecks_bot::{Bot, ConfiguredBot};
+use kodecks_catalog::CATALOG;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+
+/// Games taking more steps than this are stopped and recorded as aborted.
+const MAX_STEPS: usize = 10000;
+
+/// A bot playing a deck.
+#[derive(Debug, Clone)]
+pub struct Entrant {
+    pub name: String,
+    /// The name of the bot, shared by the entrants playing it with other decks.
+    pub bot_name: String,
+    pub bot: BotConfig,
+    pub deck: DeckList,
+}
+
+/// The result of a game, with the entrants referred to by index.
+#[derive(Debug, Clone)]
+pub struct GameRecord {
+    /// The entrants playing as player 0 and player 1.
+    pub seats: [usize; 2],
+    pub winner: Option<usize>,
+    /// The game was stopped after [`MAX_STEPS`] steps without an end.
+    pub aborted: bool,
+    pub turns: u16,
+    /// The archetypes each entrant cast at least once.
+    pub casts: BTreeSet<(usize, ArchetypeId)>,
+    /// The shards generated by the cards of each archetype.
+    pub shards: BTreeMap<ArchetypeId, u32>,
+}
+
+/// Plays a game between two entrants to the end.
+pub fn play_game(entrants: &[Entrant], seats: [usize; 2], seed: u64) -> GameRecord {
+    let profile = GameProfile {
+        players: seats
+            .iter()
+            .map(|&entrant| PlayerConfig {
+                deck: entrants[entrant].deck.clone(),
+            })
+            .collect(),
+        rng_seed: Some(seed),
+        ..Default::default()
+    };
+    let mut bots = seats
+        .iter()
+        .enumerate()
+        .map(|(player, &entrant)| {
+            let prior =
+                CardPrior::default().with_deck(player as u8, entrants[entrant].deck.clone());
+            ConfiguredBot::new(
+                &BotConfig {
+                    player: player as u8,
+                    ..entrants[entrant].bot.clone()
+                },
+                prior,
+                ConfiguredBot::seed(seed, player as u8),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut env = Arc::new(Environment::new(profile, CATALOG.clone()));
+    let mut available_actions: Option<PlayerAvailableActions> = None;
+    let mut casts = BTreeSet::new();
+    let mut shards = BTreeMap::new();
+    for _ in 0..MAX_STEPS {
+        if env.game_condition().is_ended() {
+            break;
+        }
+        let (player, action) = match &available_actions {
+            Some(actions) => (
+                actions.player,
+                bots[actions.player as usize].compute_best_action(env.clone(), actions),
+            ),
+            None => (0, None),
+        };
+        let report = Arc::make_mut(&mut env).process(player, action);
+        for log in &report.logs {
+            match log {
+                GameLog::CardMoved {
+                    card,
+                    reason: MoveReason::Casted,
+                    ..
+                } => {
+                    casts.insert((seats[card.owner as usize], card.archetype_id));
+                }
+                GameLog::ShardsEarned { source, amount, .. } => {
+                    *shards.entry(source.archetype_id).or_default() += *amount as u32;
+                }
+                _ => {}
+            }
+        }
+        available_actions = report.available_actions;
+    }
+
+    let winner = match env.game_condition() {
+        EndgameState::Finished {
+            winner: Some(winner),
+            ..
+        } => Some(seats[winner as usize]),
+        _ => None,
+    };
+    GameRecord {
+        seats,
+        winner,
+        aborted: !env.game_condition().is_ended(),
+        turns: env.state.turn,
+        casts,
+        shards,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodecks::profile::{BotDifficulty, BotKind};
+    use kodecks_catalog::decks::{blue_deck, red_deck};
+
+    #[test]
+    fn test_play_game_seed() {
+        let bot = BotConfig {
+            kind: BotKind::Mcts,
+            difficulty: BotDifficulty {
+                iterations: 8,
+                time: None,
+                blunder_rate: 0.2,
+                exploration: 1.4,
+            },
+            ..Default::default()
+        };
+        let entrants =
+            [("blue", blue_deck(&CATALOG)), ("red", red_deck(&CATALOG))].map(|(name, deck)| {
+                Entrant {
+                    name: name.to_string(),
+                    bot_name: "mcts".to_string(),
+                    bot: bot.clone(),
+                    deck,
+                }
+            });
+
+        // The bots draw their random choices from the seed of the game.
+        let a = play_game(&entrants, [0, 1], 1);
+        let b = play_game(&entrants, [0, 1], 1);
+        assert_eq!(a.winner, b.winner);
+        assert_eq!(a.turns, b.turns);
+        assert_eq!(a.casts, b.casts);
+        assert_eq!(a.shards, b.shards);
+    }
+}
@@ -0,0 +1,174 @@
+use bpaf::Bpaf;
+use game::{Entrant, GameRecord};
+use kodecks::{
+    catalog::Catalog,
+    deck::DeckList,
+    profile::{BotConfig, BotDifficulty, BotKind},
+};
+use kodecks_catalog::{
+    decks::{blue_deck, red_deck, starter_deck},
+    CATALOG,
+};
+use rayon::prelude::*;
+use report::Report;
+use std::{fs, path::Path, path::PathBuf, process::ExitCode};
+
+mod game;
+mod report;
+mod stats;
+
+fn main() -> ExitCode {
+    let opts = options().run();
+    let entrants = match load_entrants(&opts.bots, &opts.decks, &CATALOG) {
+        Ok(entrants) => entrants,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if entrants.len() < 2 {
+        eprintln!("At least two entrants are needed, add more bots or decks");
+        return ExitCode::FAILURE;
+    }
+
+    // Every pair of entrants plays the same seeds, swapping seats on every other game.
+    let mut schedule = vec![];
+    for a in 0..entrants.len() {
+        for b in a + 1..entrants.len() {
+            for game in 0..opts.games {
+                let seats = if game.is_multiple_of(2) {
+                    [a, b]
+                } else {
+                    [b, a]
+                };
+                schedule.push((seats, opts.seed.wrapping_add(game as u64)));
+            }
+        }
+    }
+
+    eprintln!(
+        "Playing {} games between {} entrants",
+        schedule.len(),
+        entrants.len()
+    );
+    let records = schedule
+        .into_par_iter()
+        .map(|(seats, seed)| game::play_game(&entrants, seats, seed))
+        .collect::<Vec<GameRecord>>();
+
+    let report = Report::new(&entrants, &records, &CATALOG);
+    report.print_table();
+
+    if let Some(path) = opts.json {
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        if let Err(err) = fs::write(&path, json) {
+            eprintln!("Failed to write {path:?}: {err}");
+            return ExitCode::FAILURE;
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Returns an entrant for each combination of the bots and the decks.
+fn load_entrants(
+    bots: &[String],
+    decks: &[String],
+    catalog: &Catalog,
+) -> Result<Vec<Entrant>, String> {
+    let bots = if bots.is_empty() {
+        vec!["mcts".to_string()]
+    } else {
+        bots.to_vec()
+    };
+    let decks = if decks.is_empty() {
+        vec!["blue".to_string(), "red".to_string()]
+    } else {
+        decks.to_vec()
+    };
+
+    let mut entrants = vec![];
+    for bot in &bots {
+        let config = parse_bot(bot)?;
+        for deck in &decks {
+            entrants.push(Entrant {
+                name: if bots.len() > 1 && decks.len() > 1 {
+                    format!("{bot}/{deck}")
+                } else if bots.len() > 1 {
+                    bot.clone()
+                } else {
+                    deck.clone()
+                },
+                bot_name: bot.clone(),
+                bot: config.clone(),
+                deck: load_deck(deck, catalog)?,
+            });
+        }
+    }
+    Ok(entrants)
+}
+
+/// Parses a bot of the form `KIND[-LEVEL]`, e.g. `mcts-hard`.
+fn parse_bot(spec: &str) -> Result<BotConfig, String> {
+    let (kind, level) = spec.split_once('-').unwrap_or((spec, "normal"));
+    let kind = match kind {
+        "simple" => BotKind::Simple,
+        "default" => BotKind::Default {
+            style: Default::default(),
+        },
+        "mcts" => BotKind::Mcts,
+        _ => return Err(format!("Unknown bot kind: {kind}")),
+    };
+    let difficulty = match level {
+        "easy" => BotDifficulty::EASY,
+        "normal" => BotDifficulty::NORMAL,
+        "hard" => BotDifficulty::HARD,
+        _ => return Err(format!("Unknown difficulty: {level}")),
+    };
+    Ok(BotConfig {
+        kind,
+        difficulty,
+        ..Default::default()
+    })
+}
+
+fn load_deck(deck: &str, catalog: &Catalog) -> Result<DeckList, String> {
+    match deck {
+        "starter" => return Ok(starter_deck(catalog)),
+        "blue" => return Ok(blue_deck(catalog)),
+        "red" => return Ok(red_deck(catalog)),
+        _ => {}
+    }
+    let path = Path::new(deck);
+    if path.is_file() {
+        let data =
+            fs::read_to_string(path).map_err(|err| format!("Failed to read {path:?}: {err}"))?;
+        DeckList::parse(&data, catalog).ok_or_else(|| format!("Invalid deck list {path:?}"))
+    } else {
+        DeckList::from_code(deck, catalog).map_err(|err| err.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Bpaf)]
+#[bpaf(options)]
+pub struct Options {
+    #[bpaf(long("bot"), argument("BOT"))]
+    /// Add a bot of the form KIND[-LEVEL], where KIND is simple, default or mcts
+    /// and LEVEL is easy, normal or hard (default: mcts)
+    bots: Vec<String>,
+
+    #[bpaf(long("deck"), argument("DECK"))]
+    /// Add a deck: starter, blue, red, a deck code or a deck list file (default: blue and red)
+    decks: Vec<String>,
+
+    #[bpaf(long("games"), argument("N"), fallback(20))]
+    /// Play N games between each pair of bot and deck combinations
+    games: u32,
+
+    #[bpaf(long("seed"), argument("SEED"), fallback(0))]
+    /// Use the seeds from SEED for the games
+    seed: u64,
+
+    #[bpaf(long("json"), argument("FILE"))]
+    /// Also write the report as JSON to FILE
+    json: Option<PathBuf>,
+}
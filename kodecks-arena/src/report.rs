@@ -0,0 +1,419 @@
+use crate::{
+    game::{Entrant, GameRecord},
+    stats::{wilson_interval, Elo},
+};
+use kodecks::{archetype::ArchetypeId, catalog::Catalog};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    /// The number of finished games. The aborted ones are left out of all other results.
+    pub games: u32,
+    pub aborted: u32,
+    pub average_turns: f64,
+    pub entrants: Vec<EntrantStats>,
+    pub bots: Vec<BotStats>,
+    pub matchups: Vec<MatchupStats>,
+    pub archetypes: Vec<ArchetypeStats>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EntrantStats {
+    pub name: String,
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub aborted: u32,
+    /// The share of the games won, counting a draw as half a win.
+    pub win_rate: f64,
+    /// The 95% confidence interval of the win rate.
+    pub interval: (f64, f64),
+    pub elo: f64,
+}
+
+/// The rating of a bot over all its decks.
+///
+/// Only the games between different bots count, so a single bot keeps the initial rating.
+#[derive(Debug, Clone, Serialize)]
+pub struct BotStats {
+    pub name: String,
+    pub games: u32,
+    pub elo: f64,
+}
+
+/// The results of the games between two entrants, from the side of the first.
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchupStats {
+    pub first: String,
+    pub second: String,
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub win_rate: f64,
+    pub interval: (f64, f64),
+    pub average_turns: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchetypeStats {
+    pub id: ArchetypeId,
+    pub name: String,
+    /// The number of games in which a player cast the card, counted for each player.
+    pub games_cast: u32,
+    /// The share of those games won by the player who cast it.
+    pub win_rate_when_cast: f64,
+    pub shards_generated: u32,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Tally {
+    games: u32,
+    wins: u32,
+    losses: u32,
+    draws: u32,
+    turns: u32,
+}
+
+impl Tally {
+    fn record(&mut self, player: usize, record: &GameRecord) {
+        self.games += 1;
+        self.turns += record.turns as u32;
+        match record.winner {
+            Some(winner) if winner == player => self.wins += 1,
+            Some(_) => self.losses += 1,
+            None => self.draws += 1,
+        }
+    }
+
+    fn score(&self) -> f64 {
+        self.wins as f64 + self.draws as f64 / 2.0
+    }
+
+    fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.score() / self.games as f64
+        }
+    }
+
+    fn average_turns(&self) -> f64 {
+        if self.games == 0 {
+            0.0
+        } else {
+            self.turns as f64 / self.games as f64
+        }
+    }
+}
+
+impl Report {
+    /// Summarizes the games, which are expected in the order they were scheduled
+    /// so that the Elo ratings do not depend on which game finished first.
+    pub fn new(entrants: &[Entrant], records: &[GameRecord], catalog: &Catalog) -> Self {
+        let mut tallies = vec![Tally::default(); entrants.len()];
+        let mut aborted = vec![0; entrants.len()];
+        let mut matchups: BTreeMap<(usize, usize), Tally> = BTreeMap::new();
+        let mut elo = Elo::new(entrants.len());
+        let mut bots: Vec<(&str, u32)> = vec![];
+        let bot_of = entrants
+            .iter()
+            .map(|entrant| {
+                bots.iter()
+                    .position(|(name, _)| *name == entrant.bot_name)
+                    .unwrap_or_else(|| {
+                        bots.push((&entrant.bot_name, 0));
+                        bots.len() - 1
+                    })
+            })
+            .collect::<Vec<_>>();
+        let mut bot_elo = Elo::new(bots.len());
+        let mut archetypes: BTreeMap<ArchetypeId, (u32, u32, u32)> = BTreeMap::new();
+        let mut turns = 0;
+
+        for record in records {
+            let [a, b] = record.seats;
+            if record.aborted {
+                aborted[a] += 1;
+                aborted[b] += 1;
+                continue;
+            }
+            let (first, second) = (a.min(b), a.max(b));
+            tallies[a].record(a, record);
+            tallies[b].record(b, record);
+            matchups
+                .entry((first, second))
+                .or_default()
+                .record(first, record);
+            turns += record.turns as u32;
+
+            let score = match record.winner {
+                Some(winner) if winner == first => 1.0,
+                Some(_) => 0.0,
+                None => 0.5,
+            };
+            elo.update(first, second, score);
+            let (first_bot, second_bot) = (bot_of[first], bot_of[second]);
+            if first_bot != second_bot {
+                bot_elo.update(first_bot, second_bot, score);
+                bots[first_bot].1 += 1;
+                bots[second_bot].1 += 1;
+            }
+
+            for &(entrant, archetype) in &record.casts {
+                let stats = archetypes.entry(archetype).or_default();
+                stats.0 += 1;
+                if record.winner == Some(entrant) {
+                    stats.1 += 1;
+                }
+            }
+            for (&archetype, &shards) in &record.shards {
+                archetypes.entry(archetype).or_default().2 += shards;
+            }
+        }
+
+        let games = records.iter().filter(|record| !record.aborted).count() as u32;
+        Self {
+            games,
+            aborted: records.len() as u32 - games,
+            average_turns: if games == 0 {
+                0.0
+            } else {
+                turns as f64 / games as f64
+            },
+            entrants: entrants
+                .iter()
+                .zip(&tallies)
+                .zip(&aborted)
+                .enumerate()
+                .map(|(index, ((entrant, tally), &aborted))| EntrantStats {
+                    name: entrant.name.clone(),
+                    games: tally.games,
+                    wins: tally.wins,
+                    losses: tally.losses,
+                    draws: tally.draws,
+                    aborted,
+                    win_rate: tally.win_rate(),
+                    interval: wilson_interval(tally.score(), tally.games),
+                    elo: elo.rating(index),
+                })
+                .collect(),
+            bots: bots
+                .iter()
+                .enumerate()
+                .map(|(index, &(name, games))| BotStats {
+                    name: name.to_string(),
+                    games,
+                    elo: bot_elo.rating(index),
+                })
+                .collect(),
+            matchups: matchups
+                .into_iter()
+                .map(|((first, second), tally)| MatchupStats {
+                    first: entrants[first].name.clone(),
+                    second: entrants[second].name.clone(),
+                    games: tally.games,
+                    wins: tally.wins,
+                    losses: tally.losses,
+                    draws: tally.draws,
+                    win_rate: tally.win_rate(),
+                    interval: wilson_interval(tally.score(), tally.games),
+                    average_turns: tally.average_turns(),
+                })
+                .collect(),
+            archetypes: archetypes
+                .into_iter()
+                .map(
+                    |(id, (games_cast, wins, shards_generated))| ArchetypeStats {
+                        id,
+                        name: catalog
+                            .get(id)
+                            .map(|archetype| archetype.name.clone())
+                            .unwrap_or_else(|| id.to_string()),
+                        games_cast,
+                        win_rate_when_cast: if games_cast == 0 {
+                            0.0
+                        } else {
+                            wins as f64 / games_cast as f64
+                        },
+                        shards_generated,
+                    },
+                )
+                .collect(),
+        }
+    }
+
+    pub fn print_table(&self) {
+        println!("Games\t{}", self.games);
+        println!("Aborted\t{}", self.aborted);
+        println!("Turns\t{:.1}", self.average_turns);
+
+        println!("\nEntrant\tGames\tW-L-D\tAborted\tWin rate\t95% CI\tElo");
+        println!("-------\t-----\t-----\t-------\t--------\t------\t---");
+        for entrant in &self.entrants {
+            println!(
+                "{}\t{}\t{}-{}-{}\t{}\t{:.1}%\t{:.1}%-{:.1}%\t{:.0}",
+                entrant.name,
+                entrant.games,
+                entrant.wins,
+                entrant.losses,
+                entrant.draws,
+                entrant.aborted,
+                entrant.win_rate * 100.0,
+                entrant.interval.0 * 100.0,
+                entrant.interval.1 * 100.0,
+                entrant.elo
+            );
+        }
+
+        if self.bots.len() > 1 {
+            println!("\nBot\tGames\tElo");
+            println!("---\t-----\t---");
+            for bot in &self.bots {
+                println!("{}\t{}\t{:.0}", bot.name, bot.games, bot.elo);
+            }
+        }
+
+        println!("\nMatchup\tGames\tW-L-D\tWin rate\t95% CI\tTurns");
+        println!("-------\t-----\t-----\t--------\t------\t-----");
+        for matchup in &self.matchups {
+            println!(
+                "{} vs {}\t{}\t{}-{}-{}\t{:.1}%\t{:.1}%-{:.1}%\t{:.1}",
+                matchup.first,
+                matchup.second,
+                matchup.games,
+                matchup.wins,
+                matchup.losses,
+                matchup.draws,
+                matchup.win_rate * 100.0,
+                matchup.interval.0 * 100.0,
+                matchup.interval.1 * 100.0,
+                matchup.average_turns
+            );
+        }
+
+        println!("\nCard\tCast\tWin rate when cast\tShards");
+        println!("----\t----\t------------------\t------");
+        for archetype in &self.archetypes {
+            println!(
+                "{}\t{}\t{:.1}%\t{}",
+                archetype.name,
+                archetype.games_cast,
+                archetype.win_rate_when_cast * 100.0,
+                archetype.shards_generated
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodecks::{
+        profile::BotConfig,
+        testing::{archetype, catalog, deck},
+    };
+    use std::collections::BTreeSet;
+
+    fn entrant(bot: &str, deck_name: &str) -> Entrant {
+        Entrant {
+            name: format!("{bot}/{deck_name}"),
+            bot_name: bot.to_string(),
+            bot: BotConfig::default(),
+            deck: deck(&[(deck_name, 30)]),
+        }
+    }
+
+    fn record(seats: [usize; 2], winner: Option<usize>, casts: &[(usize, &str)]) -> GameRecord {
+        GameRecord {
+            seats,
+            winner,
+            aborted: false,
+            turns: 10,
+            casts: casts
+                .iter()
+                .map(|&(entrant, id)| (entrant, ArchetypeId::new(id)))
+                .collect::<BTreeSet<_>>(),
+            shards: BTreeMap::from_iter([(ArchetypeId::new("c0"), 2)]),
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let entrants = [
+            entrant("mcts", "c0"),
+            entrant("simple", "c0"),
+            entrant("simple", "c1"),
+        ];
+        let records = [
+            record([0, 1], Some(0), &[(0, "c0"), (1, "c0")]),
+            record([1, 0], Some(0), &[(0, "c0")]),
+            record([1, 2], None, &[(2, "c1")]),
+            record([2, 0], Some(2), &[(2, "c1")]),
+            // Aborted games count for no results and no ratings.
+            GameRecord {
+                aborted: true,
+                ..record([0, 2], None, &[(0, "c0")])
+            },
+        ];
+        let catalog = catalog([archetype("c0")]);
+        let report = Report::new(&entrants, &records, &catalog);
+
+        assert_eq!(report.games, 4);
+        assert_eq!(report.aborted, 1);
+        let aborted = report
+            .entrants
+            .iter()
+            .map(|stats| stats.aborted)
+            .collect::<Vec<_>>();
+        assert_eq!(aborted, [1, 0, 1]);
+        assert_eq!(report.average_turns, 10.0);
+
+        let results = report
+            .entrants
+            .iter()
+            .map(|stats| (stats.games, stats.wins, stats.losses, stats.draws))
+            .collect::<Vec<_>>();
+        assert_eq!(results, [(3, 2, 1, 0), (3, 0, 2, 1), (2, 1, 0, 1)]);
+        assert_eq!(report.entrants[2].win_rate, 0.75);
+        assert!(report.entrants[0].elo > report.entrants[1].elo);
+        let total = report.entrants.iter().map(|stats| stats.elo).sum::<f64>();
+        assert!((total - 1500.0 * 3.0).abs() < 1e-9);
+
+        // The games between the two decks of the simple bot do not count for the bots.
+        let bots = report
+            .bots
+            .iter()
+            .map(|stats| (stats.name.as_str(), stats.games))
+            .collect::<Vec<_>>();
+        assert_eq!(bots, [("mcts", 3), ("simple", 3)]);
+        assert!(report.bots[0].elo > 1500.0);
+        assert!((report.bots[0].elo + report.bots[1].elo - 1500.0 * 2.0).abs() < 1e-9);
+
+        // Each matchup is seen from the entrant listed first, whichever seat it played.
+        let matchup = &report.matchups[0];
+        assert_eq!(
+            (matchup.first.as_str(), matchup.second.as_str()),
+            ("mcts/c0", "simple/c0")
+        );
+        assert_eq!((matchup.games, matchup.wins, matchup.losses), (2, 2, 0));
+        assert_eq!(report.matchups[1].wins, 0);
+        assert_eq!(report.matchups[1].losses, 1);
+
+        let archetypes = report
+            .archetypes
+            .iter()
+            .map(|stats| {
+                (
+                    stats.name.as_str(),
+                    stats.games_cast,
+                    stats.win_rate_when_cast,
+                    stats.shards_generated,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(archetypes, [("c0", 3, 2.0 / 3.0, 8), ("c1", 2, 0.5, 0)]);
+    }
+}
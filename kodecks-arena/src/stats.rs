@@ -0,0 +1,78 @@
+/// The z-score of the 95% confidence level.
+const Z_95: f64 = 1.96;
+
+const ELO_INITIAL: f64 = 1500.0;
+const ELO_K: f64 = 16.0;
+
+/// Returns the 95% Wilson score interval of the win rate,
+/// where `score` counts a win as 1 and a draw as 0.5.
+pub fn wilson_interval(score: f64, games: u32) -> (f64, f64) {
+    if games == 0 {
+        return (0.0, 1.0);
+    }
+    let n = games as f64;
+    let p = score / n;
+    let z2 = Z_95 * Z_95;
+    let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let margin = Z_95 / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+/// Elo ratings updated game by game.
+#[derive(Debug, Clone)]
+pub struct Elo {
+    ratings: Vec<f64>,
+}
+
+impl Elo {
+    pub fn new(players: usize) -> Self {
+        Self {
+            ratings: vec![ELO_INITIAL; players],
+        }
+    }
+
+    /// Records a game between `a` and `b`, where `score` is 1 if `a` won, 0.5 for a draw and 0 if `b` won.
+    pub fn update(&mut self, a: usize, b: usize, score: f64) {
+        let expected = 1.0 / (1.0 + 10f64.powf((self.ratings[b] - self.ratings[a]) / 400.0));
+        let delta = ELO_K * (score - expected);
+        self.ratings[a] += delta;
+        self.ratings[b] -= delta;
+    }
+
+    pub fn rating(&self, player: usize) -> f64 {
+        self.ratings[player]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wilson_interval() {
+        let (low, high) = wilson_interval(50.0, 100);
+        assert!((low - 0.404).abs() < 0.001);
+        assert!((high - 0.596).abs() < 0.001);
+
+        let (low, high) = wilson_interval(10.0, 10);
+        assert!(low > 0.69 && low < 0.73);
+        assert!((high - 1.0).abs() < 1e-9);
+
+        assert_eq!(wilson_interval(0.0, 0), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_elo() {
+        let mut elo = Elo::new(3);
+        elo.update(0, 1, 1.0);
+        assert_eq!(elo.rating(0), ELO_INITIAL + ELO_K / 2.0);
+        assert_eq!(elo.rating(1), ELO_INITIAL - ELO_K / 2.0);
+        assert_eq!(elo.rating(2), ELO_INITIAL);
+
+        elo.update(1, 2, 0.5);
+        assert!(elo.rating(1) > ELO_INITIAL - ELO_K / 2.0);
+        assert!(elo.rating(2) < ELO_INITIAL);
+        let total = (0..3).map(|player| elo.rating(player)).sum::<f64>();
+        assert!((total - ELO_INITIAL * 3.0).abs() < 1e-9);
+    }
+}
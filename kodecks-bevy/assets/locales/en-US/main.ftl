@@ -1,11 +1,19 @@
 menu-button-cpu-match-1 = CPU Match 1
 menu-button-cpu-match-2 = CPU Match 2
+menu-button-difficulty-easy = Difficulty: Easy
+menu-button-difficulty-normal = Difficulty: Normal
+menu-button-difficulty-hard = Difficulty: Hard
 menu-button-random-match = Random Match
 menu-button-deck-edit = Deck Edit
 
 deck-label-collection = Collection
+deck-label-search = Search
 deck-label-deck = Deck
 deck-button-quit = Quit
+deck-stats-types = Creatures: { $creatures } / Hexes: { $hexes }
+deck-stats-curve = Costs: { $curve }
+deck-stats-shards = Shards per charge: { $shards }
+deck-stats-free-cast = Cost 0 card by turn { $turn }: { $odds }%
 
 loading-message-finding-player = Finding a player...
 loading-button-cancel = Cancel
@@ -15,10 +23,12 @@ attack-button = Attack ({ $attackers })
 block-button = Block ({ $blockers })
 continue-button = Continue
 end-turn-button = End Turn
+mulligan-button = Mulligan
 
 your-turn = Your Turn
 opponents-turn = Opponent's Turn
 
+phase-mulligan = Mulligan
 phase-standby = Standby Phase
 phase-draw = Draw Phase
 phase-charge = Charge Phase
@@ -31,15 +41,18 @@ result-victory = Victory!
     .reason-concede = Your opponent has conceded.
     .reason-deck-out = Your opponent could not draw a card from their deck.
     .reason-life-zero = Your opponent's life is zero.
+    .reason-timeout = Your opponent has run out of time.
 result-defeat = Defeat...
     .reason-concede = You have conceded.
     .reason-deck-out = You could not draw a card from your deck.
     .reason-life-zero = Your life is zero.
+    .reason-timeout = You have run out of time.
 result-draw = Draw
     .reason-simultaneous-end = Both players fulfill the winning or losing condition at the same time.
 
 message-discard-excess-cards = Discard cards until you have { $maxHandSize } cards in your hand.
 message-discard = Discard a card.
+message-mulligan = Keep your hand or mulligan.
 
 ability-toxic = Toxic
     .description = After the battle, destroy the creature that battled with this creature.
@@ -49,11 +62,21 @@ ability-stealth = Stealth
     .description = This creature cannot be targeted by card effects.
 ability-devour = Devour
     .description = Creatures destroyed by this creature do not generate a shard.
+ability-defender = Defender
 
 error-failed-to-connect-server = Failed to connect to the server.
 error-client-version-outdated = Your client needs to be updated.　Supported client version: { $requirement }
 error-server-version-outdated = The server needs to be updated. Supported client version: { $requirement }
 error-invalid-deck = Your deck does not meet the regulations.
+error-invalid-player-count = A game cannot be played by { $players } players.
+error-player-unavailable = A player left the room before the game started.
+error-deck-too-few-cards = The deck has { $size } cards but needs at least { $min }.
+error-deck-too-many-cards = The deck has { $size } cards but can have at most { $max }.
+error-deck-too-many-copies = { $card }: { $count } copies, but at most { $max } are allowed.
+error-deck-unknown-card = { $card } is not a known card.
+error-deck-card-banned = { $card } is banned.
+error-deck-card-limited = { $card } is limited to { $max } copies.
+error-deck-token-in-deck = { $card } is a token and cannot be put in a deck.
 
 log-game-started = The game has started.
 
@@ -65,6 +88,24 @@ log-game-ended = {$winner ->
 
 log-game-draw = The game ended in a draw.
 
+log-player-eliminated = {$player ->
+    [you] You have
+    [opponent] Your opponent has
+   *[other] {$player} has
+} been eliminated.
+
+log-mulligan-taken = {$player ->
+    [you] You returned
+    [opponent] Your opponent returned
+   *[other] {$player} returned
+} {$amount} cards to the deck.
+
+log-hand-kept = {$player ->
+    [you] You kept
+    [opponent] Your opponent kept
+   *[other] {$player} kept
+} the hand.
+
 log-turn-changed = {$player ->
     [you] Your turn. (Turn {$turn})
     [opponent] Opponent's turn. (Turn {$turn})
@@ -72,6 +113,7 @@ log-turn-changed = {$player ->
 }
 
 log-phase-changed = {$phase ->
+    [mulligan] Mulligan started.
     [standby] Standby Phase started.
     [draw] Draw Phase started.
     [main] Main Phase started.
@@ -1,11 +1,19 @@
 menu-button-cpu-match-1 = CPU対戦1
 menu-button-cpu-match-2 = CPU対戦2
+menu-button-difficulty-easy = 難易度: かんたん
+menu-button-difficulty-normal = 難易度: ふつう
+menu-button-difficulty-hard = 難易度: むずかしい
 menu-button-random-match = ランダム対戦
 menu-button-deck-edit = デッキ編集
 
 deck-label-collection = コレクション
+deck-label-search = 検索
 deck-label-deck = デッキ
 deck-button-quit = 終了
+deck-stats-types = クリーチャー: { $creatures } / ヘクス: { $hexes }
+deck-stats-curve = コスト: { $curve }
+deck-stats-shards = チャージ1回あたりのカケラ: { $shards }
+deck-stats-free-cast = { $turn }ターン目までにコスト0のカードを引く確率: { $odds }%
 
 loading-message-finding-player = プレイヤーを探しています...
 loading-button-cancel = キャンセル
@@ -13,6 +21,7 @@ loading-button-cancel = キャンセル
 your-turn = Your Turn
 opponents-turn = Opponent's Turn
 
+phase-mulligan = マリガン
 phase-standby = スタンバイフェイズ
 phase-draw = ドローフェイズ
 phase-charge = チャージフェイズ
@@ -26,20 +35,24 @@ attack-button = { $attackers }体で攻撃
 block-button = { $blockers }体でブロック
 continue-button = 続行
 end-turn-button = ターン終了
+mulligan-button = マリガン
 
 result-victory = Victory!
     .reason-concede = 相手が降参しました。
     .reason-deck-out = 相手がデッキからカードを引けなくなりました。
     .reason-life-zero = 相手のライフが0になりました。
+    .reason-timeout = 相手の持ち時間がなくなりました。
 result-defeat = Defeat...
     .reason-concede = あなたが降参しました。
     .reason-deck-out = あなたがデッキからカードを引けなくなりました。
     .reason-life-zero = あなたのライフが0になりました。
+    .reason-timeout = あなたの持ち時間がなくなりました。
 result-draw = Draw
     .reason-simultaneous-end = 両プレイヤーが同時に勝利条件または敗北条件を満たしました。
 
 message-discard-excess-cards = 手札が{ $maxHandSize }枚になるまでカードを捨ててください。
 message-discard = カードを捨ててください。
+message-mulligan = 手札をキープするかマリガンしてください。
 
 ability-toxic = 有毒
     .description = 戦闘後、このクリーチャーとバトルしたクリーチャーを破壊する。
@@ -49,11 +62,21 @@ ability-stealth = ステルス
     .description = このクリーチャーはカード効果の対象に指定できない。
 ability-devour = 貪食
     .description = このクリーチャーによって破壊されたクリーチャーはカケラを生成しない。
+ability-defender = 防御
 
 error-failed-to-connect-server = サーバーに接続できませんでした。
 error-client-version-outdated = クライアントのアップデートが必要です。対応クライアントバージョン: { $requirement }
 error-server-version-outdated = サーバーのアップデートが必要です。対応クライアントバージョン: { $requirement }
 error-invalid-deck = デッキがレギュレーションに適合していません。
+error-invalid-player-count = { $players }人ではゲームをプレイできません。
+error-player-unavailable = ゲーム開始前にプレイヤーが退出しました。
+error-deck-too-few-cards = デッキは{ $size }枚です。{ $min }枚以上必要です。
+error-deck-too-many-cards = デッキは{ $size }枚です。{ $max }枚まで入れられます。
+error-deck-too-many-copies = { $card }が{ $count }枚入っています。{ $max }枚まで入れられます。
+error-deck-unknown-card = { $card }は存在しないカードです。
+error-deck-card-banned = { $card }は使用できません。
+error-deck-card-limited = { $card }は{ $max }枚までに制限されています。
+error-deck-token-in-deck = { $card }はトークンのためデッキに入れられません。
 
 log-game-started = ゲームが開始されました。
 
@@ -65,6 +88,24 @@ log-game-ended = {$winner ->
 
 log-game-draw = ゲームは引き分けです。
 
+log-player-eliminated = {$player ->
+    [you] あなた
+    [opponent] 対戦相手
+   *[other] {$player}
+}が脱落しました。
+
+log-mulligan-taken = {$player ->
+    [you] あなた
+    [opponent] 対戦相手
+   *[other] {$player}
+}がカードを{$amount}枚デッキに戻しました。
+
+log-hand-kept = {$player ->
+    [you] あなた
+    [opponent] 対戦相手
+   *[other] {$player}
+}が手札をキープしました。
+
 log-turn-changed = {$player ->
     [you] あなたのターンです。(ターン{$turn})
     [opponent] 相手のターンです。(ターン{$turn})
@@ -72,6 +113,7 @@ log-turn-changed = {$player ->
 }
 
 log-phase-changed = {$phase ->
+    [mulligan] マリガンを開始します。
     [standby] スタンバイフェイズを開始します。
     [draw] ドローフェイズを開始します。
     [main] メインフェイズを開始します。
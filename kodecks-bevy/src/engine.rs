@@ -5,6 +5,7 @@ use futures::{
     SinkExt, StreamExt,
 };
 use kodecks_engine::{
+    draft::start_draft,
     game::start_game,
     message::{Command, Input, Output},
     Connection,
@@ -31,13 +32,21 @@ impl Default for LocalEngine {
 
 async fn start_task(mut command_recv: Receiver<Input>, event_send: Sender<Output>) {
     let mut sender = None;
+    let mut draft_sender = None;
     while let Some(input) = command_recv.next().await {
         match input {
             Input::Command(Command::CreateGame { log_id, profile }) => {
                 let (command_sender, receiver) = mpsc::channel(256);
                 sender = Some(command_sender);
                 AsyncComputeTaskPool::get()
-                    .spawn(start_game(log_id, profile, receiver, event_send.clone()))
+                    .spawn(start_game(log_id, *profile, receiver, event_send.clone()))
+                    .detach();
+            }
+            Input::Command(Command::CreateDraft { config }) => {
+                let (command_sender, receiver) = mpsc::channel(256);
+                draft_sender = Some(command_sender);
+                AsyncComputeTaskPool::get()
+                    .spawn(start_draft(config, receiver, event_send.clone()))
                     .detach();
             }
             Input::GameCommand(session_command) => {
@@ -45,6 +54,11 @@ async fn start_task(mut command_recv: Receiver<Input>, event_send: Sender<Output
                     sender.send(session_command).await.unwrap();
                 }
             }
+            Input::DraftCommand(draft_command) => {
+                if let Some(sender) = &mut draft_sender {
+                    sender.send(draft_command).await.unwrap();
+                }
+            }
             _ => {}
         }
     }
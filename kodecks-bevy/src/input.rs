@@ -15,6 +15,7 @@ pub enum UserAction {
     Attack,
     AllAttack,
     Block,
+    NextTarget,
     Continue,
     Concede,
     ToggleDebugger,
@@ -24,6 +25,7 @@ fn init(mut commands: Commands) {
     let mut input_map = InputMap::new([
         (UserAction::Attack, KeyCode::KeyA),
         (UserAction::Block, KeyCode::KeyA),
+        (UserAction::NextTarget, KeyCode::Tab),
         (UserAction::Continue, KeyCode::Space),
     ]);
     input_map.insert(
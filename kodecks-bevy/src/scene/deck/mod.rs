@@ -7,16 +7,23 @@ use crate::{assets::AssetServerExt, save_data::SaveData};
 use bevy::{
     color::palettes::css,
     ecs::system::SystemParam,
-    input::mouse::{MouseScrollUnit, MouseWheel},
+    input::{
+        keyboard::{Key, KeyboardInput},
+        mouse::{MouseScrollUnit, MouseWheel},
+    },
     prelude::*,
     text::BreakLineOn,
     ui::RelativeCursorPosition,
 };
 use bevy_mod_picking::prelude::*;
+use fluent_bundle::FluentArgs;
+use fluent_content::Request;
 use kodecks::{
     archetype::ArchetypeId,
     card::{CardEntry, CardSnapshot},
-    deck::DeckItem,
+    deck::{AnalysisConfig, DeckAnalysis, DeckItem},
+    pool::CardPool,
+    regulation::Regulation,
 };
 use kodecks_catalog::CATALOG;
 use std::collections::BTreeMap;
@@ -26,6 +33,7 @@ pub struct DeckPlugin;
 impl Plugin for DeckPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UIState>()
+            .init_resource::<CollectionFilter>()
             .add_event::<DeckEvent>()
             .add_event::<UiEvent>()
             .add_systems(OnEnter(GlobalState::DeckMain), init)
@@ -34,10 +42,14 @@ impl Plugin for DeckPlugin {
                 Update,
                 (
                     mouse_scroll,
+                    type_search_query,
+                    filter_collection.run_if(resource_exists_and_changed::<CollectionFilter>),
                     handle_deck_event.run_if(on_event::<DeckEvent>()),
                     handle_ui_event.run_if(on_event::<UiEvent>()),
                     (update_card_image, update_card_info)
                         .run_if(resource_exists_and_changed::<UIState>),
+                    (update_deck_violations, update_deck_stats)
+                        .run_if(resource_exists_and_changed::<SaveData>),
                 )
                     .run_if(in_state(GlobalState::DeckMain)),
             );
@@ -57,12 +69,21 @@ struct UiRoot;
 #[derive(Component)]
 struct CollectionItem(ArchetypeId);
 
+#[derive(Component)]
+struct SearchBox;
+
 #[derive(Component)]
 struct UiDeckItem(ArchetypeId);
 
 #[derive(Component)]
 struct DeckList;
 
+#[derive(Component)]
+struct DeckViolations;
+
+#[derive(Component)]
+struct DeckStats;
+
 #[derive(Component)]
 struct KeywordList;
 
@@ -72,6 +93,7 @@ struct Keyword;
 #[derive(Debug, Event)]
 enum UiEvent {
     CardHovered(ArchetypeId),
+    FocusSearch,
     Quit,
 }
 
@@ -86,6 +108,16 @@ pub struct UIState {
     pub selected_card: Option<UICardInfo>,
 }
 
+/// The search query typed in the deck builder to filter the collection.
+///
+/// Key presses only edit the query while the search box is focused,
+/// either by clicking it or by pressing `/`.
+#[derive(Debug, Resource, Default)]
+pub struct CollectionFilter {
+    pub query: String,
+    pub focused: bool,
+}
+
 fn init(
     mut commands: Commands,
     translator: Res<Translator>,
@@ -93,6 +125,8 @@ fn init(
     asset_server: Res<AssetServer>,
     catalog: Res<Catalog>,
 ) {
+    commands.insert_resource(CollectionFilter::default());
+
     let slicer = TextureSlicer {
         border: BorderRect::square(2.0),
         center_scale_mode: SliceScaleMode::Stretch,
@@ -286,6 +320,24 @@ fn init(
                         Label,
                     ));
 
+                    parent.spawn((
+                        TextBundle::from_section(
+                            format!("{}: ", translator.get("deck-label-search")),
+                            translator.style(TextPurpose::Button),
+                        )
+                        .with_style(Style {
+                            margin: UiRect::all(Val::Px(5.)),
+                            ..default()
+                        }),
+                        SearchBox,
+                        Label,
+                        On::<Pointer<Click>>::commands_mut(move |_, commands| {
+                            commands.add(move |w: &mut World| {
+                                w.send_event(UiEvent::FocusSearch);
+                            });
+                        }),
+                    ));
+
                     parent
                         .spawn(NodeBundle {
                             style: Style {
@@ -476,6 +528,35 @@ fn init(
                         Label,
                     ));
 
+                    parent.spawn((
+                        TextBundle::from_section(
+                            deck_violations(&save_data, &catalog, &translator),
+                            TextStyle {
+                                color: css::ORANGE_RED.into(),
+                                ..translator.style(TextPurpose::CardText)
+                            },
+                        )
+                        .with_style(Style {
+                            margin: UiRect::horizontal(Val::Px(5.)),
+                            ..default()
+                        }),
+                        DeckViolations,
+                        Label,
+                    ));
+
+                    parent.spawn((
+                        TextBundle::from_section(
+                            deck_stats(&save_data, &catalog, &translator),
+                            translator.style(TextPurpose::CardText),
+                        )
+                        .with_style(Style {
+                            margin: UiRect::horizontal(Val::Px(5.)),
+                            ..default()
+                        }),
+                        DeckStats,
+                        Label,
+                    ));
+
                     parent
                         .spawn(NodeBundle {
                             style: Style {
@@ -702,11 +783,68 @@ fn mouse_scroll(
     }
 }
 
+fn type_search_query(mut events: EventReader<KeyboardInput>, mut filter: ResMut<CollectionFilter>) {
+    for event in events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+        if !filter.focused {
+            if matches!(&event.logical_key, Key::Character(text) if text == "/") {
+                filter.focused = true;
+            }
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => filter.query.push_str(text),
+            Key::Space => filter.query.push(' '),
+            Key::Backspace => {
+                filter.query.pop();
+            }
+            Key::Enter => filter.focused = false,
+            Key::Escape => {
+                filter.query.clear();
+                filter.focused = false;
+            }
+            _ => {}
+        }
+    }
+}
+
+fn filter_collection(
+    filter: Res<CollectionFilter>,
+    catalog: Res<Catalog>,
+    translator: Res<Translator>,
+    mut items: Query<(&CollectionItem, &mut Style), Without<Text>>,
+    mut search_box: Query<&mut Text, With<SearchBox>>,
+) {
+    let cursor = if filter.focused { "_" } else { "" };
+    for mut text in search_box.iter_mut() {
+        text.sections[0].value = format!(
+            "{}: {}{}",
+            translator.get("deck-label-search"),
+            filter.query,
+            cursor
+        );
+    }
+    // Keep the current list while the query is incomplete, e.g. `cost>`.
+    let Ok(results) = catalog.search(&filter.query) else {
+        return;
+    };
+    for (item, mut style) in items.iter_mut() {
+        style.display = if results.iter().any(|archetype| archetype.id == item.0) {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
 fn handle_ui_event(
     mut event: EventReader<UiEvent>,
     mut state: ResMut<UIState>,
     mut next_state: ResMut<NextState<GlobalState>>,
     catalog: Res<Catalog>,
+    mut filter: ResMut<CollectionFilter>,
 ) {
     for event in event.read() {
         match event {
@@ -714,6 +852,9 @@ fn handle_ui_event(
                 let archetype = &catalog[*id];
                 state.selected_card = Some(UICardInfo::new(CardSnapshot::new(archetype)));
             }
+            UiEvent::FocusSearch => {
+                filter.focused = true;
+            }
             UiEvent::Quit => {
                 next_state.set(GlobalState::MenuMain);
             }
@@ -887,6 +1028,92 @@ fn handle_deck_event(
     }
 }
 
+fn deck_violations(save_data: &SaveData, catalog: &Catalog, translator: &Translator) -> String {
+    let Some(deck) = save_data.decks.list.first() else {
+        return String::new();
+    };
+    Regulation::STANDARD
+        .verify_with_pool(deck, &CardPool::default(), catalog)
+        .iter()
+        .map(|violation| translator.deck_violation(violation, catalog))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn update_deck_violations(
+    save_data: Res<SaveData>,
+    catalog: Res<Catalog>,
+    translator: Res<Translator>,
+    mut query: Query<&mut Text, With<DeckViolations>>,
+) {
+    for mut text in query.iter_mut() {
+        text.sections[0].value = deck_violations(&save_data, &catalog, &translator);
+    }
+}
+
+/// The number of turns whose draw odds are shown in the deck builder.
+const DECK_STATS_TURNS: u8 = 3;
+
+fn deck_stats(save_data: &SaveData, catalog: &Catalog, translator: &Translator) -> String {
+    let Some(deck) = save_data.decks.list.first() else {
+        return String::new();
+    };
+    let config = AnalysisConfig {
+        turns: DECK_STATS_TURNS,
+        ..AnalysisConfig::new(&Regulation::STANDARD)
+    };
+    let analysis = DeckAnalysis::new(deck, catalog, &config);
+
+    let mut args = FluentArgs::new();
+    args.set("creatures", analysis.creatures);
+    args.set("hexes", analysis.hexes);
+    let types = translator.get(Request::new("deck-stats-types").args(&args));
+
+    let curve = analysis
+        .cost_curve
+        .iter()
+        .map(|(cost, count)| format!("{cost}:{count}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut args = FluentArgs::new();
+    args.set("curve", curve);
+    let curve = translator.get(Request::new("deck-stats-curve").args(&args));
+
+    let mut args = FluentArgs::new();
+    args.set("shards", format!("{:.1}", analysis.expected_shards()));
+    let shards = translator.get(Request::new("deck-stats-shards").args(&args));
+
+    let odds = analysis
+        .free_cast_odds
+        .iter()
+        .enumerate()
+        .map(|(turn, odds)| {
+            let mut args = FluentArgs::new();
+            args.set("turn", turn + 1);
+            args.set("odds", format!("{:.0}", odds * 100.0));
+            translator
+                .get(Request::new("deck-stats-free-cast").args(&args))
+                .to_string()
+        });
+
+    [types.to_string(), curve.to_string(), shards.to_string()]
+        .into_iter()
+        .chain(odds)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn update_deck_stats(
+    save_data: Res<SaveData>,
+    catalog: Res<Catalog>,
+    translator: Res<Translator>,
+    mut query: Query<&mut Text, With<DeckStats>>,
+) {
+    for mut text in query.iter_mut() {
+        text.sections[0].value = deck_stats(&save_data, &catalog, &translator);
+    }
+}
+
 fn update_card_image(
     state: Res<UIState>,
     mut image_query: Query<(&CardInfo, &mut UiImage)>,
@@ -6,7 +6,8 @@ use kodecks::{
     field::{FieldBattleState, FieldState},
     id::{ObjectId, TimedCardId, TimedObjectId},
     phase::Phase,
-    player::Zone,
+    player::{PlayerItem, Zone},
+    target::Target,
     zone::ZoneKind,
 };
 use std::{f32::consts::PI, ops::Deref};
@@ -67,11 +68,15 @@ pub struct Board {
     pub player_field: Vec<(TimedObjectId, FieldState)>,
     pub opponent_hand: Vec<TimedObjectId>,
     pub opponent_field: Vec<(TimedObjectId, FieldState)>,
+    /// The opponent shown on the board, which is the attack target if one is selected.
+    pub opponent: u8,
 
+    attack_target: Option<u8>,
     attackers: Vec<TimedObjectId>,
     blocking_pairs: Vec<(TimedObjectId, TimedObjectId)>,
     temp_attackers: Vec<TimedObjectId>,
     temp_blocking_pairs: Vec<(TimedObjectId, TimedObjectId)>,
+    mulligan_cards: Vec<TimedObjectId>,
 }
 
 impl Board {
@@ -92,6 +97,27 @@ impl Board {
         )
     }
 
+    /// Selects the next player in `targets` as the attack target.
+    pub fn cycle_attack_target(&mut self, targets: &[Target]) {
+        let players = targets
+            .iter()
+            .filter_map(|target| match target {
+                Target::Player(player) => Some(*player),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let next = self
+            .attack_target
+            .and_then(|current| players.iter().position(|&player| player == current))
+            .map(|index| (index + 1) % players.len())
+            .unwrap_or_default();
+        self.attack_target = players.get(next).copied();
+    }
+
+    pub fn attack_target(&self) -> Option<Target> {
+        self.attack_target.map(Target::Player)
+    }
+
     pub fn toggle_blocker(&mut self, blocker: TimedObjectId, attacker: Option<TimedObjectId>) {
         self.temp_blocking_pairs.retain(|(_, b)| *b != blocker);
         if let Some(attacker) = attacker {
@@ -103,6 +129,19 @@ impl Board {
         self.update_battle_layout();
     }
 
+    pub fn toggle_mulligan_card(&mut self, card: TimedObjectId) {
+        if self.mulligan_cards.contains(&card) {
+            self.mulligan_cards.retain(|&c| c != card);
+        } else {
+            self.mulligan_cards.push(card);
+        }
+    }
+
+    /// Returns the cards chosen to be put on the bottom of the deck.
+    pub fn mulligan_cards(&self) -> impl Iterator<Item = &TimedObjectId> {
+        self.mulligan_cards.iter()
+    }
+
     pub fn clear_battle(&mut self) {
         self.temp_attackers.clear();
         self.temp_blocking_pairs.clear();
@@ -119,7 +158,18 @@ impl Board {
 
     pub fn update(&mut self, env: &LocalEnvironment) {
         let player = env.players.get(env.player).unwrap();
-        let opponent = env.players.next_player(env.player).unwrap();
+        self.attack_target = self.attack_target.filter(|&target| {
+            target != env.player
+                && env
+                    .players
+                    .get(target)
+                    .is_ok_and(|player| !player.is_eliminated())
+        });
+        let opponent = match self.attack_target {
+            Some(target) => env.players.get(target).unwrap(),
+            None => env.players.next_player(env.player).unwrap(),
+        };
+        self.opponent = opponent.id;
 
         self.player_hand = player.hand.iter().map(|card| card.timed_id()).collect();
 
@@ -187,6 +237,12 @@ impl Board {
         self.temp_attackers
             .retain(|attacker| self.player_field.iter().any(|(id, _)| id == attacker));
 
+        if !matches!(env.phase, Phase::Mulligan) {
+            self.mulligan_cards.clear();
+        }
+        self.mulligan_cards
+            .retain(|card| self.player_hand.contains(card));
+
         let battle = matches!(env.phase, Phase::Block | Phase::Battle);
         if !battle {
             self.clear_battle();
@@ -2,7 +2,7 @@ use super::{
     board::{AvailableActionList, Board, Environment},
     log::translate_log,
     main::AnimationState,
-    server::{SendCommand, ServerEvent},
+    server::{RequestResync, SendCommand, ServerEvent},
 };
 use crate::{
     save_data::SaveData,
@@ -15,6 +15,7 @@ use bevy::{
 use kodecks::{
     action::{Action, AvailableAction},
     archetype::ArchetypeId,
+    clock::PlayerClock,
     id::ObjectId,
     log::GameLog,
     message::MessageDialog,
@@ -33,6 +34,7 @@ impl Plugin for EventPlugin {
             .add_event::<ShardUpdated>()
             .add_event::<LifeUpdated>()
             .add_event::<TurnChanged>()
+            .add_event::<ClocksUpdated>()
             .init_state::<AssetState>()
             .add_systems(
                 Update,
@@ -71,6 +73,10 @@ pub struct ShardUpdated;
 #[derive(Event)]
 pub struct TurnChanged(pub u8);
 
+/// Remaining time of the players when the game has a time control.
+#[derive(Event)]
+pub struct ClocksUpdated(pub Vec<PlayerClock>);
+
 #[derive(Event)]
 pub struct LifeUpdated {
     pub player: u8,
@@ -217,11 +223,21 @@ pub struct ServerEvents<'w> {
     life: EventWriter<'w, LifeUpdated>,
     shard: EventWriter<'w, ShardUpdated>,
     turn: EventWriter<'w, TurnChanged>,
+    clocks: EventWriter<'w, ClocksUpdated>,
     list: Res<'w, AvailableActionList>,
     translator: Res<'w, Translator>,
     catalog: Res<'w, Catalog>,
 }
 
+/// Resync requests sent for a single timestamp before giving up.
+const MAX_RESYNC_ATTEMPTS: u32 = 3;
+
+#[derive(Default)]
+struct ResyncAttempts {
+    timestamp: u32,
+    count: u32,
+}
+
 fn recv_server_events(
     mut commands: Commands,
     mut board: ResMut<Board>,
@@ -230,6 +246,7 @@ fn recv_server_events(
     state: Res<State<GlobalState>>,
     asset_state: Res<State<AssetState>>,
     time: Res<Time>,
+    mut resync: Local<ResyncAttempts>,
 ) {
     events.server.time.tick(time.delta());
     if *asset_state == AssetState::Loading {
@@ -331,6 +348,32 @@ fn recv_server_events(
             }
         }
 
+        if !event.env.clocks.is_empty() {
+            events.clocks.send(ClocksUpdated(event.env.clocks.clone()));
+        }
+
+        // Compare the view maintained by the client with the server state it
+        // claims to be at, before it is replaced by the update.
+        if let Some(view) = env.filter(|view| view.timestamp == event.env.timestamp) {
+            let hash = view.state_hash();
+            if hash != event.hash {
+                warn!(
+                    "State hash mismatch at {}: {:016x} != {:016x}",
+                    view.timestamp, hash, event.hash
+                );
+                if resync.timestamp != view.timestamp {
+                    *resync = ResyncAttempts {
+                        timestamp: view.timestamp,
+                        count: 0,
+                    };
+                }
+                if resync.count < MAX_RESYNC_ATTEMPTS {
+                    resync.count += 1;
+                    commands.add(RequestResync);
+                }
+            }
+        }
+
         let mut env = event.env.clone();
         let mut available_actions = event.available_actions.clone();
         if let Some(action) = next_action {
@@ -7,6 +7,7 @@ use crate::{
     assets::AssetServerExt,
     save_data,
     scene::{
+        card::Catalog,
         spinner::SpinnerState,
         translator::{TextPurpose, Translator},
         GlobalState,
@@ -232,15 +233,23 @@ fn update_loading_message(
     mut query: Query<&mut Text, With<LoadingMessage>>,
     mut button_query: Query<&mut Visibility, With<UiButton>>,
     translator: Res<Translator>,
+    catalog: Res<Catalog>,
     loading_state: Res<State<GameLoadingState>>,
 ) {
     let mut text = query.single_mut();
-    let message = match loading_state.get() {
+    let mut message = match loading_state.get() {
         GameLoadingState::RandomMatch => translator.get("loading-message-finding-player"),
         GameLoadingState::Error(error) => translator.get(error.clone()),
         _ => "".into(),
-    };
-    text.sections[0].value = message.to_string();
+    }
+    .to_string();
+    if let GameLoadingState::Error(Error::InvalidDeck { violations }) = loading_state.get() {
+        for violation in violations {
+            message.push('\n');
+            message.push_str(&translator.deck_violation(violation, &catalog));
+        }
+    }
+    text.sections[0].value = message;
 
     let visible = match loading_state.get() {
         GameLoadingState::RandomMatch | GameLoadingState::Error(_) => Visibility::Visible,
@@ -258,7 +267,10 @@ fn init_game_mode(
     save_data: Res<save_data::SaveData>,
 ) {
     match &mode.kind {
-        GameModeKind::BotMatch { bot_deck } => {
+        GameModeKind::BotMatch {
+            bot_deck,
+            difficulty,
+        } => {
             let mut hasher = fnv::FnvHasher::default();
             save_data.hash(&mut hasher);
             let profile = GameProfile {
@@ -273,8 +285,13 @@ fn init_game_mode(
                         deck: bot_deck.clone(),
                     },
                 ],
-                bots: vec![BotConfig { player: 1 }],
+                bots: vec![BotConfig {
+                    player: 1,
+                    difficulty: *difficulty,
+                    ..Default::default()
+                }],
                 rng_seed: Some(hasher.finish()),
+                sealed: None,
             };
 
             let log_id = format!(
@@ -283,7 +300,10 @@ fn init_game_mode(
                 nanoid::nanoid!()
             );
             let mut conn = ServerConnection::new_local();
-            conn.send(Input::Command(Command::CreateGame { log_id, profile }));
+            conn.send(Input::Command(Command::CreateGame {
+                log_id,
+                profile: Box::new(profile),
+            }));
             commands.insert_resource(conn);
 
             next_loading_state.set(GameLoadingState::BotMatch);
@@ -296,6 +316,7 @@ fn init_game_mode(
                     regulation: mode.regulation.clone(),
                     card_pool: mode.card_pool.clone(),
                     room_type: RoomType::RandomMatch,
+                    ..Default::default()
                 },
                 host_player: PlayerConfig {
                     deck: mode.player_deck.clone(),
@@ -30,9 +30,43 @@ pub fn translate_log<'a>(
                 );
                 "log-game-ended"
             } else {
-                "log-game-ended-draw"
+                "log-game-draw"
             }
         }
+        GameLog::MulliganTaken { player, amount } => {
+            args.set(
+                "player",
+                if *player == env.player {
+                    "you"
+                } else {
+                    "opponent"
+                },
+            );
+            args.set("amount", amount);
+            "log-mulligan-taken"
+        }
+        GameLog::HandKept { player, .. } => {
+            args.set(
+                "player",
+                if *player == env.player {
+                    "you"
+                } else {
+                    "opponent"
+                },
+            );
+            "log-hand-kept"
+        }
+        GameLog::PlayerEliminated { player, .. } => {
+            args.set(
+                "player",
+                if *player == env.player {
+                    "you"
+                } else {
+                    "opponent"
+                },
+            );
+            "log-player-eliminated"
+        }
         GameLog::TurnChanged { player, turn } => {
             args.set(
                 "player",
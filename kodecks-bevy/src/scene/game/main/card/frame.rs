@@ -146,9 +146,10 @@ pub fn update_frame_overlay(
         let selectable = list.selectable_cards().iter().any(|card| card.id == id);
         if *frame == CardFrame::Shadow {
             let castable = list.castable_cards().iter().any(|card| card.id == id);
+            let mulligan = board.mulligan_cards().any(|card| card.id == id);
             *material = if castable {
                 assets.card_active.clone()
-            } else if selectable {
+            } else if selectable || mulligan {
                 assets.card_select.clone()
             } else {
                 assets.card_shadow.clone()
@@ -0,0 +1,135 @@
+use crate::scene::{
+    game::{
+        board::{Board, Environment},
+        event::ClocksUpdated,
+    },
+    translator::{TextPurpose, Translator},
+    GlobalState,
+};
+use bevy::{prelude::*, time::Stopwatch};
+use bevy_mod_picking::prelude::*;
+use kodecks::clock::PlayerClock;
+
+pub struct ClockPlugin;
+
+impl Plugin for ClockPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GlobalState::GameLoading), init)
+            .add_systems(OnEnter(GlobalState::GameCleanup), cleanup)
+            .add_systems(
+                Update,
+                (
+                    sync_clocks.run_if(on_event::<ClocksUpdated>()),
+                    update_clocks,
+                )
+                    .chain()
+                    .run_if(in_state(GlobalState::GameMain)),
+            );
+    }
+}
+
+/// The last clocks received from the server and the time elapsed since.
+#[derive(Resource, Default)]
+struct Clocks {
+    clocks: Vec<PlayerClock>,
+    elapsed: Stopwatch,
+}
+
+impl Clocks {
+    fn remaining(&self, player: u8) -> Option<u32> {
+        self.clocks
+            .iter()
+            .find(|clock| clock.player == player)
+            .map(|clock| {
+                if clock.running {
+                    clock.remaining.saturating_sub(self.elapsed.elapsed())
+                } else {
+                    clock.remaining
+                }
+            })
+            .map(|remaining| remaining.as_secs() as u32)
+    }
+}
+
+#[derive(Component)]
+struct UiRoot;
+
+#[derive(Component)]
+enum ClockText {
+    Player,
+    Opponent,
+}
+
+fn init(mut commands: Commands, translator: Res<Translator>) {
+    commands.insert_resource(Clocks::default());
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(20.),
+                    height: Val::Percent(100.0),
+                    justify_content: JustifyContent::SpaceBetween,
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::vertical(Val::Percent(20.)),
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            Pickable::IGNORE,
+            UiRoot,
+        ))
+        .with_children(|parent| {
+            for text in [ClockText::Opponent, ClockText::Player] {
+                parent.spawn((
+                    TextBundle::from_section("", translator.style(TextPurpose::Button)),
+                    Pickable::IGNORE,
+                    Label,
+                    text,
+                ));
+            }
+        });
+}
+
+fn cleanup(mut commands: Commands, query: Query<Entity, With<UiRoot>>) {
+    commands.remove_resource::<Clocks>();
+    query.iter().for_each(|entity| {
+        commands.entity(entity).despawn_recursive();
+    });
+}
+
+fn sync_clocks(mut clocks: ResMut<Clocks>, mut events: EventReader<ClocksUpdated>) {
+    if let Some(ClocksUpdated(updated)) = events.read().last() {
+        clocks.clocks.clone_from(updated);
+        clocks.elapsed.reset();
+    }
+}
+
+fn update_clocks(
+    env: Res<Environment>,
+    board: Res<Board>,
+    time: Res<Time>,
+    mut clocks: ResMut<Clocks>,
+    mut root_query: Query<&mut Visibility, With<UiRoot>>,
+    mut text_query: Query<(&mut Text, &ClockText)>,
+) {
+    clocks.elapsed.tick(time.delta());
+    for mut visibility in root_query.iter_mut() {
+        *visibility = if clocks.clocks.is_empty() {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+    for (mut text, clock) in text_query.iter_mut() {
+        let player = match clock {
+            ClockText::Player => env.player,
+            ClockText::Opponent => board.opponent,
+        };
+        text.sections[0].value = clocks
+            .remaining(player)
+            .map(|secs| format!("{}:{:02}", secs / 60, secs % 60))
+            .unwrap_or_default();
+    }
+}
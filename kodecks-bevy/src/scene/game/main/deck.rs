@@ -3,7 +3,7 @@ use crate::{
     assets::AssetServerExt,
     scene::{
         card::{Catalog, CARD_HEIGHT, CARD_WIDTH},
-        game::board::Environment,
+        game::board::{Board, Environment},
         GlobalState,
     },
 };
@@ -194,6 +194,7 @@ fn update_decks(env: Res<Environment>, mut query: Query<(&Deck, &mut Transform,
 
 fn update_graveyards(
     env: Res<Environment>,
+    board: Res<Board>,
     mut query: Query<(
         &Graveyard,
         &mut Transform,
@@ -207,7 +208,7 @@ fn update_graveyards(
     for (graveyard, mut transform, mut material, mut visibility) in query.iter_mut() {
         let player = match graveyard {
             Graveyard::Player => env.players.get(env.player).unwrap(),
-            Graveyard::Opponent => env.players.next_player(env.player).unwrap(),
+            Graveyard::Opponent => env.players.get(board.opponent).unwrap(),
         };
 
         let len = player.graveyard.len() + 1;
@@ -1,6 +1,7 @@
 use super::ui::ActionButton;
 use crate::input::UserAction;
 use crate::scene::game::board::{AvailableActionList, Board, Environment};
+use crate::scene::game::event::{LifeUpdated, ShardUpdated};
 use crate::scene::game::server::SendCommand;
 use crate::scene::GlobalState;
 use bevy::prelude::*;
@@ -44,6 +45,8 @@ fn handle_player_events(
     mut events: EventReader<PlayerEvent>,
     mut finished: EventWriter<PlayerEventFinished>,
     action_query: Query<&ActionState<UserAction>>,
+    mut life_events: EventWriter<LifeUpdated>,
+    mut shard_events: EventWriter<ShardUpdated>,
 ) {
     let player_event = !events.is_empty();
     let mut action = events.read().find_map(|event| match event {
@@ -55,10 +58,29 @@ fn handle_player_events(
             }),
             ActionButton::Attack(_) => Some(Action::Attack {
                 attackers: board.attackers().copied().collect(),
+                target: board.attack_target(),
+            }),
+            ActionButton::Mulligan => list.iter().find_map(|action| match action {
+                AvailableAction::Mulligan { cards } => {
+                    let selected = board
+                        .mulligan_cards()
+                        .filter(|card| cards.contains(card))
+                        .copied()
+                        .collect::<Vec<_>>();
+                    Some(Action::Mulligan {
+                        cards: if selected.is_empty() {
+                            cards.clone()
+                        } else {
+                            selected
+                        },
+                    })
+                }
+                _ => None,
             }),
             ActionButton::AllAttack => list.iter().find_map(|action| match action {
-                AvailableAction::Attack { attackers } => Some(Action::Attack {
+                AvailableAction::Attack { attackers, .. } => Some(Action::Attack {
                     attackers: attackers.clone(),
+                    target: board.attack_target(),
                 }),
                 _ => None,
             }),
@@ -93,6 +115,8 @@ fn handle_player_events(
                 board.toggle_attacker(*card);
             } else if let Some(card) = list.blockers().iter().find(|blocker| blocker.id == *card) {
                 board.toggle_blocker(*card, None);
+            } else if let Some(card) = list.mulligan_cards().iter().find(|c| c.id == *card) {
+                board.toggle_mulligan_card(*card);
             } else if let Some(card) = list
                 .selectable_cards()
                 .iter()
@@ -116,14 +140,30 @@ fn handle_player_events(
     if action_state.just_pressed(&UserAction::Attack) && board.attackers().next().is_some() {
         action = Some(Action::Attack {
             attackers: board.attackers().copied().collect(),
+            target: board.attack_target(),
         });
     }
     if action_state.just_pressed(&UserAction::AllAttack) {
         if let Some(attackers) = list.iter().find_map(|action| match action {
-            AvailableAction::Attack { attackers } => Some(attackers.clone()),
+            AvailableAction::Attack { attackers, .. } => Some(attackers.clone()),
             _ => None,
         }) {
-            action = Some(Action::Attack { attackers });
+            action = Some(Action::Attack {
+                attackers,
+                target: board.attack_target(),
+            });
+        }
+    }
+    if action_state.just_pressed(&UserAction::NextTarget) {
+        let targets = list.attack_targets();
+        if targets.len() > 1 {
+            board.cycle_attack_target(&targets);
+            board.update(&env);
+            life_events.send(LifeUpdated {
+                player: board.opponent,
+                delta: 0,
+            });
+            shard_events.send(ShardUpdated);
         }
     }
     if action_state.just_pressed(&UserAction::Block) && !list.blockers().is_empty() {
@@ -1,7 +1,10 @@
 use crate::{
     painter::numbers::{Alignment, DrawOptions, NumberPainter},
     scene::{
-        game::{board::Environment, event::LifeUpdated},
+        game::{
+            board::{Board, Environment},
+            event::LifeUpdated,
+        },
         GlobalState,
     },
 };
@@ -90,6 +93,7 @@ fn cleanup(mut commands: Commands, query: OverlayQuery) {
 
 fn update(
     env: Res<Environment>,
+    board: Res<Board>,
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
@@ -98,7 +102,7 @@ fn update(
     mut event_reader: EventReader<LifeUpdated>,
 ) {
     let player_life = env.players.get(env.player).unwrap().stats.life;
-    let opponent_life = env.players.next_player(env.player).unwrap().stats.life;
+    let opponent_life = env.players.get(board.opponent).unwrap().stats.life;
 
     let ((_, mut material, transform), life, delta) = match event_reader.read().next() {
         Some(LifeUpdated { player, delta }) => {
@@ -111,7 +115,7 @@ fn update(
                     player_life,
                     delta,
                 )
-            } else {
+            } else if *player == board.opponent {
                 (
                     query
                         .iter_mut()
@@ -120,6 +124,8 @@ fn update(
                     opponent_life,
                     delta,
                 )
+            } else {
+                return;
             }
         }
         _ => return,
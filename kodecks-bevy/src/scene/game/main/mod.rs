@@ -3,6 +3,7 @@ use bevy::prelude::*;
 mod animation;
 mod battle;
 mod card;
+mod clock;
 mod deck;
 mod dialog;
 mod event;
@@ -32,6 +33,7 @@ impl Plugin for GameMainPlugin {
             .add_plugins(pointer::PointerPlugin)
             .add_plugins(dialog::DialogPlugin)
             .add_plugins(turn::TurnPlugin)
+            .add_plugins(clock::ClockPlugin)
             .add_systems(Startup, setup::setup);
     }
 }
@@ -1,7 +1,10 @@
 use crate::{
     assets::AssetServerExt,
     scene::{
-        game::{board::Environment, event::ShardUpdated},
+        game::{
+            board::{Board, Environment},
+            event::ShardUpdated,
+        },
         GlobalState,
     },
 };
@@ -101,6 +104,7 @@ struct Shard {
 fn update(
     mut commands: Commands,
     env: Res<Environment>,
+    board: Res<Board>,
     assets: Res<ShardAssets>,
     mut query: Query<(
         &mut Shard,
@@ -113,7 +117,7 @@ fn update(
     let mut shards = vec![];
 
     let player_shards = &env.players.get(env.player).unwrap().shards;
-    let opponent_shards = &env.players.next_player(env.player).unwrap().shards;
+    let opponent_shards = &env.players.get(board.opponent).unwrap().shards;
 
     let mut player_count = 0;
     for color in Color::iter_all() {
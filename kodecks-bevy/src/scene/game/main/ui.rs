@@ -66,6 +66,7 @@ pub enum ActionButton {
     Attack(u32),
     Block(u32),
     NoBlock,
+    Mulligan,
     Continue,
 }
 
@@ -88,6 +89,7 @@ impl ActionButton {
                     .into()
             }
             Self::NoBlock => translator.get("continue-button").into(),
+            Self::Mulligan => translator.get("mulligan-button").into(),
             Self::Continue => translator.get("continue-button").into(),
         }
     }
@@ -139,6 +141,7 @@ fn update_action_list(
                     Some(ActionButton::Block(blockers))
                 }
             }
+            AvailableAction::Mulligan { .. } => Some(ActionButton::Mulligan),
             AvailableAction::Continue => Some(ActionButton::Continue),
             AvailableAction::EndTurn => Some(ActionButton::EndTurn),
             _ => None,
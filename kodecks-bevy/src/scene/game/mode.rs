@@ -1,5 +1,5 @@
 use bevy::prelude::Resource;
-use kodecks::{deck::DeckList, pool::CardPool, regulation::Regulation};
+use kodecks::{deck::DeckList, pool::CardPool, profile::BotDifficulty, regulation::Regulation};
 use url::Url;
 
 #[derive(Debug, Clone, Resource)]
@@ -12,6 +12,11 @@ pub struct GameMode {
 
 #[derive(Debug, Clone)]
 pub enum GameModeKind {
-    BotMatch { bot_deck: DeckList },
-    RandomMatch { server: Url },
+    BotMatch {
+        bot_deck: DeckList,
+        difficulty: BotDifficulty,
+    },
+    RandomMatch {
+        server: Url,
+    },
 }
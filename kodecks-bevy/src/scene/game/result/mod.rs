@@ -45,6 +45,7 @@ fn init(
                 EndgameReason::DeckOut => "reason-deck-out",
                 EndgameReason::LifeZero => "reason-life-zero",
                 EndgameReason::SimultaneousEnd => "reason-simultaneous-end",
+                EndgameReason::Timeout => "reason-timeout",
             };
             let request = if let Some(winner) = winner {
                 if env.player == winner {
@@ -1,3 +1,4 @@
+use super::event::ClocksUpdated;
 use crate::scene::{spinner::SpinnerState, GlobalState};
 use bevy::{ecs::world::Command, prelude::*};
 use futures::{
@@ -298,6 +299,7 @@ fn recv_events(
     mut commands: Commands,
     mut server: ResMut<ServerConnection>,
     mut events: EventWriter<ServerEvent>,
+    mut clock_events: EventWriter<ClocksUpdated>,
     mut next_spinner_state: ResMut<NextState<SpinnerState>>,
 ) {
     while let Some(event) = server.recv() {
@@ -311,12 +313,19 @@ fn recv_events(
                         player: event.player,
                     });
                 }
-                GameEventKind::StateUpdated { state } => {
-                    events.send(ServerEvent(state));
+                GameEventKind::StateUpdated { state, hash } => {
+                    events.send(ServerEvent { state, hash });
                     next_spinner_state.set(SpinnerState::Off);
                 }
-                GameEventKind::PlayerThinking { thinking, timeout } => {
+                GameEventKind::PlayerThinking {
+                    thinking,
+                    timeout,
+                    clocks,
+                } => {
                     info!("Player {} is thinking: {:?}", thinking, timeout);
+                    if !clocks.is_empty() {
+                        clock_events.send(ClocksUpdated(clocks));
+                    }
                     if thinking != event.player {
                         next_spinner_state.set(SpinnerState::On);
                     }
@@ -334,6 +343,9 @@ fn recv_events(
                     }));
                 }
             },
+            Output::DraftEvent(event) => {
+                info!("Draft event for seat {}: {:?}", event.seat, event.event);
+            }
             Output::Error(err) => {
                 error!("Error: {}", err);
                 commands.insert_resource(ServerError(err));
@@ -346,19 +358,40 @@ pub struct SendCommand(pub Action);
 
 impl Command for SendCommand {
     fn apply(self, world: &mut World) {
-        if let Some(session) = world.get_resource::<Session>() {
-            let id = session.id;
-            let player = session.player;
-            if let Some(mut conn) = world.get_resource_mut::<ServerConnection>() {
-                conn.send(Input::GameCommand(message::GameCommand {
-                    game_id: id,
-                    player,
-                    kind: message::GameCommandKind::NextAction { action: self.0 },
-                }));
-            }
+        send_game_command(
+            world,
+            message::GameCommandKind::NextAction { action: self.0 },
+        );
+    }
+}
+
+/// Asks the server for the full state after the local view has diverged.
+pub struct RequestResync;
+
+impl Command for RequestResync {
+    fn apply(self, world: &mut World) {
+        send_game_command(world, message::GameCommandKind::Resync);
+    }
+}
+
+fn send_game_command(world: &mut World, kind: message::GameCommandKind) {
+    if let Some(session) = world.get_resource::<Session>() {
+        let id = session.id;
+        let player = session.player;
+        if let Some(mut conn) = world.get_resource_mut::<ServerConnection>() {
+            conn.send(Input::GameCommand(message::GameCommand {
+                game_id: id,
+                player,
+                kind,
+            }));
         }
     }
 }
 
 #[derive(Event, Clone, Deref)]
-pub struct ServerEvent(Box<LocalGameState>);
+pub struct ServerEvent {
+    #[deref]
+    state: Box<LocalGameState>,
+    /// Hash of the redacted server state, see `LocalEnvironment::state_hash`.
+    pub hash: u64,
+}
@@ -7,7 +7,7 @@ use super::{
 use crate::{app_version, assets::AssetServerExt, config::GlobalConfig, save_data};
 use bevy::prelude::*;
 use bevy_mod_picking::prelude::*;
-use kodecks::{deck::DeckList, pool::CardPool, regulation::Regulation};
+use kodecks::{deck::DeckList, pool::CardPool, profile::BotDifficulty, regulation::Regulation};
 use kodecks_catalog::decks::{blue_deck, red_deck};
 
 pub struct MenuPlugin;
@@ -15,6 +15,7 @@ pub struct MenuPlugin;
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<MenuEvent>()
+            .init_resource::<CpuDifficulty>()
             .add_systems(OnEnter(GlobalState::MenuMain), init)
             .add_systems(OnExit(GlobalState::MenuMain), cleanup)
             .add_systems(Update, handle_menu_events.run_if(on_event::<MenuEvent>()));
@@ -24,9 +25,48 @@ impl Plugin for MenuPlugin {
 #[derive(Component)]
 struct UiRoot;
 
+#[derive(Component)]
+struct DifficultyLabel;
+
+/// The difficulty of the CPU matches, kept while the app runs.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+enum CpuDifficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl CpuDifficulty {
+    fn next(self) -> Self {
+        match self {
+            Self::Easy => Self::Normal,
+            Self::Normal => Self::Hard,
+            Self::Hard => Self::Easy,
+        }
+    }
+
+    fn bot_difficulty(self) -> BotDifficulty {
+        match self {
+            Self::Easy => BotDifficulty::EASY,
+            Self::Normal => BotDifficulty::NORMAL,
+            Self::Hard => BotDifficulty::HARD,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Easy => "menu-button-difficulty-easy",
+            Self::Normal => "menu-button-difficulty-normal",
+            Self::Hard => "menu-button-difficulty-hard",
+        }
+    }
+}
+
 #[derive(Event)]
 enum MenuEvent {
     StartBotMatch { deck_list: DeckList },
+    ToggleDifficulty,
     StartRandomMatch,
     EditDeck,
 }
@@ -36,6 +76,7 @@ fn init(
     translator: Res<Translator>,
     asset_server: Res<AssetServer>,
     catalog: Res<Catalog>,
+    difficulty: Res<CpuDifficulty>,
 ) {
     let catalog = catalog.clone();
     let slicer = TextureSlicer {
@@ -175,6 +216,38 @@ fn init(
                             ));
                         });
 
+                    parent
+                        .spawn((
+                            ImageBundle {
+                                style: Style {
+                                    width: Val::Px(280.),
+                                    height: Val::Px(50.),
+                                    padding: UiRect::all(Val::Px(15.)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                image: button.clone().into(),
+                                ..default()
+                            },
+                            ImageScaleMode::Sliced(slicer.clone()),
+                            On::<Pointer<Click>>::commands_mut(move |_, commands| {
+                                commands.add(move |w: &mut World| {
+                                    w.send_event(MenuEvent::ToggleDifficulty);
+                                });
+                            }),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    translator.get(difficulty.label()),
+                                    translator.style(TextPurpose::Button),
+                                ),
+                                Label,
+                                DifficultyLabel,
+                            ));
+                        });
+
                     parent
                         .spawn((
                             ImageBundle {
@@ -280,6 +353,9 @@ fn handle_menu_events(
     mut next_state: ResMut<NextState<GlobalState>>,
     save_data: Res<save_data::SaveData>,
     config: Res<GlobalConfig>,
+    translator: Res<Translator>,
+    mut difficulty: ResMut<CpuDifficulty>,
+    mut difficulty_label: Query<&mut Text, With<DifficultyLabel>>,
 ) {
     let event = if let Some(event) = events.read().next() {
         event
@@ -291,9 +367,17 @@ fn handle_menu_events(
         MenuEvent::StartBotMatch { deck_list } => (
             GameModeKind::BotMatch {
                 bot_deck: deck_list.clone(),
+                difficulty: difficulty.bot_difficulty(),
             },
             save_data.decks.get_default("offline").unwrap(),
         ),
+        MenuEvent::ToggleDifficulty => {
+            *difficulty = difficulty.next();
+            for mut text in difficulty_label.iter_mut() {
+                text.sections[0].value = translator.get(difficulty.label()).to_string();
+            }
+            return;
+        }
         MenuEvent::StartRandomMatch => (
             GameModeKind::RandomMatch {
                 server: config.server.clone(),
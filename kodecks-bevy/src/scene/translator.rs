@@ -4,6 +4,8 @@ use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource};
 use fluent_content::{Content, Request};
 use kodecks::{
     card,
+    catalog::Catalog,
+    error::DeckViolation,
     prelude::KeywordAbility,
     text::{parse_text, Section},
 };
@@ -86,6 +88,17 @@ impl Translator {
             })
     }
 
+    /// Describes a deck violation, with the name of the card localized.
+    pub fn deck_violation(&self, violation: &DeckViolation, catalog: &Catalog) -> String {
+        let mut request: Request<FluentArgs> = violation.clone().into();
+        let archetype = violation.card().and_then(|card| catalog.get(card));
+        if let (Some(archetype), Some(args)) = (archetype, request.args.as_mut()) {
+            let name = self.get(&format!("card-{}", archetype.safe_name));
+            args.set("card", name.to_string());
+        }
+        self.get(request).to_string()
+    }
+
     pub fn get_related_items(&self, safe_name: &str) -> RelatedItems {
         let id = format!("card-{safe_name}.text");
         let text = self.get_default_lang(&id);
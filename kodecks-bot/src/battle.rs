@@ -3,7 +3,7 @@ use crate::{
     score::{get_score, ComputedScore},
     Bot, SimpleBot,
 };
-use kodecks::{action::Action, env::Environment, id::TimedObjectId, phase::Phase};
+use kodecks::{action::Action, env::Environment, id::TimedObjectId, phase::Phase, target::Target};
 use std::sync::Arc;
 
 #[cfg(feature = "rayon")]
@@ -12,30 +12,50 @@ use rayon::prelude::*;
 pub fn find_attacker_combination(
     ctx: BotContext,
     attackers: &[TimedObjectId],
-) -> Vec<(Vec<TimedObjectId>, ComputedScore)> {
+    targets: &[Target],
+) -> Vec<(Vec<TimedObjectId>, Option<Target>, ComputedScore)> {
     if attackers.is_empty() {
         return vec![];
     }
 
     let base_score = evaluate_battle(&ctx.env, ctx.player, None);
 
-    let combinations = possible_attacker_combinations(attackers);
+    let targets = if targets.is_empty() {
+        vec![None]
+    } else {
+        targets.iter().copied().map(Some).collect()
+    };
+    let combinations = possible_attacker_combinations(attackers)
+        .into_iter()
+        .flat_map(|attackers| {
+            if attackers.is_empty() {
+                vec![(attackers, None)]
+            } else {
+                targets
+                    .iter()
+                    .map(|&target| (attackers.clone(), target))
+                    .collect()
+            }
+        })
+        .collect::<Vec<_>>();
     #[cfg(feature = "rayon")]
     let scored_combinations = combinations.into_par_iter();
     #[cfg(not(feature = "rayon"))]
     let scored_combinations = combinations.into_iter();
 
     scored_combinations
-        .map(|attackers| {
+        .map(|(attackers, target)| {
+            let bias = ctx.style.aggression * attackers.len() as i32;
             (
                 attackers.clone(),
+                target,
                 ComputedScore {
                     base: base_score,
                     action: evaluate_battle(
                         &ctx.env,
                         ctx.player,
-                        Some(Action::Attack { attackers }),
-                    ),
+                        Some(Action::Attack { attackers, target }),
+                    ) + bias,
                 },
             )
         })
@@ -91,11 +111,12 @@ pub fn find_blocker_combination(
     let comb = combinations.into_iter();
 
     comb.map(|pairs| {
+        let bias = ctx.style.blocking * pairs.len() as i32;
         (
             pairs.clone(),
             ComputedScore {
                 base: base_score,
-                action: evaluate_battle(&ctx.env, ctx.player, Some(Action::Block { pairs })),
+                action: evaluate_battle(&ctx.env, ctx.player, Some(Action::Block { pairs })) + bias,
             },
         )
     })
@@ -0,0 +1,81 @@
+use crate::{mcts::State, score::ComputedScore, Bot, DefaultBot, MctsBot, SearchBudget, SimpleBot};
+use kodecks::{
+    action::{Action, PlayerAvailableActions},
+    env::{CardPrior, Environment},
+    profile::{BotConfig, BotKind},
+};
+use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+use std::sync::Arc;
+
+/// A bot built from a [`BotConfig`], which sometimes plays a random action
+/// depending on the blunder rate of its difficulty.
+pub struct ConfiguredBot {
+    inner: Box<dyn Bot + Send>,
+    blunder_rate: f32,
+    rng: SmallRng,
+}
+
+impl ConfiguredBot {
+    /// Creates a bot which assumes the hidden cards follow the prior, if it searches ahead.
+    ///
+    /// Every random choice of the bot comes from the seed, see [`ConfiguredBot::seed`].
+    pub fn new(config: &BotConfig, prior: CardPrior, seed: u64) -> Self {
+        let difficulty = config.difficulty;
+        let mut rng = SmallRng::seed_from_u64(seed);
+        let inner: Box<dyn Bot + Send> = match config.kind {
+            BotKind::Simple => Box::new(SimpleBot),
+            BotKind::Default { style } => Box::new(
+                DefaultBot::builder()
+                    .with_style(style)
+                    .with_rng(SmallRng::seed_from_u64(rng.gen()))
+                    .build(),
+            ),
+            BotKind::Mcts => {
+                let mut bot = MctsBot::default();
+                bot.budget = SearchBudget {
+                    iterations: difficulty.iterations,
+                    time: difficulty.time,
+                };
+                bot.exploration = difficulty.exploration as f64;
+                bot.prior = prior;
+                bot.rng = SmallRng::seed_from_u64(rng.gen());
+                Box::new(bot)
+            }
+        };
+        Self {
+            inner,
+            blunder_rate: difficulty.blunder_rate.clamp(0.0, 1.0),
+            rng,
+        }
+    }
+
+    /// Derives the seed of the bot of the player from the seed of the game,
+    /// so that replaying a game with the same seed replays the bots as well.
+    pub fn seed(game_seed: u64, player: u8) -> u64 {
+        SmallRng::seed_from_u64(game_seed).gen::<u64>() ^ player as u64
+    }
+}
+
+impl Bot for ConfiguredBot {
+    fn compute(
+        &mut self,
+        env: Arc<Environment>,
+        actions: &PlayerAvailableActions,
+    ) -> Vec<(Action, ComputedScore)> {
+        self.inner.compute(env, actions)
+    }
+
+    fn compute_best_action(
+        &mut self,
+        env: Arc<Environment>,
+        actions: &PlayerAvailableActions,
+    ) -> Option<Action> {
+        if self.rng.gen::<f32>() < self.blunder_rate {
+            let candidates = State::get_available_actions(Some(actions), &env);
+            if let Some((_, action)) = candidates.choose(&mut self.rng) {
+                return Some(action.clone());
+            }
+        }
+        self.inner.compute_best_action(env, actions)
+    }
+}
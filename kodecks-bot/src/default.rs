@@ -1,10 +1,12 @@
 use crate::{
-    battle, cast, score::ComputedScore, select::find_select_combination, Bot, BotContext, BotFlags,
+    battle, cast, mulligan::find_mulligan, score::ComputedScore, select::find_select_combination,
+    Bot, BotContext, BotFlags,
 };
 use kodecks::{
-    action::{Action, PlayerAvailableActions},
+    action::{Action, AvailableAction, PlayerAvailableActions},
     env::Environment,
     id::TimedCardId,
+    profile::BotStyle,
     score::Score,
 };
 use rand::{rngs::SmallRng, SeedableRng};
@@ -15,6 +17,7 @@ use tracing::*;
 pub struct DefaultBot {
     rng: SmallRng,
     flags: BotFlags,
+    style: BotStyle,
 }
 
 pub struct BotBuilder {
@@ -27,6 +30,11 @@ impl BotBuilder {
         self
     }
 
+    pub fn with_style(mut self, style: BotStyle) -> Self {
+        self.inner.style = style;
+        self
+    }
+
     pub fn with_rng(mut self, rng: SmallRng) -> Self {
         self.inner.rng = rng;
         self
@@ -43,6 +51,7 @@ impl DefaultBot {
             inner: DefaultBot {
                 rng: SmallRng::from_entropy(),
                 flags: Default::default(),
+                style: Default::default(),
             },
         }
     }
@@ -50,6 +59,10 @@ impl DefaultBot {
     pub fn flags(&self) -> BotFlags {
         self.flags
     }
+
+    pub fn style(&self) -> BotStyle {
+        self.style
+    }
 }
 
 impl Bot for DefaultBot {
@@ -63,8 +76,14 @@ impl Bot for DefaultBot {
             player: actions.player,
             env: env.clone(),
             flags: self.flags,
+            style: self.style,
         };
 
+        let mulligan = actions.actions.iter().find_map(|action| match action {
+            AvailableAction::Mulligan { cards } => find_mulligan(&env, cards),
+            _ => None,
+        });
+
         let selectable_card = actions.actions.selectable_cards();
         let select = find_select_combination(ctx.clone(), selectable_card);
         for (card, score) in &select {
@@ -98,31 +117,31 @@ impl Bot for DefaultBot {
             .filter(|(_, score)| score.score() > 0)
             .max_by_key(|(_, score)| *score);
 
-        let battle = battle::find_attacker_combination(ctx.clone(), &actions.actions.attackers());
-        for (attackers, score) in &battle {
+        let battle = battle::find_attacker_combination(
+            ctx.clone(),
+            &actions.actions.attackers(),
+            &actions.actions.attack_targets(),
+        );
+        for (attackers, target, score) in &battle {
             let attackers = attackers
                 .iter()
                 .filter_map(|id| env.state.find_card(*id).ok())
                 .map(|card| card.archetype().name.clone())
                 .collect::<Vec<_>>()
                 .join(", ");
-            debug!("Battle: {} score: {:?}", attackers, score);
+            debug!(
+                "Battle: {} target: {:?} score: {:?}",
+                attackers, target, score
+            );
         }
 
         let battle = battle
             .into_iter()
-            .map(|(attackers, score)| {
-                (
-                    Action::Attack {
-                        attackers: attackers.clone(),
-                    },
-                    score,
-                )
-            })
+            .map(|(attackers, target, score)| (Action::Attack { attackers, target }, score))
             .filter(|(_, score)| score.score() > 0)
             .max_by_key(|(_, score)| *score);
 
-        let opponent = if let Ok(opponent) = env.state.players().next_player(actions.player) {
+        let opponent = if let Ok(opponent) = env.state.players().player_in_turn() {
             opponent
         } else {
             return vec![];
@@ -175,6 +194,7 @@ impl Bot for DefaultBot {
                 )
             })
             .into_iter()
+            .chain(mulligan)
             .chain(select)
             .chain(cast)
             .chain(block)
@@ -2,6 +2,7 @@ use bitflags::bitflags;
 use kodecks::{
     action::{Action, PlayerAvailableActions},
     env::Environment,
+    profile::BotStyle,
 };
 use rand::rngs::SmallRng;
 use score::ComputedScore;
@@ -9,20 +10,24 @@ use std::sync::Arc;
 
 mod battle;
 mod cast;
+mod config;
 mod default;
 mod mcts;
+mod mulligan;
 mod score;
 mod select;
 mod simple;
 
+pub use config::ConfiguredBot;
 pub use default::DefaultBot;
-pub use mcts::MctsBot;
+pub use mcts::{MctsBot, SearchBudget};
 pub use simple::SimpleBot;
 
 #[derive(Clone)]
 pub struct BotContext {
     pub rng: SmallRng,
     pub flags: BotFlags,
+    pub style: BotStyle,
     pub player: u8,
     pub env: Arc<Environment>,
 }
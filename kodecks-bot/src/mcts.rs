@@ -1,14 +1,154 @@
 use crate::{score::ComputedScore, Bot};
 use kodecks::{
     action::{Action, AvailableAction, PlayerAvailableActions},
-    env::{EndgameState, Environment},
+    env::{CardPrior, EndgameState, Environment},
     id::{TimedCardId, TimedObjectId},
 };
-use rand::seq::{IteratorRandom, SliceRandom};
-use std::sync::Arc;
+use rand::{rngs::SmallRng, seq::SliceRandom, Rng, SeedableRng};
+use std::{iter, sync::Arc, time::Duration};
+use web_time::Instant;
 
-#[derive(Default)]
-pub struct MctsBot {}
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+const EXPLORATION_CONSTANT: f64 = 1.4;
+
+/// The number of simulations run on each tree between checks of the time budget.
+const SEARCH_STEP: u32 = 16;
+
+/// Limits how long the bot searches for each decision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchBudget {
+    /// The maximum number of simulations, split evenly across the trees.
+    pub iterations: u32,
+    /// The maximum time to search, if any.
+    pub time: Option<Duration>,
+}
+
+impl Default for SearchBudget {
+    fn default() -> Self {
+        Self {
+            iterations: 200,
+            time: None,
+        }
+    }
+}
+
+/// A bot searching the game tree with Monte Carlo tree search.
+///
+/// The search runs on determinizations of the game, where the cards hidden from the bot
+/// are sampled from its prior, and the visits of each action are summed over them.
+/// With the `rayon` feature, the determinizations are searched in parallel.
+///
+/// The trees are kept between decisions. When the game reaches a state the previous search
+/// has already explored, e.g. after casting a card or after the reply of the opponent,
+/// the search continues from that subtree.
+pub struct MctsBot {
+    pub budget: SearchBudget,
+    /// The number of sampled games to search.
+    /// With 0, the bot searches the actual game and sees the hidden cards.
+    pub determinizations: u32,
+    /// How much the search tries less visited actions over the ones that look best.
+    pub exploration: f64,
+    /// The deck lists the bot knows, usually its own.
+    /// The other hidden cards are assumed to be any card of the catalog.
+    pub prior: CardPrior,
+    /// Samples the determinizations and seeds the trees, which keep their own generators
+    /// so that the search does not depend on the order the trees are searched.
+    pub rng: SmallRng,
+    trees: Vec<Mcts>,
+    chosen: Option<usize>,
+}
+
+impl MctsBot {
+    pub fn new(budget: SearchBudget, determinizations: u32) -> Self {
+        Self {
+            budget,
+            determinizations,
+            exploration: EXPLORATION_CONSTANT,
+            prior: CardPrior::default(),
+            rng: SmallRng::from_entropy(),
+            trees: vec![],
+            chosen: None,
+        }
+    }
+
+    /// Starts a search for the decision of the player.
+    ///
+    /// The subtrees of the previous search whose state looks the same to the player are kept.
+    pub fn start_search(&mut self, env: &Environment, player: u8) {
+        let trees = self.determinizations.max(1) as usize;
+        let hash = env.state.redacted_hash(player);
+        let previous = std::mem::take(&mut self.trees);
+        if let Some(chosen) = self.chosen.take() {
+            self.trees = previous
+                .into_iter()
+                .filter_map(|tree| tree.into_subtree(chosen, player, hash))
+                .take(trees)
+                .collect();
+        }
+        while self.trees.len() < trees {
+            let env = if self.determinizations == 0 {
+                env.clone()
+            } else {
+                env.determinize(player, &self.prior, &mut self.rng)
+            };
+            let rng = SmallRng::seed_from_u64(self.rng.gen());
+            self.trees.push(Mcts::new(&env, player, rng));
+        }
+    }
+
+    /// Runs the simulations on each tree.
+    pub fn search(&mut self, iterations: u32) {
+        #[cfg(feature = "rayon")]
+        let trees = self.trees.par_iter_mut();
+        #[cfg(not(feature = "rayon"))]
+        let trees = self.trees.iter_mut();
+
+        let exploration = self.exploration;
+        trees.for_each(|tree| tree.search(iterations, exploration));
+    }
+
+    /// Returns the best action found so far.
+    pub fn best_action(&self) -> Option<Action> {
+        let index = self.best_index()?;
+        self.trees
+            .iter()
+            .find_map(|tree| tree.child(index)?.action.clone())
+    }
+
+    /// Returns the best action found, and keeps its subtree for the next search.
+    pub fn finish_search(&mut self) -> Option<Action> {
+        self.chosen = self.best_index();
+        self.best_action()
+    }
+
+    fn best_index(&self) -> Option<usize> {
+        // The actions of the bot do not depend on the hidden cards,
+        // so every tree has the same children in the same order.
+        let mut visits: Vec<u32> = vec![];
+        for tree in &self.trees {
+            let children = &tree.nodes[Mcts::ROOT].children;
+            if visits.len() < children.len() {
+                visits.resize(children.len(), 0);
+            }
+            for (index, &child) in children.iter().enumerate() {
+                visits[index] += tree.nodes[child].visits;
+            }
+        }
+        visits
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, visits)| **visits)
+            .map(|(index, _)| index)
+    }
+}
+
+impl Default for MctsBot {
+    fn default() -> Self {
+        Self::new(SearchBudget::default(), 4)
+    }
+}
 
 impl Bot for MctsBot {
     fn compute(
@@ -16,11 +156,24 @@ impl Bot for MctsBot {
         env: Arc<Environment>,
         actions: &PlayerAvailableActions,
     ) -> Vec<(Action, ComputedScore)> {
-        let mut mcts = Mcts::new(&env, actions.player);
-        if let Some(action) = mcts.search(200, 1.4) {
-            return vec![(action, ComputedScore::default())];
+        self.start_search(&env, actions.player);
+
+        let deadline = self.budget.time.map(|time| Instant::now() + time);
+        let iterations = self
+            .budget
+            .iterations
+            .div_ceil(self.determinizations.max(1));
+        let mut done = 0;
+        while done < iterations && deadline.is_none_or(|deadline| Instant::now() < deadline) {
+            let step = SEARCH_STEP.min(iterations - done);
+            self.search(step);
+            done += step;
         }
-        vec![]
+
+        self.finish_search()
+            .map(|action| (action, ComputedScore::default()))
+            .into_iter()
+            .collect()
     }
 }
 
@@ -39,7 +192,7 @@ impl State {
         }
     }
 
-    fn get_available_actions(
+    pub(crate) fn get_available_actions(
         actions: Option<&PlayerAvailableActions>,
         env: &Environment,
     ) -> Vec<(u8, Action)> {
@@ -57,10 +210,27 @@ impl State {
                         .iter()
                         .map(|&card| (player, Action::CastCard { card }))
                         .collect(),
-                    AvailableAction::Attack { attackers } => possible_combinations(attackers)
-                        .into_iter()
-                        .map(|attackers| (player, Action::Attack { attackers }))
-                        .collect(),
+                    AvailableAction::Attack { attackers, targets } => {
+                        let targets = if targets.is_empty() {
+                            vec![None]
+                        } else {
+                            targets.iter().copied().map(Some).collect()
+                        };
+                        possible_combinations(attackers)
+                            .into_iter()
+                            .flat_map(|attackers| {
+                                targets.iter().map(move |&target| {
+                                    (
+                                        player,
+                                        Action::Attack {
+                                            attackers: attackers.clone(),
+                                            target,
+                                        },
+                                    )
+                                })
+                            })
+                            .collect()
+                    }
                     AvailableAction::Block {
                         attackers,
                         blockers,
@@ -76,6 +246,12 @@ impl State {
                         })
                         .chain(Some((player, Action::Block { pairs: vec![] })))
                         .collect(),
+                    AvailableAction::Mulligan { cards } => vec![(
+                        player,
+                        Action::Mulligan {
+                            cards: cards.clone(),
+                        },
+                    )],
                     AvailableAction::Continue => vec![(player, Action::Continue)],
                     AvailableAction::EndTurn => vec![(player, Action::EndTurn)],
                 })
@@ -106,17 +282,17 @@ impl State {
 struct Node {
     state: State,
     action: Option<Action>,
-    parent: Option<*mut Self>,
-    children: Vec<Self>,
+    parent: Option<usize>,
+    children: Vec<usize>,
     visits: u32,
     wins: f64,
 }
 
 impl Node {
-    fn new(state: State, parent: Option<*mut Self>) -> Self {
+    fn new(state: State, action: Option<Action>, parent: Option<usize>) -> Self {
         Self {
             state,
-            action: None,
+            action,
             parent,
             children: vec![],
             visits: 0,
@@ -124,20 +300,6 @@ impl Node {
         }
     }
 
-    fn expand(&mut self, possible_actions: Vec<(u8, Action)>) {
-        for (player, action) in possible_actions {
-            let child = Node {
-                state: self.state.progress(player, action.clone()),
-                action: Some(action),
-                parent: Some(self),
-                children: Vec::new(),
-                visits: 0,
-                wins: 0.0,
-            };
-            self.children.push(child);
-        }
-    }
-
     fn ucb1(&self, parent_visits: u32, exploration_constant: f64) -> f64 {
         if self.visits == 0 {
             f64::INFINITY
@@ -148,39 +310,12 @@ impl Node {
         }
     }
 
-    fn select_child(&self, exploration_constant: f64) -> Option<&Self> {
-        let unvisited = self
-            .children
-            .iter()
-            .filter(|child| child.visits == 0)
-            .collect::<Vec<_>>();
-
-        if !unvisited.is_empty() {
-            return unvisited.choose(&mut rand::thread_rng()).copied();
-        }
-
-        self.children.iter().max_by(|a, b| {
-            let a_ucb = a.ucb1(self.visits, exploration_constant);
-            let b_ucb = b.ucb1(self.visits, exploration_constant);
-            a_ucb.partial_cmp(&b_ucb).unwrap()
-        })
-    }
-
-    fn backpropagate(&mut self, result: f64) {
-        let mut current = Some(self);
-        while let Some(curr_node) = current {
-            curr_node.visits += 1;
-            curr_node.wins += result;
-            current = unsafe { curr_node.parent.map(|p| &mut *p) };
-        }
-    }
-
-    fn simulate(&self, player: u8) -> f64 {
+    fn simulate(&self, player: u8, rng: &mut SmallRng) -> f64 {
         let mut env = self.state.env.clone();
         while !env.game_condition().is_ended() {
             let available_actions =
                 State::get_available_actions(env.last_available_actions(), &env);
-            let action = available_actions.choose(&mut rand::thread_rng());
+            let action = available_actions.choose(rng);
             if let Some((player, action)) = action {
                 env.process(*player, Some(action.clone()));
             } else {
@@ -203,57 +338,142 @@ impl Node {
     }
 }
 
+/// A search tree whose nodes are stored in an arena and refer to each other by index.
 struct Mcts {
-    root: Node,
+    nodes: Vec<Node>,
     player: u8,
+    rng: SmallRng,
 }
 
 impl Mcts {
-    fn new(env: &Environment, player: u8) -> Self {
+    const ROOT: usize = 0;
+
+    fn new(env: &Environment, player: u8, rng: SmallRng) -> Self {
         Self {
-            root: Node::new(State::new(env), None),
+            nodes: vec![Node::new(State::new(env), None, None)],
             player,
+            rng,
         }
     }
 
-    fn search(&mut self, num_iterations: u32, exploration_constant: f64) -> Option<Action> {
-        for _ in 0..num_iterations {
-            let mut current_node = &mut self.root;
-            while !current_node.children.is_empty() {
-                let selected = current_node.select_child(exploration_constant)? as *const Node;
-                current_node = current_node
-                    .children
-                    .iter_mut()
-                    .find(|c| *c as *const Node == selected)?;
+    fn expand(&mut self, index: usize) {
+        let possible_actions = self.nodes[index].state.available_actions.clone();
+        for (player, action) in possible_actions {
+            let state = self.nodes[index].state.progress(player, action.clone());
+            let child = self.nodes.len();
+            self.nodes.push(Node::new(state, Some(action), Some(index)));
+            self.nodes[index].children.push(child);
+        }
+    }
 
-                if current_node.visits == 0 {
-                    let simulation_result = current_node.simulate(self.player);
-                    current_node.backpropagate(simulation_result);
-                    continue;
+    fn select_child(&mut self, index: usize, exploration_constant: f64) -> Option<usize> {
+        let node = &self.nodes[index];
+        let unvisited = node
+            .children
+            .iter()
+            .copied()
+            .filter(|&child| self.nodes[child].visits == 0)
+            .collect::<Vec<_>>();
+
+        if !unvisited.is_empty() {
+            return unvisited.choose(&mut self.rng).copied();
+        }
+
+        node.children.iter().copied().max_by(|&a, &b| {
+            let a_ucb = self.nodes[a].ucb1(node.visits, exploration_constant);
+            let b_ucb = self.nodes[b].ucb1(node.visits, exploration_constant);
+            a_ucb.partial_cmp(&b_ucb).unwrap()
+        })
+    }
+
+    /// Simulates a game from the node and propagates the result to its ancestors.
+    fn rollout(&mut self, index: usize) {
+        let result = self.nodes[index].simulate(self.player, &mut self.rng);
+        let mut current = Some(index);
+        while let Some(index) = current {
+            let node = &mut self.nodes[index];
+            node.visits += 1;
+            node.wins += result;
+            current = node.parent;
+        }
+    }
+
+    /// Runs the simulations, each one rolling out a single node.
+    ///
+    /// The root is expanded first, so that a single simulation already visits an action.
+    fn search(&mut self, num_iterations: u32, exploration_constant: f64) {
+        if self.nodes[Self::ROOT].children.is_empty() {
+            self.expand(Self::ROOT);
+        }
+        for _ in 0..num_iterations {
+            let mut current = Self::ROOT;
+            while !self.nodes[current].children.is_empty() {
+                let Some(selected) = self.select_child(current, exploration_constant) else {
+                    return;
+                };
+                current = selected;
+                if self.nodes[current].visits == 0 {
+                    break;
                 }
             }
 
-            let possible_actions = current_node.state.available_actions.clone();
-            if !possible_actions.is_empty() && current_node.visits > 0 {
-                current_node.expand(possible_actions);
-
-                if let Some(idx) = (0..current_node.children.len()).choose(&mut rand::thread_rng())
-                {
-                    let child = &mut current_node.children[idx];
-                    let simulation_result = child.simulate(self.player);
-                    child.backpropagate(simulation_result);
+            let node = &self.nodes[current];
+            if !node.state.available_actions.is_empty() && node.visits > 0 {
+                self.expand(current);
+                if let Some(&child) = self.nodes[current].children.choose(&mut self.rng) {
+                    current = child;
                 }
-            } else if current_node.visits == 0 {
-                let simulation_result = current_node.simulate(self.player);
-                current_node.backpropagate(simulation_result);
             }
+            self.rollout(current);
         }
+    }
 
-        let bast_node = self.root.children.iter().max_by_key(|child| child.visits);
-        if let Some(best_node) = bast_node {
-            return best_node.action.clone();
+    fn child(&self, index: usize) -> Option<&Node> {
+        let child = self.nodes[Self::ROOT].children.get(index)?;
+        Some(&self.nodes[*child])
+    }
+
+    /// Returns the subtree for the state after the chosen action of the root,
+    /// or after a reply to it, if the state looks the same to the player as `hash`.
+    fn into_subtree(self, chosen: usize, player: u8, hash: u64) -> Option<Self> {
+        let child = *self.nodes[Self::ROOT].children.get(chosen)?;
+        let root = iter::once(child)
+            .chain(self.nodes[child].children.iter().copied())
+            .find(|&index| {
+                let state = &self.nodes[index].state;
+                state
+                    .available_actions
+                    .first()
+                    .is_some_and(|(actor, _)| *actor == player)
+                    && state.env.state.redacted_hash(player) == hash
+            })?;
+
+        let mut nodes = self.nodes.into_iter().map(Some).collect::<Vec<_>>();
+        let mut subtree: Vec<Node> = vec![];
+        let mut stack = vec![(root, None)];
+        while let Some((index, parent)) = stack.pop() {
+            let Some(mut node) = nodes[index].take() else {
+                continue;
+            };
+            let children = std::mem::take(&mut node.children);
+            let new_index = subtree.len();
+            node.parent = parent;
+            subtree.push(node);
+            if let Some(parent) = parent {
+                subtree[parent].children.push(new_index);
+            }
+            stack.extend(
+                children
+                    .into_iter()
+                    .rev()
+                    .map(|child| (child, Some(new_index))),
+            );
         }
-        None
+        Some(Self {
+            nodes: subtree,
+            player: self.player,
+            rng: self.rng,
+        })
     }
 }
 
@@ -318,3 +538,190 @@ fn select_blocking_pair(
         .map(|(a, b)| (a.timed_id(), b.timed_id()))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodecks::{
+        archetype::{CardArchetype, CardAttribute, CardType},
+        player::PlayerConfig,
+        profile::GameProfile,
+        testing::{archetype, catalog, deck},
+    };
+
+    /// Starts a game of vanilla creatures, and plays it until the first player can cast a card.
+    fn start_game() -> (Environment, PlayerAvailableActions) {
+        let creature = CardArchetype {
+            attribute: CardAttribute {
+                card_type: CardType::Creature,
+                power: Some(1000),
+                ..Default::default()
+            },
+            ..archetype("c0")
+        };
+        let profile = GameProfile {
+            players: vec![
+                PlayerConfig {
+                    deck: deck(&[("c0", 10)]),
+                };
+                2
+            ],
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let mut env = Environment::new(profile, Arc::new(catalog([creature])));
+        let mut player = 0;
+        let mut action = None;
+        loop {
+            let report = env.process(player, action.take());
+            let Some(actions) = report.available_actions else {
+                continue;
+            };
+            let castable = actions.actions.iter().any(
+                |action| matches!(action, AvailableAction::CastCard { cards } if !cards.is_empty()),
+            );
+            if actions.player == 0 && castable {
+                return (env, actions);
+            }
+            player = actions.player;
+            action = State::get_available_actions(Some(&actions), &env)
+                .into_iter()
+                .next()
+                .map(|(_, action)| action);
+        }
+    }
+
+    /// Checks that the parents and the children of the nodes point to each other,
+    /// and that every visit of a node is a rollout from it or a visit of a child.
+    fn assert_consistent(tree: &Mcts) {
+        assert_eq!(tree.nodes[Mcts::ROOT].parent, None);
+        let mut reached = vec![false; tree.nodes.len()];
+        reached[Mcts::ROOT] = true;
+        for (index, node) in tree.nodes.iter().enumerate() {
+            for &child in &node.children {
+                assert_eq!(tree.nodes[child].parent, Some(index));
+                assert!(!reached[child], "node {child} has more than one parent");
+                reached[child] = true;
+            }
+            if !node.children.is_empty() {
+                let visits = node
+                    .children
+                    .iter()
+                    .map(|&child| tree.nodes[child].visits)
+                    .sum::<u32>();
+                assert!(
+                    (visits..=visits + 1).contains(&node.visits),
+                    "node {index} has {} visits but its children have {visits}",
+                    node.visits
+                );
+            }
+        }
+        assert!(reached.iter().all(|&reached| reached));
+    }
+
+    #[test]
+    fn test_search_tree() {
+        let (env, actions) = start_game();
+        let mut bot = MctsBot::new(SearchBudget::default(), 2);
+        bot.start_search(&env, actions.player);
+        bot.search(50);
+
+        assert_eq!(bot.trees.len(), 2);
+        for tree in &bot.trees {
+            assert_consistent(tree);
+            assert_eq!(tree.nodes[Mcts::ROOT].visits, 50);
+        }
+        assert!(bot.best_action().is_some());
+    }
+
+    fn cast_index(tree: &Mcts) -> usize {
+        tree.nodes[Mcts::ROOT]
+            .children
+            .iter()
+            .position(|&child| matches!(tree.nodes[child].action, Some(Action::CastCard { .. })))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_into_subtree() {
+        let (mut env, actions) = start_game();
+        let mut tree = Mcts::new(&env, actions.player, SmallRng::seed_from_u64(0));
+        tree.search(200, EXPLORATION_CONSTANT);
+
+        let chosen = cast_index(&tree);
+        let child = tree.nodes[Mcts::ROOT].children[chosen];
+        let mut descendants = vec![];
+        let mut stack = vec![child];
+        while let Some(index) = stack.pop() {
+            descendants.push(index);
+            stack.extend(&tree.nodes[index].children);
+        }
+        let mut visits = descendants
+            .iter()
+            .map(|&index| tree.nodes[index].visits)
+            .collect::<Vec<_>>();
+
+        let action = tree.nodes[child].action.clone();
+        let report = env.process(actions.player, action);
+        assert_eq!(report.available_actions.unwrap().player, actions.player);
+
+        let hash = env.state.redacted_hash(actions.player);
+        let subtree = tree.into_subtree(chosen, actions.player, hash).unwrap();
+        assert_consistent(&subtree);
+        assert_eq!(subtree.nodes.len(), descendants.len());
+        assert_eq!(subtree.nodes[Mcts::ROOT].visits, visits[0]);
+        let mut subtree_visits = subtree
+            .nodes
+            .iter()
+            .map(|node| node.visits)
+            .collect::<Vec<_>>();
+        subtree_visits.sort();
+        visits.sort();
+        assert_eq!(subtree_visits, visits);
+    }
+
+    #[test]
+    fn test_reuse_after_own_action() {
+        let (mut env, actions) = start_game();
+        let mut bot = MctsBot::new(SearchBudget::default(), 2);
+        bot.start_search(&env, actions.player);
+        bot.search(100);
+
+        let chosen = cast_index(&bot.trees[0]);
+        let action = bot.trees[0].child(chosen).unwrap().action.clone();
+        let visits = bot
+            .trees
+            .iter()
+            .map(|tree| tree.child(chosen).unwrap().visits)
+            .collect::<Vec<_>>();
+        bot.chosen = Some(chosen);
+
+        let report = env.process(actions.player, action);
+        assert_eq!(report.available_actions.unwrap().player, actions.player);
+        bot.start_search(&env, actions.player);
+        assert_eq!(
+            bot.trees
+                .iter()
+                .map(|tree| tree.nodes[Mcts::ROOT].visits)
+                .collect::<Vec<_>>(),
+            visits
+        );
+    }
+
+    #[test]
+    fn test_minimal_budget() {
+        let (env, actions) = start_game();
+        let mut bot = MctsBot::new(
+            SearchBudget {
+                iterations: 2,
+                time: None,
+            },
+            2,
+        );
+        let action = bot.compute_best_action(Arc::new(env), &actions);
+        assert!(action.is_some());
+        for tree in &bot.trees {
+            assert_eq!(tree.nodes[Mcts::ROOT].visits, 1);
+        }
+    }
+}
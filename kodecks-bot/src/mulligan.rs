@@ -0,0 +1,54 @@
+use crate::score::ComputedScore;
+use kodecks::{
+    action::Action,
+    card::Card,
+    env::Environment,
+    id::{TimedCardId, TimedObjectId},
+    regulation::MulliganPolicy,
+};
+
+const MAX_EARLY_COST: u8 = 2;
+const MIN_EARLY_CARDS: usize = 2;
+
+pub fn find_mulligan(
+    env: &Environment,
+    cards: &[TimedObjectId],
+) -> Option<(Action, ComputedScore)> {
+    let mut cards = cards
+        .iter()
+        .filter_map(|id| env.state.find_card(*id).ok())
+        .collect::<Vec<_>>();
+    let is_early = |card: &Card| card.computed().cost.value() <= MAX_EARLY_COST;
+    let early_cards = cards.iter().filter(|card| is_early(card)).count();
+    if early_cards >= MIN_EARLY_CARDS {
+        return None;
+    }
+
+    let returned = match env.state.regulation.mulligan {
+        MulliganPolicy::Disabled => return None,
+        MulliganPolicy::Redraw => {
+            if early_cards > 0 {
+                return None;
+            }
+            cards.iter().map(|card| card.timed_id()).collect::<Vec<_>>()
+        }
+        MulliganPolicy::Bottom => {
+            cards.retain(|card| !is_early(card));
+            cards.sort_by_key(|card| std::cmp::Reverse(card.computed().cost.value()));
+            cards
+                .iter()
+                .take(MIN_EARLY_CARDS - early_cards)
+                .map(|card| card.timed_id())
+                .collect::<Vec<_>>()
+        }
+    };
+    if returned.is_empty() {
+        return None;
+    }
+
+    let score = ComputedScore {
+        base: 0,
+        action: returned.len() as i32,
+    };
+    Some((Action::Mulligan { cards: returned }, score))
+}
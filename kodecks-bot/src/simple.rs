@@ -1,9 +1,10 @@
-use crate::{score::ComputedScore, Bot};
+use crate::{mulligan::find_mulligan, score::ComputedScore, Bot};
 use kodecks::{
     action::{Action, AvailableAction, PlayerAvailableActions},
     env::Environment,
     id::TimedCardId,
     score::Score,
+    target::Target,
 };
 use std::sync::Arc;
 use tracing::debug;
@@ -17,6 +18,12 @@ impl Bot for SimpleBot {
         actions: &PlayerAvailableActions,
     ) -> Vec<(Action, ComputedScore)> {
         for action in actions.actions.as_ref() {
+            if let AvailableAction::Mulligan { cards } = action {
+                if let Some(mulligan) = find_mulligan(&env, cards) {
+                    return vec![mulligan];
+                }
+            }
+
             if let AvailableAction::SelectCard { cards } = action {
                 let best_candidate = cards
                     .iter()
@@ -41,48 +48,77 @@ impl Bot for SimpleBot {
                 }
             }
 
-            if let AvailableAction::Attack { attackers } = action {
+            if let AvailableAction::Attack { attackers, targets } = action {
                 let player = if let Ok(player) = env.state.players().get(actions.player) {
                     player
                 } else {
                     return vec![];
                 };
-                let opponent = if let Ok(opponent) = env.state.players().next_player(actions.player)
-                {
-                    opponent
-                } else {
-                    return vec![];
-                };
 
-                let blockers = opponent
-                    .field
-                    .active_cards()
+                let blocker_power_sum = env
+                    .state
+                    .players()
+                    .opponents(actions.player)
+                    .flat_map(|opponent| opponent.field.active_cards())
                     .filter_map(|card| card.computed().power)
                     .map(|power| power.value())
-                    .collect::<Vec<_>>();
-                let blocker_power_sum = blockers.iter().sum::<u32>();
-                let max_blocker_power = blockers.iter().copied().max().unwrap_or_default();
+                    .sum::<u32>();
                 if blocker_power_sum >= player.stats.life {
                     return vec![(
-                        Action::Attack { attackers: vec![] },
+                        Action::Attack {
+                            attackers: vec![],
+                            target: None,
+                        },
                         ComputedScore::default(),
                     )];
                 }
-                let attackers = attackers
-                    .iter()
-                    .filter_map(|id| env.state.find_card(*id).ok())
-                    .filter(|card| {
-                        let power = card
-                            .computed()
-                            .power
+
+                // Attack the opponent with the lowest life among those whose blockers
+                // cannot stop the attackers.
+                let targets = if targets.is_empty() {
+                    vec![None]
+                } else {
+                    targets.iter().copied().map(Some).collect()
+                };
+                let attack = targets
+                    .into_iter()
+                    .filter_map(|target| {
+                        let opponent = match target {
+                            Some(Target::Player(id)) => env.state.players().get(id).ok()?,
+                            _ => env.state.players().next_player(actions.player).ok()?,
+                        };
+                        let max_blocker_power = opponent
+                            .field
+                            .active_cards()
+                            .filter_map(|card| card.computed().power)
                             .map(|power| power.value())
+                            .max()
                             .unwrap_or_default();
-                        power > 0 && power > max_blocker_power
+                        let attackers = attackers
+                            .iter()
+                            .filter_map(|id| env.state.find_card(*id).ok())
+                            .filter(|card| {
+                                let power = card
+                                    .computed()
+                                    .power
+                                    .map(|power| power.value())
+                                    .unwrap_or_default();
+                                power > 0 && power > max_blocker_power
+                            })
+                            .map(|card| card.timed_id())
+                            .collect::<Vec<_>>();
+                        if attackers.is_empty() {
+                            None
+                        } else {
+                            Some((opponent.stats.life, attackers, target))
+                        }
                     })
-                    .map(|card| card.timed_id())
-                    .collect::<Vec<_>>();
-                if !attackers.is_empty() {
-                    return vec![(Action::Attack { attackers }, ComputedScore::default())];
+                    .min_by_key(|(life, _, _)| *life);
+                if let Some((_, attackers, target)) = attack {
+                    return vec![(
+                        Action::Attack { attackers, target },
+                        ComputedScore::default(),
+                    )];
                 }
             }
 
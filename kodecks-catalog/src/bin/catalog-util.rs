@@ -1,20 +1,508 @@
 use bpaf::Bpaf;
-use kodecks::{card::safe_name, color::Color};
-use kodecks_catalog::CATALOG;
-use std::io::Write;
+use kodecks::{
+    action::Action,
+    archetype::EffectFactory,
+    booster::SealedConfig,
+    card::{safe_name, CardEntry},
+    catalog::Catalog,
+    color::Color,
+    deck::{AnalysisConfig, DeckAnalysis, DeckItem, DeckList},
+    env::Environment,
+    id::ObjectId,
+    profile::GameProfile,
+    zone::CardZone,
+};
+use kodecks_catalog::{export, locale, CATALOG};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::io::{self, BufRead, Write};
 use std::{
     collections::{BTreeMap, HashSet},
-    fs,
-    path::Path,
+    fs, mem,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-fn main() {
+fn main() -> ExitCode {
     let opts = options().run();
+    if let Some(dir) = opts.lint {
+        return lint(&dir);
+    }
+    if opts.check_locales {
+        return check_locales(opts.cards.as_deref(), opts.locales.as_deref());
+    }
+    if let Some(path) = opts.export {
+        return export_catalog(&path, opts.cards.as_deref(), opts.locales.as_deref());
+    }
+    if !opts.diff.is_empty() {
+        return diff_exports(&opts.diff, opts.cards.as_deref(), opts.locales.as_deref());
+    }
+    if let Some(query) = opts.search {
+        return search(&query, opts.cards.as_deref());
+    }
+    if let Some(deck) = opts.analyze {
+        return analyze(&deck, opts.cards.as_deref());
+    }
+    if let Some(packs) = opts.open_packs {
+        return open_packs(packs, opts.seed, opts.set, opts.cards.as_deref());
+    }
+    if let Some(profile) = opts.repl {
+        return repl(
+            &profile,
+            opts.actions.as_deref(),
+            opts.cards.as_deref(),
+            opts.source,
+        );
+    }
     if let Some(name) = opts.card_name {
         add_card(&name, opts.card_id);
     } else {
         show_stat();
     }
+    ExitCode::SUCCESS
+}
+
+fn lint(dir: &Path) -> ExitCode {
+    let catalog = match Catalog::load_from_dir(dir) {
+        Ok(catalog) => catalog,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut errors = 0;
+    for archetype in catalog.iter() {
+        if let EffectFactory::Script(def) = &archetype.effect {
+            for diagnostic in def.module().check() {
+                eprintln!("{} ({}): {diagnostic}", archetype.safe_name, archetype.id);
+                errors += 1;
+            }
+        }
+    }
+
+    if errors > 0 {
+        eprintln!("{errors} problem(s) found");
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn load_catalog(cards: Option<&Path>) -> Result<Arc<Catalog>, String> {
+    if let Some(dir) = cards {
+        Ok(Arc::new(
+            Catalog::load_from_dir(dir).map_err(|err| err.to_string())?,
+        ))
+    } else {
+        Ok(CATALOG.clone())
+    }
+}
+
+/// Returns the locales directory given by `--locales`, or the client assets
+/// of the source tree this binary was built from if it still exists.
+fn locales_dir(locales: Option<&Path>) -> Result<PathBuf, String> {
+    if let Some(dir) = locales {
+        return Ok(dir.to_path_buf());
+    }
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../kodecks-bevy/assets/locales");
+    if dir.is_dir() {
+        Ok(dir)
+    } else {
+        Err(format!(
+            "The client locales {dir:?} do not exist, pass --locales DIR to read them from elsewhere"
+        ))
+    }
+}
+
+fn check_locales(cards: Option<&Path>, locales: Option<&Path>) -> ExitCode {
+    let problems = load_catalog(cards)
+        .and_then(|catalog| locale::check_locales(&locales_dir(locales)?, &catalog));
+    match problems {
+        Ok(problems) if problems.is_empty() => ExitCode::SUCCESS,
+        Ok(problems) => {
+            for problem in &problems {
+                eprintln!("{problem}");
+            }
+            eprintln!("{} problem(s) found", problems.len());
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn export_records(
+    cards: Option<&Path>,
+    locales: Option<&Path>,
+) -> Result<Vec<export::CardRecord>, String> {
+    let catalog = load_catalog(cards)?;
+    let locales = export::load_locales(&locales_dir(locales)?)?;
+    Ok(export::export(&catalog, &locales))
+}
+
+fn export_catalog(path: &Path, cards: Option<&Path>, locales: Option<&Path>) -> ExitCode {
+    let records = match export_records(cards, locales) {
+        Ok(records) => records,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let data = if path.extension().is_some_and(|ext| ext == "csv") {
+        export::to_csv(&records)
+    } else {
+        serde_json::to_string_pretty(&records).unwrap()
+    };
+    if let Err(err) = fs::write(path, data) {
+        eprintln!("Failed to write {path:?}: {err}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Compares two exports, or an export with the current catalog.
+fn diff_exports(paths: &[PathBuf], cards: Option<&Path>, locales: Option<&Path>) -> ExitCode {
+    let rows = match paths {
+        [old] => export::read_export(old).and_then(|old| {
+            let new = export_records(cards, locales)?
+                .iter()
+                .map(|record| record.columns().into_iter().collect())
+                .collect::<Vec<_>>();
+            Ok((old, new))
+        }),
+        [old, new] => export::read_export(old).and_then(|old| Ok((old, export::read_export(new)?))),
+        _ => Err("--diff takes one or two files".to_string()),
+    };
+    let (old, new) = match rows {
+        Ok(rows) => rows,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    for change in export::diff(&old, &new) {
+        println!("{change}");
+    }
+    ExitCode::SUCCESS
+}
+
+fn search(query: &str, cards: Option<&Path>) -> ExitCode {
+    let catalog = match load_catalog(cards) {
+        Ok(catalog) => catalog,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let results = match catalog.search(query) {
+        Ok(results) => results,
+        Err(err) => {
+            eprintln!("Invalid query: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("ID\tSet\tRarity\tCost\tColor\tType\tPower\tName");
+    for archetype in results {
+        let attr = &archetype.attribute;
+        println!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            archetype.id,
+            catalog
+                .set_of(archetype.id)
+                .map(|set| set.code())
+                .unwrap_or_default(),
+            attr.rarity,
+            attr.cost,
+            attr.color,
+            attr.card_type,
+            attr.power
+                .map(|power| power.to_string())
+                .unwrap_or_default(),
+            archetype.name
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+/// Reads a deck from a file in the deck list format, or from a deck code.
+fn load_deck(deck: &str, catalog: &Catalog) -> Result<DeckList, String> {
+    let path = Path::new(deck);
+    if path.is_file() {
+        let data =
+            fs::read_to_string(path).map_err(|err| format!("Failed to read {path:?}: {err}"))?;
+        DeckList::parse(&data, catalog).ok_or_else(|| format!("Invalid deck list {path:?}"))
+    } else {
+        DeckList::from_code(deck, catalog).map_err(|err| err.to_string())
+    }
+}
+
+fn analyze(deck: &str, cards: Option<&Path>) -> ExitCode {
+    let result = load_catalog(cards).and_then(|catalog| {
+        let deck = load_deck(deck, &catalog)?;
+        let first = DeckAnalysis::new(&deck, &catalog, &AnalysisConfig::default());
+        let second = DeckAnalysis::new(
+            &deck,
+            &catalog,
+            &AnalysisConfig {
+                draw_first_turn: true,
+                ..Default::default()
+            },
+        );
+        Ok((first, second))
+    });
+    let (analysis, on_draw) = match result {
+        Ok(analysis) => analysis,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("Cards\t{}", analysis.size);
+    println!("Creatures\t{}", analysis.creatures);
+    println!("Hexes\t{}", analysis.hexes);
+
+    println!("\nCost\tCount");
+    println!("----\t-----");
+    for (cost, count) in &analysis.cost_curve {
+        println!("{}\t{}", cost, count);
+    }
+
+    println!("\nColor\tCount\tShards");
+    println!("-----\t-----\t------");
+    for (color, count) in &analysis.colors {
+        println!("{}\t{}\t{}", color, count, analysis.shards[color]);
+    }
+    println!(
+        "Expected shards per charge: {:.2}",
+        analysis.expected_shards()
+    );
+
+    println!("\nPower\tCount");
+    println!("-----\t-----");
+    for (power, count) in &analysis.power_curve {
+        println!("{}\t{}", power, count);
+    }
+
+    println!("\nTurn\tCost 0 (first)\tCost 0 (second)");
+    println!("----\t--------------\t---------------");
+    for (turn, (first, second)) in analysis
+        .free_cast_odds
+        .iter()
+        .zip(&on_draw.free_cast_odds)
+        .enumerate()
+    {
+        println!(
+            "{}\t{:.1}%\t{:.1}%",
+            turn + 1,
+            first * 100.0,
+            second * 100.0
+        );
+    }
+    ExitCode::SUCCESS
+}
+
+/// Prints the cards of sealed packs as a deck list.
+fn open_packs(packs: u8, seed: Option<u64>, set: Option<String>, cards: Option<&Path>) -> ExitCode {
+    let catalog = match load_catalog(cards) {
+        Ok(catalog) => catalog,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if set.as_ref().is_some_and(|code| catalog.set(code).is_none()) {
+        eprintln!("Unknown card set: {}", set.unwrap_or_default());
+        return ExitCode::FAILURE;
+    }
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+    let config = SealedConfig {
+        seed,
+        packs,
+        layout: Default::default(),
+        set,
+    };
+    let pool = config.open(&catalog, 1).remove(0);
+    let deck = DeckList {
+        name: format!("Sealed {seed}"),
+        cards: pool
+            .into_iter()
+            .map(|archetype_id| DeckItem {
+                card: CardEntry {
+                    archetype_id,
+                    style: 0,
+                },
+                base_id: None,
+            })
+            .collect(),
+        ..Default::default()
+    };
+    println!("# {}", deck.name);
+    for (card, count) in deck.count_cards() {
+        println!("{} {}", catalog[card].name, count);
+    }
+    ExitCode::SUCCESS
+}
+
+/// A player input used to advance the game before the REPL starts.
+#[derive(Debug, Deserialize)]
+struct Step {
+    player: u8,
+    #[serde(default)]
+    action: Option<Action>,
+}
+
+fn repl(
+    profile: &Path,
+    actions: Option<&Path>,
+    cards: Option<&Path>,
+    source: Option<u32>,
+) -> ExitCode {
+    let env = match start_game(profile, actions, cards) {
+        Ok(env) => env,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let source = source.and_then(|id| ObjectId::try_from(id).ok());
+    run_repl(env, source);
+    ExitCode::SUCCESS
+}
+
+fn read_json<T>(path: &Path) -> Result<T, String>
+where
+    T: DeserializeOwned,
+{
+    let data = fs::read_to_string(path).map_err(|err| format!("Failed to read {path:?}: {err}"))?;
+    serde_json::from_str(&data).map_err(|err| format!("Invalid file {path:?}: {err}"))
+}
+
+fn start_game(
+    profile: &Path,
+    actions: Option<&Path>,
+    cards: Option<&Path>,
+) -> Result<Environment, String> {
+    let catalog = load_catalog(cards)?;
+    let profile: GameProfile = read_json(profile)?;
+    if profile.rng_seed.is_none() {
+        return Err("The profile must have an rng_seed".to_string());
+    }
+    let steps: Vec<Step> = actions.map(read_json).transpose()?.unwrap_or_default();
+
+    let mut env = Environment::new(profile, catalog);
+    for (i, step) in steps.into_iter().enumerate() {
+        if !env.accepts(step.player, step.action.as_ref()) {
+            return Err(format!(
+                "Invalid action at step {i}: player: {} action: {:?}",
+                step.player, step.action
+            ));
+        }
+        env.process(step.player, step.action);
+    }
+    Ok(env)
+}
+
+const REPL_HELP: &str = "\
+:cards        List the cards in the game
+:source ID    Evaluate expressions as an effect of the card ID
+:apply        Apply the commands of the last evaluation
+:quit         Exit
+Anything else is evaluated as a script expression.";
+
+fn run_repl(mut env: Environment, mut source: Option<ObjectId>) {
+    println!("Type :help for the list of commands.");
+    let mut stdin = io::stdin().lock();
+    let mut commands = vec![];
+    loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if stdin.read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        let line = line.trim();
+        let (command, arg) = line.split_once(' ').unwrap_or((line, ""));
+        match command {
+            "" => {}
+            ":quit" | ":q" => break,
+            ":help" => println!("{REPL_HELP}"),
+            ":cards" => list_cards(&env),
+            ":source" => {
+                let id = arg
+                    .trim()
+                    .parse::<u32>()
+                    .ok()
+                    .and_then(|id| ObjectId::try_from(id).ok())
+                    .filter(|id| env.state.find_card(*id).is_ok());
+                if id.is_some() {
+                    source = id;
+                } else {
+                    eprintln!("No card with id {arg}");
+                }
+            }
+            ":apply" => {
+                if commands.is_empty() {
+                    eprintln!("No commands to apply");
+                }
+                for log in env.apply_commands(mem::take(&mut commands)) {
+                    println!("{log:?}");
+                }
+            }
+            _ if command.starts_with(':') => eprintln!("Unknown command: {command}"),
+            _ => {
+                let Some(source) = source else {
+                    eprintln!("Select a source card with :source first");
+                    continue;
+                };
+                match env.eval_script(source, line) {
+                    Ok(output) => {
+                        for value in &output.values {
+                            println!("{value}");
+                        }
+                        for command in &output.report.commands {
+                            println!("command: {}", serde_json::to_string(command).unwrap());
+                        }
+                        commands = output.report.commands;
+                    }
+                    Err(err) => eprintln!("{err}"),
+                }
+            }
+        }
+    }
+}
+
+fn list_cards(env: &Environment) {
+    println!("ID\tPlayer\tZone\tName");
+    for player in env.state.players.iter() {
+        for zone in [
+            &player.field,
+            &player.hand,
+            &player.graveyard,
+            &player.limbo,
+            &player.deck,
+        ] {
+            for card in zone.iter() {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    u32::from(card.id()),
+                    card.zone().player,
+                    card.zone().kind,
+                    card.archetype().name
+                );
+            }
+        }
+    }
 }
 
 fn add_card(name: &str, id: Option<String>) {
@@ -146,6 +634,63 @@ fn show_stat() {
 #[derive(Debug, Clone, Bpaf)]
 #[bpaf(options)]
 pub struct Options {
+    #[bpaf(long("lint"), argument("DIR"))]
+    /// Check the scripts of the card definitions in DIR
+    lint: Option<PathBuf>,
+
+    #[bpaf(long("search"), argument("QUERY"))]
+    /// List the cards matching QUERY, e.g. "color:red cost<=2"
+    search: Option<String>,
+
+    #[bpaf(long("export"), argument("FILE"))]
+    /// Export all cards to FILE, as CSV if FILE ends with .csv and as JSON otherwise
+    export: Option<PathBuf>,
+
+    #[bpaf(long("diff"), argument("FILE"))]
+    /// Show the changes between two exports, or between an export and the current catalog
+    diff: Vec<PathBuf>,
+
+    #[bpaf(long("check-locales"))]
+    /// Check that the locales define every message the game uses, and nothing stale
+    check_locales: bool,
+
+    #[bpaf(long("locales"), argument("DIR"))]
+    /// Read the locales from DIR instead of the client assets, required when the
+    /// binary runs outside the source tree
+    locales: Option<PathBuf>,
+
+    #[bpaf(long("analyze"), argument("DECK"))]
+    /// Show the cost curve, colors, shards and draw odds of DECK, a deck code or a deck list file
+    analyze: Option<String>,
+
+    #[bpaf(long("open-packs"), argument("N"))]
+    /// Open N booster packs and print the cards as a deck list
+    open_packs: Option<u8>,
+
+    #[bpaf(long("seed"), argument("SEED"))]
+    /// Use SEED to open the packs, instead of a random one
+    seed: Option<u64>,
+
+    #[bpaf(long("set"), argument("CODE"))]
+    /// Open packs of the card set CODE only
+    set: Option<String>,
+
+    #[bpaf(long("repl"), argument("PROFILE"))]
+    /// Evaluate scripts interactively in a game started from the JSON profile PROFILE
+    repl: Option<PathBuf>,
+
+    #[bpaf(long("actions"), argument("FILE"))]
+    /// Advance the REPL game by the JSON list of player actions in FILE
+    actions: Option<PathBuf>,
+
+    #[bpaf(long("cards"), argument("DIR"))]
+    /// Use the card definitions in DIR instead of the built-in catalog
+    cards: Option<PathBuf>,
+
+    #[bpaf(long("source"), argument("ID"))]
+    /// Select the card ID as the initial source of the REPL
+    source: Option<u32>,
+
     #[bpaf(positional("NAME"))]
     /// Add a new card
     card_name: Option<String>,
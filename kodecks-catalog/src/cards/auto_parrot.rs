@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Cyborg,
     power: 300,
     shards: 4,
+    rarity: Rarity::Common,
 );
 
 impl Effect for CardDef {}
@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Mutant,
     power: 700,
     shards: 6,
+    rarity: Rarity::Uncommon,
 );
 
 impl Effect for CardDef {}
@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Robot,
     power: 300,
     shards: 0,
+    rarity: Rarity::Uncommon,
 );
 
 impl Effect for CardDef {
@@ -18,27 +19,31 @@ impl Effect for CardDef {
         EventFilter::ATTACKING
     }
 
-    fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> Result<()> {
-        if id == "main" {
-            ctx.push_stack("main", |ctx, _| {
-                let target = ctx.source().controller();
-                let power = ctx
-                    .source()
-                    .computed()
-                    .power
-                    .map_or(0, |power| power.value());
-                let commands = if power > 0 {
-                    vec![ActionCommand::InflictDamage {
-                        target,
-                        amount: power,
-                    }]
-                } else {
-                    vec![]
-                };
-                Ok(EffectReport::default().with_commands(commands))
-            });
+    fn resolve(
+        &mut self,
+        id: EffectId,
+        _args: &[Value],
+        ctx: &mut EffectTriggerContext,
+        _action: Option<Action>,
+    ) -> Result<EffectReport> {
+        if id != "main" {
+            return Ok(EffectReport::default());
         }
-        Ok(())
+        let target = ctx.source().controller();
+        let power = ctx
+            .source()
+            .computed()
+            .power
+            .map_or(0, |power| power.value());
+        let commands = if power > 0 {
+            vec![ActionCommand::InflictDamage {
+                target,
+                amount: power,
+            }]
+        } else {
+            vec![]
+        };
+        Ok(EffectReport::default().with_commands(commands))
     }
 
     fn activate(&mut self, _event: CardEvent, ctx: &mut EffectActivateContext) -> Result<()> {
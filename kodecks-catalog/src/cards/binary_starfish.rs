@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Mutant,
     power: 200,
     shards: 1,
+    rarity: Rarity::Uncommon,
 );
 
 impl Effect for CardDef {
@@ -18,19 +19,23 @@ impl Effect for CardDef {
         EventFilter::CASTED
     }
 
-    fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> Result<()> {
-        if id == "main" {
-            ctx.push_stack("main", |ctx, _| {
-                let token = ctx.new_id();
-                let commands = vec![ActionCommand::GenerateCardToken {
-                    token,
-                    archetype: ctx.source().archetype().id,
-                    player: ctx.source().controller(),
-                }];
-                Ok(EffectReport::default().with_commands(commands))
-            });
+    fn resolve(
+        &mut self,
+        id: EffectId,
+        _args: &[Value],
+        ctx: &mut EffectTriggerContext,
+        _action: Option<Action>,
+    ) -> Result<EffectReport> {
+        if id != "main" {
+            return Ok(EffectReport::default());
         }
-        Ok(())
+        let token = ctx.new_id();
+        let commands = vec![ActionCommand::GenerateCardToken {
+            token,
+            archetype: ctx.source().archetype().id,
+            player: ctx.source().controller(),
+        }];
+        Ok(EffectReport::default().with_commands(commands))
     }
 
     fn activate(&mut self, event: CardEvent, ctx: &mut EffectActivateContext) -> Result<()> {
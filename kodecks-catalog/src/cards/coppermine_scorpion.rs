@@ -12,6 +12,7 @@ card_def!(
     power: 200,
     abilities: &[KeywordAbility::Toxic][..],
     shards: 3,
+    rarity: Rarity::Common,
 );
 
 impl Effect for CardDef {}
@@ -12,6 +12,7 @@ card_def!(
     power: 500,
     abilities: &[KeywordAbility::Stealth][..],
     shards: 5,
+    rarity: Rarity::Rare,
 );
 
 impl Effect for CardDef {
@@ -19,24 +20,28 @@ impl Effect for CardDef {
         EventFilter::CASTED
     }
 
-    fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> Result<()> {
-        if id == "main" {
-            ctx.push_stack("main", |ctx, _| {
-                let power = ctx.source().computed().current_power();
-                let commands = ctx
-                    .state()
-                    .players()
-                    .iter()
-                    .flat_map(|p| p.field.iter())
-                    .filter(|card| card.computed().current_power() < power)
-                    .map(|card| ActionCommand::ShuffleCardIntoDeck {
-                        source: ctx.source().id(),
-                        target: card.timed_id(),
-                    });
-                Ok(EffectReport::default().with_commands(commands))
-            });
+    fn resolve(
+        &mut self,
+        id: EffectId,
+        _args: &[Value],
+        ctx: &mut EffectTriggerContext,
+        _action: Option<Action>,
+    ) -> Result<EffectReport> {
+        if id != "main" {
+            return Ok(EffectReport::default());
         }
-        Ok(())
+        let power = ctx.source().computed().current_power();
+        let commands = ctx
+            .state()
+            .players()
+            .iter()
+            .flat_map(|p| p.field.iter())
+            .filter(|card| card.computed().current_power() < power)
+            .map(|card| ActionCommand::ShuffleCardIntoDeck {
+                source: ctx.source().id(),
+                target: card.timed_id(),
+            });
+        Ok(EffectReport::default().with_commands(commands))
     }
 
     fn activate(&mut self, _event: CardEvent, ctx: &mut EffectActivateContext) -> Result<()> {
@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Cyborg,
     power: 100,
     shards: 0,
+    rarity: Rarity::Uncommon,
 );
 
 impl Effect for CardDef {
@@ -18,21 +19,25 @@ impl Effect for CardDef {
         EventFilter::DEALT_DAMAGE
     }
 
-    fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> Result<()> {
-        if id == "main" {
-            ctx.push_stack("main", |ctx, _| {
-                let card = ctx.source();
-                let target = card.zone().player;
-                let commands = vec![ActionCommand::GenerateShards {
-                    player: target,
-                    source: ctx.source().id(),
-                    color: card.computed().color,
-                    amount: 1,
-                }];
-                Ok(EffectReport::default().with_commands(commands))
-            });
+    fn resolve(
+        &mut self,
+        id: EffectId,
+        _args: &[Value],
+        ctx: &mut EffectTriggerContext,
+        _action: Option<Action>,
+    ) -> Result<EffectReport> {
+        if id != "main" {
+            return Ok(EffectReport::default());
         }
-        Ok(())
+        let card = ctx.source();
+        let target = card.zone().player;
+        let commands = vec![ActionCommand::GenerateShards {
+            player: target,
+            source: ctx.source().id(),
+            color: card.computed().color,
+            amount: 1,
+        }];
+        Ok(EffectReport::default().with_commands(commands))
     }
 
     fn activate(&mut self, event: CardEvent, ctx: &mut EffectActivateContext) -> Result<()> {
@@ -12,6 +12,7 @@ card_def!(
     power: 100,
     abilities: &[KeywordAbility::Volatile][..],
     shards: 1,
+    rarity: Rarity::Common,
 );
 
 impl Effect for CardDef {}
@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Cyborg,
     power: 300,
     shards: 4,
+    rarity: Rarity::Uncommon,
 );
 
 impl Effect for CardDef {
@@ -18,24 +19,28 @@ impl Effect for CardDef {
         EventFilter::CASTED
     }
 
-    fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> Result<()> {
-        if id == "main" {
-            ctx.push_stack("main", |ctx, _| {
-                let commands = ctx
-                    .state()
-                    .players()
-                    .iter()
-                    .flat_map(|p| p.field.iter())
-                    .map(|card| ActionCommand::SetFieldState {
-                        source: ctx.source().id(),
-                        target: card.timed_id(),
-                        state: kodecks::field::FieldState::Exhausted,
-                        reason: EventReason::Effect,
-                    });
-                Ok(EffectReport::default().with_commands(commands))
-            });
+    fn resolve(
+        &mut self,
+        id: EffectId,
+        _args: &[Value],
+        ctx: &mut EffectTriggerContext,
+        _action: Option<Action>,
+    ) -> Result<EffectReport> {
+        if id != "main" {
+            return Ok(EffectReport::default());
         }
-        Ok(())
+        let commands = ctx
+            .state()
+            .players()
+            .iter()
+            .flat_map(|p| p.field.iter())
+            .map(|card| ActionCommand::SetFieldState {
+                source: ctx.source().id(),
+                target: card.timed_id(),
+                state: kodecks::field::FieldState::Exhausted,
+                reason: EventReason::Effect,
+            });
+        Ok(EffectReport::default().with_commands(commands))
     }
 
     fn activate(&mut self, _event: CardEvent, ctx: &mut EffectActivateContext) -> Result<()> {
@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Mutant,
     power: 200,
     shards: 5,
+    rarity: Rarity::Common,
 );
 
 impl Effect for CardDef {}
@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Cyborg,
     power: 100,
     shards: 3,
+    rarity: Rarity::Common,
 );
 
 impl Effect for CardDef {}
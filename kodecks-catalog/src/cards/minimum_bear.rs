@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Program,
     power: 100,
     shards: 5,
+    rarity: Rarity::Common,
 );
 
 impl Effect for CardDef {}
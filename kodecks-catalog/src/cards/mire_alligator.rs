@@ -12,6 +12,7 @@ card_def!(
     power: 400,
     abilities: &[KeywordAbility::Devour][..],
     shards: 6,
+    rarity: Rarity::Rare,
 );
 
 impl Effect for CardDef {
@@ -19,45 +20,49 @@ impl Effect for CardDef {
         EventFilter::CASTED
     }
 
-    fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> Result<()> {
-        if id == "main" {
-            ctx.push_stack("main", |ctx, action| {
-                let controller = ctx.source().controller();
-                let player = ctx.state().players().get(controller)?;
-                let candidates = player
-                    .field
-                    .iter()
-                    .filter(|card| card.flags().is_targetable())
-                    .map(|card| card.timed_id())
-                    .collect::<Vec<_>>();
-                if candidates.is_empty() {
-                    return Ok(EffectReport::default());
-                }
-                if let Some(Action::SelectCard { card }) = action {
-                    let target = ctx.state().find_card(card)?;
-                    let commands = vec![ActionCommand::DestroyCard {
-                        source: ctx.source().id(),
-                        target: target.timed_id(),
-                        reason: EventReason::Effect,
-                    }];
-                    return Ok(EffectReport::default().with_commands(commands));
-                }
-                Ok(
-                    EffectReport::default().with_available_actions(PlayerAvailableActions {
-                        player: controller,
-                        actions: vec![AvailableAction::SelectCard { cards: candidates }]
-                            .into_iter()
-                            .collect(),
-                        instructions: Some(Message {
-                            id: "card-mire-alligator.message".to_string(),
-                            ..Default::default()
-                        }),
-                        message_dialog: None,
-                    }),
-                )
-            });
+    fn resolve(
+        &mut self,
+        id: EffectId,
+        _args: &[Value],
+        ctx: &mut EffectTriggerContext,
+        action: Option<Action>,
+    ) -> Result<EffectReport> {
+        if id != "main" {
+            return Ok(EffectReport::default());
         }
-        Ok(())
+        let controller = ctx.source().controller();
+        let player = ctx.state().players().get(controller)?;
+        let candidates = player
+            .field
+            .iter()
+            .filter(|card| card.flags().is_targetable())
+            .map(|card| card.timed_id())
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return Ok(EffectReport::default());
+        }
+        if let Some(Action::SelectCard { card }) = action {
+            let target = ctx.state().find_card(card)?;
+            let commands = vec![ActionCommand::DestroyCard {
+                source: ctx.source().id(),
+                target: target.timed_id(),
+                reason: EventReason::Effect,
+            }];
+            return Ok(EffectReport::default().with_commands(commands));
+        }
+        Ok(
+            EffectReport::default().with_available_actions(PlayerAvailableActions {
+                player: controller,
+                actions: vec![AvailableAction::SelectCard { cards: candidates }]
+                    .into_iter()
+                    .collect(),
+                instructions: Some(Message {
+                    id: "card-mire-alligator.message".to_string(),
+                    ..Default::default()
+                }),
+                message_dialog: None,
+            }),
+        )
     }
 
     fn activate(&mut self, _event: CardEvent, ctx: &mut EffectActivateContext) -> Result<()> {
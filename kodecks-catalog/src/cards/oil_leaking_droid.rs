@@ -12,6 +12,7 @@ card_def!(
     power: 100,
     abilities: &[KeywordAbility::Toxic][..],
     shards: 2,
+    rarity: Rarity::Common,
 );
 
 impl Effect for CardDef {}
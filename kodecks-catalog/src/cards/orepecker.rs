@@ -12,6 +12,7 @@ card_def!(
     power: 100,
     abilities: &[][..],
     shards: 3,
+    rarity: Rarity::Common,
 );
 
 impl Effect for CardDef {}
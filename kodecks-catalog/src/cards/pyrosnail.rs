@@ -12,6 +12,7 @@ card_def!(
     power: 100,
     abilities: &[KeywordAbility::Volatile][..],
     shards: 5,
+    rarity: Rarity::Uncommon,
 );
 
 impl Effect for CardDef {
@@ -19,18 +20,22 @@ impl Effect for CardDef {
         EventFilter::DESTROYED
     }
 
-    fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> Result<()> {
-        if id == "main" {
-            ctx.push_stack("main", |ctx, _| {
-                let target = ctx.state().players.next_id(ctx.source().controller())?;
-                let commands = vec![ActionCommand::InflictDamage {
-                    target,
-                    amount: 100,
-                }];
-                Ok(EffectReport::default().with_commands(commands))
-            });
+    fn resolve(
+        &mut self,
+        id: EffectId,
+        _args: &[Value],
+        ctx: &mut EffectTriggerContext,
+        _action: Option<Action>,
+    ) -> Result<EffectReport> {
+        if id != "main" {
+            return Ok(EffectReport::default());
         }
-        Ok(())
+        let target = ctx.state().players.next_id(ctx.source().controller())?;
+        let commands = vec![ActionCommand::InflictDamage {
+            target,
+            amount: 100,
+        }];
+        Ok(EffectReport::default().with_commands(commands))
     }
 
     fn activate(&mut self, _event: CardEvent, ctx: &mut EffectActivateContext) -> Result<()> {
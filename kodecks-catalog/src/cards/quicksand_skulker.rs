@@ -12,6 +12,7 @@ card_def!(
     power: 500,
     abilities: &[KeywordAbility::Stealth][..],
     shards: 1,
+    rarity: Rarity::Uncommon,
 );
 
 impl Effect for CardDef {}
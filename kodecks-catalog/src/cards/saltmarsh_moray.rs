@@ -12,6 +12,7 @@ card_def!(
     power: 400,
     abilities: &[KeywordAbility::Stealth][..],
     shards: 4,
+    rarity: Rarity::Common,
 );
 
 impl Effect for CardDef {}
@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Mutant,
     power: 200,
     shards: 5,
+    rarity: Rarity::Uncommon,
 );
 
 impl Effect for CardDef {
@@ -18,20 +19,24 @@ impl Effect for CardDef {
         EventFilter::CASTED
     }
 
-    fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> Result<()> {
-        if id == "main" {
-            ctx.push_stack("main", |ctx, _| {
-                Ok(
-                    EffectReport::default().with_commands(vec![ActionCommand::GenerateShards {
-                        player: ctx.source().zone().player,
-                        source: ctx.source().id(),
-                        color: ctx.source().computed().color,
-                        amount: 1,
-                    }]),
-                )
-            });
+    fn resolve(
+        &mut self,
+        id: EffectId,
+        _args: &[Value],
+        ctx: &mut EffectTriggerContext,
+        _action: Option<Action>,
+    ) -> Result<EffectReport> {
+        if id != "main" {
+            return Ok(EffectReport::default());
         }
-        Ok(())
+        Ok(
+            EffectReport::default().with_commands(vec![ActionCommand::GenerateShards {
+                player: ctx.source().zone().player,
+                source: ctx.source().id(),
+                color: ctx.source().computed().color,
+                amount: 1,
+            }]),
+        )
     }
 
     fn activate(&mut self, _event: CardEvent, ctx: &mut EffectActivateContext) -> Result<()> {
@@ -12,6 +12,7 @@ card_def!(
     power: 300,
     abilities: &[KeywordAbility::Stealth][..],
     shards: 0,
+    rarity: Rarity::Common,
 );
 
 impl Effect for CardDef {}
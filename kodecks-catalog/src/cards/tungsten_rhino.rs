@@ -12,6 +12,7 @@ card_def!(
     power: 500,
     abilities: &[][..],
     shards: 3,
+    rarity: Rarity::Uncommon,
 );
 
 impl Effect for CardDef {}
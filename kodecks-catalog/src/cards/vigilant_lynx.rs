@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Mutant,
     power: 100,
     shards: 5,
+    rarity: Rarity::Rare,
 );
 
 impl Effect for CardDef {
@@ -18,39 +19,36 @@ impl Effect for CardDef {
         EventFilter::ANY_CASTED
     }
 
-    fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> Result<()> {
-        if id == "main" {
-            ctx.push_stack("main", |ctx, _| {
-                ctx.push_continuous(
-                    Powerup {
-                        turn: ctx.state().turn,
-                    },
-                    Target::Card(ctx.source().id()),
-                );
-                Ok(EffectReport::default())
-            });
+    fn resolve(
+        &mut self,
+        id: EffectId,
+        _args: &[Value],
+        ctx: &mut EffectTriggerContext,
+        _action: Option<Action>,
+    ) -> Result<EffectReport> {
+        if id != "main" {
+            return Ok(EffectReport::default());
         }
-        Ok(())
+        let turn = ctx.state().turn;
+        let target = Target::Card(ctx.source().id());
+        ctx.push_continuous("powerup", vec![turn.into()], target);
+        Ok(EffectReport::default())
     }
 
-    fn activate(&mut self, _event: CardEvent, ctx: &mut EffectActivateContext) -> Result<()> {
-        if ctx.source().id() != ctx.target().id()
-            && ctx.source().controller() != ctx.target().controller()
-        {
-            ctx.trigger_stack("main");
+    fn apply_card(
+        &self,
+        id: EffectId,
+        args: &[Value],
+        ctx: &mut ContinuousCardEffectContext,
+    ) -> Result<bool> {
+        if id != "powerup" {
+            return Ok(true);
         }
-        Ok(())
-    }
-}
-
-#[derive(Clone)]
-struct Powerup {
-    turn: u16,
-}
-
-impl ContinuousEffect for Powerup {
-    fn apply_card(&mut self, ctx: &mut ContinuousCardEffectContext) -> Result<bool> {
-        if ctx.state.turn != self.turn {
+        let turn = match args.first() {
+            Some(Value::Constant(turn)) => turn.as_u64(),
+            _ => None,
+        };
+        if turn != Some(ctx.state.turn as u64) {
             return Ok(false);
         }
         if ctx.target.id() == ctx.source.id() {
@@ -60,4 +58,13 @@ impl ContinuousEffect for Powerup {
         }
         Ok(true)
     }
+
+    fn activate(&mut self, _event: CardEvent, ctx: &mut EffectActivateContext) -> Result<()> {
+        if ctx.source().id() != ctx.target().id()
+            && ctx.source().controller() != ctx.target().controller()
+        {
+            ctx.trigger_stack("main");
+        }
+        Ok(())
+    }
 }
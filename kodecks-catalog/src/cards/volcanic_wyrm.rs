@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Mutant,
     power: 500,
     shards: 6,
+    rarity: Rarity::Mythic,
 );
 
 impl Effect for CardDef {
@@ -18,18 +19,22 @@ impl Effect for CardDef {
         EventFilter::ATTACKING | EventFilter::BLOCKING
     }
 
-    fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> Result<()> {
-        if id == "main" {
-            ctx.push_stack("main", |ctx, _| {
-                let target = ctx.state().players.next_id(ctx.source().controller())?;
-                let commands = vec![ActionCommand::InflictDamage {
-                    target,
-                    amount: 200,
-                }];
-                Ok(EffectReport::default().with_commands(commands))
-            });
+    fn resolve(
+        &mut self,
+        id: EffectId,
+        _args: &[Value],
+        ctx: &mut EffectTriggerContext,
+        _action: Option<Action>,
+    ) -> Result<EffectReport> {
+        if id != "main" {
+            return Ok(EffectReport::default());
         }
-        Ok(())
+        let target = ctx.state().players.next_id(ctx.source().controller())?;
+        let commands = vec![ActionCommand::InflictDamage {
+            target,
+            amount: 200,
+        }];
+        Ok(EffectReport::default().with_commands(commands))
     }
 
     fn activate(&mut self, _event: CardEvent, ctx: &mut EffectActivateContext) -> Result<()> {
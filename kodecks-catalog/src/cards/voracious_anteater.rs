@@ -12,6 +12,7 @@ card_def!(
     power: 400,
     abilities: &[KeywordAbility::Devour][..],
     shards: 0,
+    rarity: Rarity::Uncommon,
 );
 
 impl Effect for CardDef {
@@ -19,19 +20,23 @@ impl Effect for CardDef {
         EventFilter::DESTROYED
     }
 
-    fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> Result<()> {
-        if id == "main" {
-            ctx.push_stack("main", |ctx, _| {
-                let token = ctx.new_id();
-                let commands = vec![ActionCommand::GenerateCardToken {
-                    token,
-                    archetype: ArchetypeId::new("ant"),
-                    player: ctx.source().controller(),
-                }];
-                Ok(EffectReport::default().with_commands(commands))
-            });
+    fn resolve(
+        &mut self,
+        id: EffectId,
+        _args: &[Value],
+        ctx: &mut EffectTriggerContext,
+        _action: Option<Action>,
+    ) -> Result<EffectReport> {
+        if id != "main" {
+            return Ok(EffectReport::default());
         }
-        Ok(())
+        let token = ctx.new_id();
+        let commands = vec![ActionCommand::GenerateCardToken {
+            token,
+            archetype: ArchetypeId::new("ant"),
+            player: ctx.source().controller(),
+        }];
+        Ok(EffectReport::default().with_commands(commands))
     }
 
     fn activate(&mut self, _event: CardEvent, ctx: &mut EffectActivateContext) -> Result<()> {
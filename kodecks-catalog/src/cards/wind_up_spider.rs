@@ -11,6 +11,7 @@ card_def!(
     creature_type: CreatureType::Robot,
     power: 100,
     shards: 2,
+    rarity: Rarity::Common,
 );
 
 impl Effect for CardDef {}
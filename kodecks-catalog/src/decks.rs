@@ -80,3 +80,21 @@ pub fn red_deck(catalog: &Catalog) -> DeckList {
     )
     .unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CATALOG;
+
+    #[test]
+    fn test_deck_code() {
+        for deck in [
+            starter_deck(&CATALOG),
+            blue_deck(&CATALOG),
+            red_deck(&CATALOG),
+        ] {
+            let decoded = DeckList::from_code(&deck.to_code(), &CATALOG).unwrap();
+            assert_eq!(decoded.cards, deck.cards);
+        }
+    }
+}
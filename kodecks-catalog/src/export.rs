@@ -0,0 +1,421 @@
+use fluent_syntax::{ast, parser, serializer};
+use kodecks::{
+    archetype::{effect::EVENT_HANDLERS, CardAttribute},
+    catalog::Catalog,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt, fs, path::Path};
+
+/// Localized text of a card, keyed by `name` and the message attributes such as `text`.
+pub type CardText = BTreeMap<String, String>;
+
+/// Card texts keyed by language, then by the safe name of the card.
+pub type Locales = BTreeMap<String, BTreeMap<String, CardText>>;
+
+/// A flattened record, keyed by column name.
+pub type Row = BTreeMap<String, String>;
+
+/// A card archetype as exported for external tools.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CardRecord {
+    pub id: String,
+    pub name: String,
+    pub safe_name: String,
+    /// The code of the card set.
+    #[serde(default)]
+    pub set: String,
+    pub attribute: CardAttribute,
+    /// Card events handled by the effect, e.g. `casted`.
+    pub events: Vec<String>,
+    #[serde(default)]
+    pub text: BTreeMap<String, CardText>,
+}
+
+impl CardRecord {
+    /// Returns the record as columns in a stable order.
+    pub fn columns(&self) -> Vec<(String, String)> {
+        let mut columns = vec![
+            ("id".to_string(), self.id.clone()),
+            ("name".to_string(), self.name.clone()),
+            ("safe_name".to_string(), self.safe_name.clone()),
+            ("set".to_string(), self.set.clone()),
+        ];
+        let attribute = serde_json::to_value(&self.attribute).unwrap_or_default();
+        flatten_value("attribute", &attribute, &mut columns);
+        columns.push(("events".to_string(), self.events.join(",")));
+        for (lang, text) in &self.text {
+            for (key, value) in text {
+                columns.push((format!("text.{lang}.{key}"), value.clone()));
+            }
+        }
+        columns
+    }
+}
+
+fn flatten_value(prefix: &str, value: &serde_json::Value, columns: &mut Vec<(String, String)>) {
+    use serde_json::Value;
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                flatten_value(&format!("{prefix}.{key}"), value, columns);
+            }
+        }
+        value => columns.push((prefix.to_string(), scalar_to_string(value))),
+    }
+}
+
+fn scalar_to_string(value: &serde_json::Value) -> String {
+    use serde_json::Value;
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items)
+            if items
+                .iter()
+                .all(|item| !item.is_array() && !item.is_object()) =>
+        {
+            items
+                .iter()
+                .map(scalar_to_string)
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+        value => value.to_string(),
+    }
+}
+
+/// Loads the card texts from `cards.ftl` in each language directory of `dir`.
+pub fn load_locales(dir: &Path) -> Result<Locales, String> {
+    let entries = fs::read_dir(dir).map_err(|err| format!("Failed to read {dir:?}: {err}"))?;
+    let mut locales = Locales::new();
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path().join("cards.ftl");
+        if !path.is_file() {
+            continue;
+        }
+        let lang = entry.file_name().to_string_lossy().to_string();
+        let source =
+            fs::read_to_string(&path).map_err(|err| format!("Failed to read {path:?}: {err}"))?;
+        locales.insert(
+            lang,
+            parse_card_texts(&source).map_err(|err| format!("{path:?}: {err}"))?,
+        );
+    }
+    Ok(locales)
+}
+
+fn parse_card_texts(source: &str) -> Result<BTreeMap<String, CardText>, String> {
+    let resource = parser::parse(source).map_err(|(_, errors)| {
+        errors
+            .iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    })?;
+    let mut texts = BTreeMap::new();
+    for entry in resource.body {
+        let ast::Entry::Message(message) = entry else {
+            continue;
+        };
+        let Some(safe_name) = message.id.name.strip_prefix("card-") else {
+            continue;
+        };
+        let mut text = CardText::new();
+        if let Some(value) = &message.value {
+            text.insert("name".to_string(), pattern_to_string(value));
+        }
+        for attr in &message.attributes {
+            text.insert(attr.id.name.to_string(), pattern_to_string(&attr.value));
+        }
+        texts.insert(safe_name.to_string(), text);
+    }
+    Ok(texts)
+}
+
+fn pattern_to_string(pattern: &ast::Pattern<&str>) -> String {
+    pattern
+        .elements
+        .iter()
+        .map(|element| match element {
+            ast::PatternElement::TextElement { value } => value.to_string(),
+            placeable => {
+                // Serialize a message with the placeable alone to get its source form.
+                let resource = ast::Resource {
+                    body: vec![ast::Entry::Message(ast::Message {
+                        id: ast::Identifier { name: "x" },
+                        value: Some(ast::Pattern {
+                            elements: vec![placeable.clone()],
+                        }),
+                        attributes: vec![],
+                        comment: None,
+                    })],
+                };
+                let source = serializer::serialize(&resource);
+                source.trim_start_matches("x =").trim().to_string()
+            }
+        })
+        .collect()
+}
+
+/// Exports every archetype in the catalog with its localized text.
+pub fn export(catalog: &Catalog, locales: &Locales) -> Vec<CardRecord> {
+    let archetypes = catalog
+        .sets
+        .iter()
+        .flat_map(|set| set.iter().map(move |archetype| (set.code(), archetype)));
+    archetypes
+        .map(|(set, archetype)| {
+            let filter = archetype.effect.create().event_filter();
+            let events = EVENT_HANDLERS
+                .iter()
+                .filter(|(_, event)| filter.contains(*event))
+                .map(|(name, _)| name.trim_start_matches("on_").to_string())
+                .collect();
+            let text = locales
+                .iter()
+                .filter_map(|(lang, texts)| {
+                    texts
+                        .get(&archetype.safe_name)
+                        .map(|text| (lang.clone(), text.clone()))
+                })
+                .collect();
+            CardRecord {
+                id: archetype.id.to_string(),
+                name: archetype.name.clone(),
+                safe_name: archetype.safe_name.clone(),
+                set: set.to_string(),
+                attribute: archetype.attribute.clone(),
+                events,
+                text,
+            }
+        })
+        .collect()
+}
+
+/// Writes records as CSV with a header row. Columns missing in a record are left empty.
+pub fn to_csv(records: &[CardRecord]) -> String {
+    let rows = records
+        .iter()
+        .map(|record| record.columns())
+        .collect::<Vec<_>>();
+    let mut header: Vec<&str> = vec![];
+    for (key, _) in rows.iter().flatten() {
+        if !header.contains(&key.as_str()) {
+            header.push(key);
+        }
+    }
+
+    let mut csv = String::new();
+    write_csv_line(&mut csv, header.iter().copied());
+    for row in &rows {
+        let row = row
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect::<BTreeMap<_, _>>();
+        write_csv_line(
+            &mut csv,
+            header
+                .iter()
+                .map(|key| row.get(key).copied().unwrap_or_default()),
+        );
+    }
+    csv
+}
+
+fn write_csv_line<'a, I>(csv: &mut String, fields: I)
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    for (i, field) in fields.into_iter().enumerate() {
+        if i > 0 {
+            csv.push(',');
+        }
+        if field.contains([',', '"', '\n', '\r']) {
+            csv.push('"');
+            csv.push_str(&field.replace('"', "\"\""));
+            csv.push('"');
+        } else {
+            csv.push_str(field);
+        }
+    }
+    csv.push('\n');
+}
+
+/// Reads CSV written by `to_csv` into rows keyed by the header.
+pub fn from_csv(data: &str) -> Result<Vec<Row>, String> {
+    let mut lines = vec![];
+    let mut line = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = data.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => line.push(std::mem::take(&mut field)),
+            '\r' if !quoted => {}
+            '\n' if !quoted => {
+                line.push(std::mem::take(&mut field));
+                lines.push(std::mem::take(&mut line));
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("Unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !line.is_empty() {
+        line.push(field);
+        lines.push(line);
+    }
+
+    let mut lines = lines.into_iter();
+    let header = lines.next().unwrap_or_default();
+    lines
+        .enumerate()
+        .map(|(i, line)| {
+            if line.len() != header.len() {
+                return Err(format!(
+                    "Line {}: expected {} fields, found {}",
+                    i + 2,
+                    header.len(),
+                    line.len()
+                ));
+            }
+            Ok(header.iter().cloned().zip(line).collect())
+        })
+        .collect()
+}
+
+/// Reads an export in JSON or, if the file name ends with `.csv`, in CSV.
+pub fn read_export(path: &Path) -> Result<Vec<Row>, String> {
+    let data = fs::read_to_string(path).map_err(|err| format!("Failed to read {path:?}: {err}"))?;
+    if path.extension().is_some_and(|ext| ext == "csv") {
+        from_csv(&data).map_err(|err| format!("{path:?}: {err}"))
+    } else {
+        let records: Vec<CardRecord> =
+            serde_json::from_str(&data).map_err(|err| format!("Invalid file {path:?}: {err}"))?;
+        Ok(records
+            .iter()
+            .map(|record| record.columns().into_iter().collect())
+            .collect())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    Added(String),
+    Removed(String),
+    Modified {
+        id: String,
+        column: String,
+        old: String,
+        new: String,
+    },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added(id) => write!(f, "+ {id}"),
+            Change::Removed(id) => write!(f, "- {id}"),
+            Change::Modified {
+                id,
+                column,
+                old,
+                new,
+            } => write!(f, "~ {id} {column}: {old:?} -> {new:?}"),
+        }
+    }
+}
+
+/// Compares two exports by card id.
+pub fn diff(old: &[Row], new: &[Row]) -> Vec<Change> {
+    let by_id = |rows: &[Row]| {
+        rows.iter()
+            .filter_map(|row| row.get("id").map(|id| (id.clone(), row.clone())))
+            .collect::<BTreeMap<_, _>>()
+    };
+    let old = by_id(old);
+    let new = by_id(new);
+
+    let mut changes = vec![];
+    for (id, old_row) in &old {
+        let Some(new_row) = new.get(id) else {
+            changes.push(Change::Removed(id.clone()));
+            continue;
+        };
+        let mut columns = old_row.keys().chain(new_row.keys()).collect::<Vec<_>>();
+        columns.sort();
+        columns.dedup();
+        for column in columns {
+            let old = old_row.get(column).cloned().unwrap_or_default();
+            let new = new_row.get(column).cloned().unwrap_or_default();
+            if old != new {
+                changes.push(Change::Modified {
+                    id: id.clone(),
+                    column: column.clone(),
+                    old,
+                    new,
+                });
+            }
+        }
+    }
+    for id in new.keys().filter(|id| !old.contains_key(*id)) {
+        changes.push(Change::Added(id.clone()));
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CATALOG;
+
+    #[test]
+    fn test_export() {
+        let locales = Locales::from([(
+            "en-US".to_string(),
+            parse_card_texts("card-pyrosnail = Pyrosnail\n    .text = Deals \"100\", damage.\n")
+                .unwrap(),
+        )]);
+        let records = export(&CATALOG, &locales);
+        let pyrosnail = records
+            .iter()
+            .find(|record| record.safe_name == "pyrosnail")
+            .unwrap();
+        assert_eq!(pyrosnail.events, vec!["destroyed"]);
+        assert_eq!(pyrosnail.text["en-US"]["text"], "Deals \"100\", damage.");
+
+        let old = from_csv(&to_csv(&records)).unwrap();
+        let json = serde_json::to_string(&records).unwrap();
+        let mut records: Vec<CardRecord> = serde_json::from_str(&json).unwrap();
+        let rows = records
+            .iter()
+            .map(|record| record.columns().into_iter().collect::<Row>())
+            .collect::<Vec<_>>();
+        assert_eq!(old.len(), rows.len());
+        assert!(diff(&old, &rows).is_empty());
+
+        let removed = records.pop().unwrap();
+        let pyrosnail = records
+            .iter_mut()
+            .find(|record| record.safe_name == "pyrosnail")
+            .unwrap();
+        pyrosnail.attribute.cost += 1;
+        let modified = Change::Modified {
+            id: pyrosnail.id.clone(),
+            column: "attribute.cost".to_string(),
+            old: (pyrosnail.attribute.cost - 1).to_string(),
+            new: pyrosnail.attribute.cost.to_string(),
+        };
+        let new = from_csv(&to_csv(&records)).unwrap();
+        let changes = diff(&old, &new);
+        assert_eq!(changes.len(), 2);
+        assert!(changes.contains(&modified));
+        assert!(changes.contains(&Change::Removed(removed.id)));
+    }
+}
@@ -5,6 +5,86 @@ use std::sync::{Arc, LazyLock};
 
 mod cards;
 pub mod decks;
+pub mod export;
+pub mod locale;
 mod macros;
 
 pub static CATALOG: LazyLock<Arc<Catalog>> = LazyLock::new(|| Arc::new(Catalog::new(cards::CARDS)));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodecks::{
+        archetype::{EffectFactory, Rarity},
+        booster::{BoosterGenerator, BoosterLayout},
+        card::Card,
+        effect::EffectTriggerContext,
+        env::Environment,
+        id::ObjectIdCounter,
+        player::PlayerConfig,
+        profile::GameProfile,
+    };
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_rarities() {
+        let mut counts = BTreeMap::<Rarity, usize>::new();
+        for archetype in CATALOG.iter() {
+            if archetype.attribute.is_token {
+                assert_eq!(archetype.attribute.rarity, Rarity::Common);
+            } else {
+                *counts.entry(archetype.attribute.rarity).or_default() += 1;
+            }
+        }
+        // Every rarity has cards, and the rarer ones fewer.
+        let counts = [
+            Rarity::Common,
+            Rarity::Uncommon,
+            Rarity::Rare,
+            Rarity::Mythic,
+        ]
+        .map(|rarity| counts.get(&rarity).copied().unwrap_or_default());
+        assert!(counts[3] > 0);
+        assert!(counts.windows(2).all(|pair| pair[0] > pair[1]));
+
+        // The default layout fills each slot without repeating a card.
+        let mut generator = BoosterGenerator::new(&CATALOG, None, BoosterLayout::default(), 0);
+        for _ in 0..100 {
+            let mut pack = generator.open();
+            assert_eq!(pack.len(), BoosterLayout::default().size());
+            pack.sort();
+            pack.dedup();
+            assert_eq!(pack.len(), BoosterLayout::default().size());
+        }
+    }
+
+    #[test]
+    fn test_native_triggers() {
+        let profile = GameProfile {
+            players: vec![
+                PlayerConfig {
+                    deck: decks::starter_deck(&CATALOG),
+                };
+                2
+            ],
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let env = Environment::new(profile, CATALOG.clone());
+        let mut counter = ObjectIdCounter::default();
+
+        // A native card pushes a single stack item for each triggered id,
+        // so none pushes its own item on top of the default of `Effect::trigger`.
+        for archetype in CATALOG.iter() {
+            if !matches!(archetype.effect, EffectFactory::Native(_)) {
+                continue;
+            }
+            let card = Card::new_token(counter.allocate(None), archetype.clone(), 0);
+            let mut ctx = EffectTriggerContext::new(&env.state, &mut counter, &card);
+            card.effect().trigger("main".into(), &mut ctx).unwrap();
+            let (continuous, stack) = ctx.into_inner();
+            assert_eq!(stack.len(), 1, "{}", archetype.name);
+            assert!(continuous.is_empty(), "{}", archetype.name);
+        }
+    }
+}
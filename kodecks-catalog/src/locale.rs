@@ -0,0 +1,416 @@
+use fluent_bundle::FluentArgs;
+use fluent_content::Request;
+use fluent_syntax::{ast, parser};
+use kodecks::{
+    ability::{AnonymousAbility, KeywordAbility},
+    archetype::ArchetypeId,
+    catalog::Catalog,
+    error::{DeckViolation, DeckViolationKind, Error},
+    log::LOG_MESSAGES,
+    message::ENGINE_MESSAGES,
+    phase::Phase,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, fs,
+    path::Path,
+};
+use strum::IntoEnumIterator;
+
+/// Prefixes of the message ids which are generated from the game data.
+///
+/// Messages with these prefixes are reported as stale if nothing refers to them.
+const MANAGED_PREFIXES: &[&str] = &["card-", "ability-", "phase-", "error-", "message-", "log-"];
+
+/// Variables referenced by each message of a locale, including its attributes.
+type Messages = BTreeMap<String, BTreeSet<String>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    Missing {
+        lang: String,
+        id: String,
+    },
+    Stale {
+        lang: String,
+        id: String,
+    },
+    UnknownVariable {
+        lang: String,
+        id: String,
+        variable: String,
+    },
+    InconsistentVariables {
+        lang: String,
+        id: String,
+        reference: String,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::Missing { lang, id } => write!(f, "{lang}: missing {id}"),
+            Problem::Stale { lang, id } => write!(f, "{lang}: stale {id}"),
+            Problem::UnknownVariable { lang, id, variable } => {
+                write!(f, "{lang}: {id} uses unknown variable ${variable}")
+            }
+            Problem::InconsistentVariables {
+                lang,
+                id,
+                reference,
+            } => write!(f, "{lang}: {id} uses different variables from {reference}"),
+        }
+    }
+}
+
+/// Returns the message ids the game refers to, with the variables given to each message.
+pub fn required_messages(catalog: &Catalog) -> BTreeMap<String, BTreeSet<String>> {
+    fn request_args(request: Request<FluentArgs>) -> (String, BTreeSet<String>) {
+        let args = request
+            .args
+            .iter()
+            .flat_map(|args| args.iter().map(|(key, _)| key.to_string()))
+            .collect();
+        (request.id.to_string(), args)
+    }
+
+    /// Returns a violation of the kind with placeholder values, to list its message arguments.
+    fn deck_violation(kind: DeckViolationKind) -> DeckViolation {
+        let card = ArchetypeId::new("");
+        match kind {
+            DeckViolationKind::TooFewCards => DeckViolation::TooFewCards { size: 0, min: 0 },
+            DeckViolationKind::TooManyCards => DeckViolation::TooManyCards { size: 0, max: 0 },
+            DeckViolationKind::TooManyCopies => DeckViolation::TooManyCopies {
+                card,
+                count: 0,
+                max: 0,
+            },
+            DeckViolationKind::UnknownCard => DeckViolation::UnknownCard { card },
+            DeckViolationKind::CardBanned => DeckViolation::CardBanned { card },
+            DeckViolationKind::CardLimited => DeckViolation::CardLimited {
+                card,
+                count: 0,
+                max: 0,
+            },
+            DeckViolationKind::TokenInDeck => DeckViolation::TokenInDeck { card },
+        }
+    }
+
+    let cards = catalog
+        .iter()
+        .map(|archetype| (format!("card-{}", archetype.safe_name), BTreeSet::new()));
+    let abilities = KeywordAbility::iter()
+        .map(|ability| ability.to_string())
+        .chain(AnonymousAbility::iter().map(|ability| ability.to_string()))
+        .map(|ability| {
+            (
+                format!("ability-{}", ability.to_lowercase()),
+                BTreeSet::new(),
+            )
+        });
+    let phases = Phase::iter().map(|phase| request_args(phase.into()));
+    let errors = Error::iter()
+        .map(|error| request_args(error.into()))
+        .chain(DeckViolationKind::iter().map(|kind| request_args(deck_violation(kind).into())));
+    let messages = ENGINE_MESSAGES
+        .iter()
+        .chain(LOG_MESSAGES)
+        .map(|(id, args)| {
+            (
+                id.to_string(),
+                args.iter().map(|arg| arg.to_string()).collect(),
+            )
+        });
+    cards
+        .chain(abilities)
+        .chain(phases)
+        .chain(errors)
+        .chain(messages)
+        .collect()
+}
+
+/// Checks every locale directory in `dir` against the messages the game refers to.
+pub fn check_locales(dir: &Path, catalog: &Catalog) -> Result<Vec<Problem>, String> {
+    let entries = fs::read_dir(dir).map_err(|err| format!("Failed to read {dir:?}: {err}"))?;
+    let mut locales = BTreeMap::new();
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        if entry.path().is_dir() {
+            let lang = entry.file_name().to_string_lossy().to_string();
+            locales.insert(lang, load_messages(&entry.path())?);
+        }
+    }
+    Ok(check_messages(&locales, &required_messages(catalog)))
+}
+
+fn check_messages(
+    locales: &BTreeMap<String, Messages>,
+    required: &BTreeMap<String, BTreeSet<String>>,
+) -> Vec<Problem> {
+    let is_stale = |id: &str| {
+        MANAGED_PREFIXES.iter().any(|prefix| id.starts_with(prefix)) && !required.contains_key(id)
+    };
+    let expected = locales
+        .values()
+        .flat_map(|messages| messages.keys())
+        .filter(|id| !is_stale(id))
+        .chain(required.keys())
+        .collect::<BTreeSet<_>>();
+
+    let mut problems = vec![];
+    let reference = locales.iter().next();
+    for (lang, messages) in locales {
+        for id in expected.iter().filter(|id| !messages.contains_key(**id)) {
+            problems.push(Problem::Missing {
+                lang: lang.clone(),
+                id: id.to_string(),
+            });
+        }
+        for (id, variables) in messages {
+            if is_stale(id) {
+                problems.push(Problem::Stale {
+                    lang: lang.clone(),
+                    id: id.clone(),
+                });
+                continue;
+            }
+            if let Some(args) = required.get(id) {
+                for variable in variables.difference(args) {
+                    problems.push(Problem::UnknownVariable {
+                        lang: lang.clone(),
+                        id: id.clone(),
+                        variable: variable.clone(),
+                    });
+                }
+            } else if let Some((reference, ref_messages)) = reference {
+                if ref_messages
+                    .get(id)
+                    .is_some_and(|ref_variables| ref_variables != variables)
+                {
+                    problems.push(Problem::InconsistentVariables {
+                        lang: lang.clone(),
+                        id: id.clone(),
+                        reference: reference.clone(),
+                    });
+                }
+            }
+        }
+    }
+    problems
+}
+
+fn load_messages(dir: &Path) -> Result<Messages, String> {
+    let entries = fs::read_dir(dir).map_err(|err| format!("Failed to read {dir:?}: {err}"))?;
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ftl"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut messages = Messages::new();
+    for path in paths {
+        let source =
+            fs::read_to_string(&path).map_err(|err| format!("Failed to read {path:?}: {err}"))?;
+        messages.extend(parse_messages(&source).map_err(|err| format!("{path:?}: {err}"))?);
+    }
+    Ok(messages)
+}
+
+fn parse_messages(source: &str) -> Result<Messages, String> {
+    let resource = parser::parse(source).map_err(|(_, errors)| {
+        errors
+            .iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    })?;
+    let mut messages = Messages::new();
+    for entry in &resource.body {
+        let ast::Entry::Message(message) = entry else {
+            continue;
+        };
+        let mut variables = BTreeSet::new();
+        if let Some(value) = &message.value {
+            pattern_variables(value, &mut variables);
+        }
+        for attr in &message.attributes {
+            pattern_variables(&attr.value, &mut variables);
+        }
+        messages.insert(message.id.name.to_string(), variables);
+    }
+    Ok(messages)
+}
+
+fn pattern_variables(pattern: &ast::Pattern<&str>, variables: &mut BTreeSet<String>) {
+    for element in &pattern.elements {
+        if let ast::PatternElement::Placeable { expression } = element {
+            expression_variables(expression, variables);
+        }
+    }
+}
+
+fn expression_variables(expression: &ast::Expression<&str>, variables: &mut BTreeSet<String>) {
+    match expression {
+        ast::Expression::Select { selector, variants } => {
+            inline_variables(selector, variables);
+            for variant in variants {
+                pattern_variables(&variant.value, variables);
+            }
+        }
+        ast::Expression::Inline(inline) => inline_variables(inline, variables),
+    }
+}
+
+fn inline_variables(inline: &ast::InlineExpression<&str>, variables: &mut BTreeSet<String>) {
+    match inline {
+        ast::InlineExpression::VariableReference { id } => {
+            variables.insert(id.name.to_string());
+        }
+        ast::InlineExpression::Placeable { expression } => {
+            expression_variables(expression, variables);
+        }
+        ast::InlineExpression::FunctionReference { arguments, .. }
+        | ast::InlineExpression::TermReference {
+            arguments: Some(arguments),
+            ..
+        } => {
+            let named = arguments.named.iter().map(|arg| &arg.value);
+            for arg in arguments.positional.iter().chain(named) {
+                inline_variables(arg, variables);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_messages() {
+        let en = parse_messages(
+            "message-discard-excess-cards = Keep { $maxHandSize } cards.\n\
+             log-card-drawn = { $player } drew { $cards }.\n\
+             attack-button = Attack ({ $attackers })\n\
+             phase-upkeep = Upkeep\n",
+        )
+        .unwrap();
+        let ja = parse_messages(
+            "message-discard-excess-cards = { $maxHandSize }枚まで捨ててください。\n\
+             attack-button = 攻撃 ({ $count })\n",
+        )
+        .unwrap();
+        let locales = BTreeMap::from([("en-US".to_string(), en), ("ja-JP".to_string(), ja)]);
+        let required = ENGINE_MESSAGES
+            .iter()
+            .chain(LOG_MESSAGES)
+            .filter(|(id, _)| *id == "message-discard-excess-cards" || *id == "log-card-drawn")
+            .map(|(id, args)| {
+                (
+                    id.to_string(),
+                    args.iter().map(|arg| arg.to_string()).collect(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            check_messages(&locales, &required),
+            vec![
+                Problem::UnknownVariable {
+                    lang: "en-US".to_string(),
+                    id: "log-card-drawn".to_string(),
+                    variable: "cards".to_string(),
+                },
+                Problem::Stale {
+                    lang: "en-US".to_string(),
+                    id: "phase-upkeep".to_string(),
+                },
+                Problem::Missing {
+                    lang: "ja-JP".to_string(),
+                    id: "log-card-drawn".to_string(),
+                },
+                Problem::InconsistentVariables {
+                    lang: "ja-JP".to_string(),
+                    id: "attack-button".to_string(),
+                    reference: "en-US".to_string(),
+                },
+            ]
+        );
+    }
+
+    /// Splits `source` at `separator` and returns, for each part, the message ids starting with
+    /// `prefix` and the names of the variables passed to `set`.
+    fn emitted_messages<'a>(
+        source: &'a str,
+        separator: &str,
+        prefix: &str,
+    ) -> Vec<(BTreeSet<&'a str>, BTreeSet<&'a str>)> {
+        source
+            .split(separator)
+            .map(|part| {
+                let pieces = part.split('"').collect::<Vec<_>>();
+                let ids = pieces
+                    .iter()
+                    .skip(1)
+                    .step_by(2)
+                    .filter(|literal| literal.starts_with(prefix))
+                    .copied()
+                    .collect();
+                let variables = pieces
+                    .windows(2)
+                    .step_by(2)
+                    .filter(|pair| pair[0].trim_end().ends_with("set("))
+                    .map(|pair| pair[1])
+                    .collect();
+                (ids, variables)
+            })
+            .collect()
+    }
+
+    fn assert_messages_match(
+        messages: &[(&str, &[&str])],
+        emitted: Vec<(BTreeSet<&str>, BTreeSet<&str>)>,
+    ) {
+        let messages = messages
+            .iter()
+            .map(|(id, variables)| (*id, variables.iter().copied().collect::<BTreeSet<_>>()))
+            .collect::<BTreeMap<_, _>>();
+        let ids = emitted
+            .iter()
+            .flat_map(|(ids, _)| ids.iter().copied())
+            .collect::<BTreeSet<_>>();
+        assert_eq!(messages.keys().copied().collect::<BTreeSet<_>>(), ids);
+
+        for (ids, variables) in emitted {
+            let mut listed = BTreeSet::new();
+            for id in &ids {
+                assert!(
+                    messages[id].is_subset(&variables),
+                    "{id} is emitted without some of {:?}",
+                    messages[id]
+                );
+                listed.extend(messages[id].iter().copied());
+            }
+            assert!(
+                variables.is_subset(&listed),
+                "{variables:?} are not all declared for {ids:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_engine_messages_match_emit_sites() {
+        let source = include_str!("../../kodecks/src/env/action.rs");
+        assert_messages_match(
+            ENGINE_MESSAGES,
+            emitted_messages(source, "Message {", "message-"),
+        );
+    }
+
+    #[test]
+    fn test_log_messages_match_client() {
+        let source = include_str!("../../kodecks-bevy/src/scene/game/log.rs");
+        assert_messages_match(LOG_MESSAGES, emitted_messages(source, "GameLog::", "log-"));
+    }
+}
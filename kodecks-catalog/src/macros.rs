@@ -13,7 +13,7 @@ macro_rules! card_def {
                     $( $key : ($value).into(), )*
                     ..Default::default()
                 },
-                effect: || Box::new(CardDef),
+                effect: EffectFactory::Native(|| Box::new(CardDef)),
             });
             &CACHE
         };
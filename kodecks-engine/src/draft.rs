@@ -0,0 +1,461 @@
+use crate::message::{DraftCommand, DraftCommandKind, DraftEvent, DraftEventKind, Output};
+use bincode::{Decode, Encode};
+use futures::{
+    channel::mpsc::{Receiver, Sender},
+    future, select, FutureExt, SinkExt, StreamExt,
+};
+use futures_timer::Delay;
+use kodecks::{
+    archetype::ArchetypeId,
+    booster::{BoosterGenerator, BoosterLayout},
+    catalog::Catalog,
+    pool::CardPool,
+    score::Score,
+};
+use kodecks_catalog::CATALOG;
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use web_time::Instant;
+
+/// The score a card is worth to a bot when all of its picks so far share the color of the card.
+const COLOR_COMMITMENT: f32 = 4.0;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct DraftConfig {
+    pub seats: u8,
+    /// The seats picked by bots.
+    #[serde(default)]
+    pub bots: Vec<u8>,
+    /// The number of packs each seat opens.
+    /// Packs are passed to the next seat in the first round and to the previous seat in the second one, and so on.
+    pub packs: u8,
+    #[serde(default)]
+    pub layout: BoosterLayout,
+    /// Opens packs of the set with the code instead of the whole catalog.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub set: Option<String>,
+    pub seed: u64,
+    /// The time given to each pick. A card is picked for the seat when it runs out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pick_timeout: Option<Duration>,
+}
+
+/// A booster draft, where the seats pick a card from their packs at the same time
+/// and pass the rest around the table until the packs are empty.
+///
+/// Like [`ChessClock`](kodecks::clock::ChessClock), the draft does not read the system time.
+/// The caller reports the elapsed time with [`Draft::elapse`].
+#[derive(Debug, Clone)]
+pub struct Draft {
+    config: DraftConfig,
+    catalog: Arc<Catalog>,
+    generator: BoosterGenerator,
+    round: u8,
+    pick: u8,
+    packs: Vec<Vec<ArchetypeId>>,
+    picks: Vec<Vec<ArchetypeId>>,
+    pending: Vec<Option<ArchetypeId>>,
+    elapsed: Duration,
+}
+
+impl Draft {
+    pub fn new(config: DraftConfig, catalog: Arc<Catalog>) -> Self {
+        let generator = BoosterGenerator::new(
+            &catalog,
+            config.set.as_deref(),
+            config.layout.clone(),
+            config.seed,
+        );
+        let seats = config.seats as usize;
+        let mut draft = Self {
+            config,
+            catalog,
+            generator,
+            round: 0,
+            pick: 0,
+            packs: vec![vec![]; seats],
+            picks: vec![vec![]; seats],
+            pending: vec![None; seats],
+            elapsed: Duration::ZERO,
+        };
+        draft.open_packs();
+        draft.start_pick();
+        draft.advance();
+        draft
+    }
+
+    pub fn config(&self) -> &DraftConfig {
+        &self.config
+    }
+
+    /// The pack being drafted, starting from 0.
+    pub fn round(&self) -> u8 {
+        self.round
+    }
+
+    /// The number of cards taken from the current packs.
+    pub fn pick_number(&self) -> u8 {
+        self.pick
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.round >= self.config.packs
+    }
+
+    pub fn is_bot(&self, seat: u8) -> bool {
+        self.config.bots.contains(&seat)
+    }
+
+    /// The pack in front of the seat.
+    pub fn pack(&self, seat: u8) -> &[ArchetypeId] {
+        self.packs
+            .get(seat as usize)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The cards the seat has picked so far.
+    pub fn picks(&self, seat: u8) -> &[ArchetypeId] {
+        self.picks
+            .get(seat as usize)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The seats which have not picked from their current pack yet.
+    pub fn waiting(&self) -> impl Iterator<Item = u8> + '_ {
+        self.pending
+            .iter()
+            .enumerate()
+            .filter(|(_, pending)| pending.is_none())
+            .map(|(seat, _)| seat as u8)
+    }
+
+    /// The time left for the current pick.
+    pub fn remaining_time(&self) -> Option<Duration> {
+        self.config
+            .pick_timeout
+            .map(|timeout| timeout.saturating_sub(self.elapsed))
+    }
+
+    /// Picks a card from the pack in front of the seat. The packs are passed once every seat has picked.
+    ///
+    /// Returns false if the draft is over, the seat has already picked, or the card is not in its pack.
+    pub fn pick(&mut self, seat: u8, card: ArchetypeId) -> bool {
+        if self.is_finished() || !self.pack(seat).contains(&card) {
+            return false;
+        }
+        match self.pending.get_mut(seat as usize) {
+            Some(pending @ None) => *pending = Some(card),
+            _ => return false,
+        }
+        self.advance();
+        true
+    }
+
+    /// Picks a card for the seat as a bot would.
+    pub fn time_out(&mut self, seat: u8) -> bool {
+        match bot_pick(&self.catalog, self.pack(seat), self.picks(seat)) {
+            Some(card) => self.pick(seat, card),
+            None => false,
+        }
+    }
+
+    /// Advances the pick timer, and picks for the seats still waiting once it runs out.
+    pub fn elapse(&mut self, elapsed: Duration) {
+        let Some(timeout) = self.config.pick_timeout else {
+            return;
+        };
+        self.elapsed += elapsed;
+        if self.elapsed >= timeout {
+            for seat in self.waiting().collect::<Vec<_>>() {
+                self.time_out(seat);
+            }
+        }
+    }
+
+    /// Returns the pool made from the picks of the seat.
+    pub fn card_pool(&self, seat: u8) -> CardPool {
+        CardPool::from_cards(self.picks(seat).iter().copied())
+    }
+
+    fn open_packs(&mut self) {
+        while !self.is_finished() {
+            for pack in &mut self.packs {
+                *pack = self.generator.open();
+            }
+            if self.packs.iter().any(|pack| !pack.is_empty()) {
+                break;
+            }
+            self.round += 1;
+        }
+    }
+
+    fn start_pick(&mut self) {
+        self.elapsed = Duration::ZERO;
+        for &seat in &self.config.bots {
+            if let Some(pending) = self.pending.get_mut(seat as usize) {
+                *pending = bot_pick(
+                    &self.catalog,
+                    &self.packs[seat as usize],
+                    &self.picks[seat as usize],
+                );
+            }
+        }
+    }
+
+    fn advance(&mut self) {
+        while !self.is_finished() && self.pending.iter().all(Option::is_some) {
+            for (seat, pending) in self.pending.iter_mut().enumerate() {
+                if let Some(card) = pending.take() {
+                    let pack = &mut self.packs[seat];
+                    if let Some(index) = pack.iter().position(|item| *item == card) {
+                        pack.remove(index);
+                    }
+                    self.picks[seat].push(card);
+                }
+            }
+            if self.packs.iter().all(Vec::is_empty) {
+                self.round += 1;
+                self.pick = 0;
+                self.open_packs();
+            } else {
+                self.pick += 1;
+                if self.round.is_multiple_of(2) {
+                    self.packs.rotate_right(1);
+                } else {
+                    self.packs.rotate_left(1);
+                }
+            }
+            self.start_pick();
+        }
+    }
+}
+
+/// Returns the card a bot takes from the pack.
+///
+/// Cards are rated by their score, plus a bonus for the share of the picks so far in their color,
+/// so bots commit to colors as the draft goes on. Colorless cards get half the bonus.
+pub fn bot_pick(
+    catalog: &Catalog,
+    pack: &[ArchetypeId],
+    picks: &[ArchetypeId],
+) -> Option<ArchetypeId> {
+    let colors = picks
+        .iter()
+        .filter_map(|card| catalog.get(*card))
+        .map(|archetype| archetype.attribute.color)
+        .collect::<Vec<_>>();
+    let rating = |card: &ArchetypeId| {
+        let Some(archetype) = catalog.get(*card) else {
+            return f32::MIN;
+        };
+        let color = archetype.attribute.color;
+        let commitment = if color.is_empty() {
+            0.5
+        } else if colors.is_empty() {
+            0.0
+        } else {
+            colors
+                .iter()
+                .filter(|picked| picked.intersects(color))
+                .count() as f32
+                / colors.len() as f32
+        };
+        archetype.score().score() as f32 + commitment * COLOR_COMMITMENT
+    };
+
+    let mut best: Option<(ArchetypeId, f32)> = None;
+    for card in pack {
+        let rating = rating(card);
+        if best.is_none_or(|(_, best)| rating > best) {
+            best = Some((*card, rating));
+        }
+    }
+    best.map(|(card, _)| card)
+}
+
+/// Runs a draft, picking for the bot seats and sending the packs to the other seats.
+///
+/// Like the server, the draft keeps the time and picks for the seats which run out of it.
+pub async fn start_draft(
+    config: DraftConfig,
+    mut receiver: Receiver<DraftCommand>,
+    mut sender: Sender<Output>,
+) {
+    let mut draft = Draft::new(config, CATALOG.clone());
+    let players = (0..draft.config().seats)
+        .filter(|seat| !draft.is_bot(*seat))
+        .collect::<Vec<_>>();
+
+    while !draft.is_finished() {
+        for &seat in &players {
+            let event = DraftEvent {
+                draft_id: 0,
+                seat,
+                event: DraftEventKind::PackReceived {
+                    round: draft.round(),
+                    pick: draft.pick_number(),
+                    pack: draft.pack(seat).to_vec(),
+                    timeout: draft.remaining_time().map(|time| time.as_secs() as u32),
+                },
+            };
+            sender.send(Output::DraftEvent(event)).await.unwrap();
+        }
+
+        let pick_started = Instant::now();
+        let deadline = draft.remaining_time().map(|time| pick_started + time);
+        let step = (draft.round(), draft.pick_number());
+        while !draft.is_finished() && (draft.round(), draft.pick_number()) == step {
+            let timer = match deadline {
+                Some(deadline) => {
+                    Delay::new(deadline.saturating_duration_since(Instant::now())).left_future()
+                }
+                None => future::pending().right_future(),
+            };
+            select! {
+                command = receiver.next() => {
+                    let Some(command) = command else {
+                        return;
+                    };
+                    let DraftCommandKind::Pick { card } = command.kind;
+                    draft.pick(command.seat, card);
+                }
+                _ = timer.fuse() => {
+                    draft.elapse(pick_started.elapsed());
+                }
+            }
+        }
+    }
+
+    for &seat in &players {
+        let event = DraftEvent {
+            draft_id: 0,
+            seat,
+            event: DraftEventKind::Finished {
+                pool: draft.card_pool(seat),
+            },
+        };
+        sender.send(Output::DraftEvent(event)).await.unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kodecks::{
+        archetype::{CardArchetype, CardAttribute},
+        color::Color,
+    };
+    use std::sync::LazyLock;
+
+    static CARDS: LazyLock<Vec<CardArchetype>> = LazyLock::new(|| {
+        let cards = [
+            ("rbig", Color::RED, 400),
+            ("rsml", Color::RED, 200),
+            ("bbig", Color::BLUE, 500),
+            ("bsml", Color::BLUE, 100),
+        ];
+        cards
+            .iter()
+            .map(|(id, color, power)| CardArchetype {
+                id: ArchetypeId::new(id),
+                name: id.to_string(),
+                safe_name: id.to_string(),
+                attribute: CardAttribute {
+                    color: *color,
+                    power: Some(*power),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .collect()
+    });
+
+    #[test]
+    fn test_draft() {
+        let catalog = Arc::new(Catalog::new(&[
+            || &CARDS[0],
+            || &CARDS[1],
+            || &CARDS[2],
+            || &CARDS[3],
+        ]));
+        let card = ArchetypeId::new;
+
+        // The bigger card wins, unless the picks are committed to the other color.
+        assert_eq!(
+            bot_pick(&catalog, &[card("rbig"), card("bbig")], &[]),
+            Some(card("bbig"))
+        );
+        assert_eq!(
+            bot_pick(&catalog, &[card("rsml"), card("bbig")], &[card("rbig")]),
+            Some(card("rsml"))
+        );
+
+        let config = DraftConfig {
+            seats: 3,
+            bots: vec![1, 2],
+            packs: 2,
+            layout: BoosterLayout::default(),
+            set: None,
+            seed: 3,
+            pick_timeout: Some(Duration::from_secs(10)),
+        };
+        let size = config.layout.size();
+        let mut draft = Draft::new(config, catalog);
+        assert_eq!(draft.waiting().collect::<Vec<_>>(), vec![0]);
+
+        let pack = draft.pack(0).to_vec();
+        let next = draft.pack(2).to_vec();
+        assert!(!draft.pick(1, pack[0]));
+        assert!(!draft.pick(0, card("none")));
+        assert!(draft.pick(0, pack[0]));
+        assert_eq!(draft.pick_number(), 1);
+        assert_eq!(draft.picks(0), &pack[..1]);
+        // The first packs are passed to the next seat.
+        assert_eq!(draft.pack(0).len(), size - 1);
+        assert!(draft.pack(0).iter().all(|card| next.contains(card)));
+
+        draft.elapse(Duration::from_secs(5));
+        assert_eq!(draft.remaining_time(), Some(Duration::from_secs(5)));
+        assert_eq!(draft.pick_number(), 1);
+        while !draft.is_finished() {
+            draft.elapse(Duration::from_secs(10));
+        }
+        for seat in 0..3 {
+            assert_eq!(draft.picks(seat).len(), size * 2);
+        }
+        assert_eq!(
+            draft.card_pool(0),
+            CardPool::from_cards(draft.picks(0).iter().copied())
+        );
+    }
+
+    #[test]
+    fn test_start_draft_deadline() {
+        let config = DraftConfig {
+            seats: 2,
+            bots: vec![1],
+            packs: 1,
+            layout: BoosterLayout::default(),
+            set: None,
+            seed: 1,
+            pick_timeout: Some(Duration::from_millis(1)),
+        };
+        let size = config.layout.size();
+        let (_command_sender, receiver) = futures::channel::mpsc::channel(1);
+        let (sender, events) = futures::channel::mpsc::channel(size + 1);
+
+        // The seat never picks, so every card is picked for it when the time runs out.
+        futures::executor::block_on(start_draft(config, receiver, sender));
+        let events = futures::executor::block_on(events.collect::<Vec<_>>());
+        assert_eq!(events.len(), size + 1);
+        assert!(matches!(
+            events.last(),
+            Some(Output::DraftEvent(DraftEvent {
+                event: DraftEventKind::Finished { pool },
+                ..
+            })) if *pool != CardPool::default()
+        ));
+    }
+}
@@ -1,39 +1,55 @@
 use crate::message::{GameCommand, GameCommandKind, GameEvent, GameEventKind, Output};
 use futures::{
     channel::mpsc::{Receiver, Sender},
-    SinkExt, StreamExt,
+    future, select, FutureExt, SinkExt, StreamExt,
 };
+use futures_timer::Delay;
 use kodecks::{
     action::{Action, PlayerAvailableActions},
-    env::{Environment, LocalGameState},
+    clock::{ChessClock, PlayerClock},
+    env::{CardPrior, Environment, LocalGameState},
+    error::Error,
+    log::GameLog,
     profile::GameProfile,
 };
-use kodecks_bot::{Bot, MctsBot};
+use kodecks_bot::{Bot, ConfiguredBot};
 use kodecks_catalog::CATALOG;
 use std::sync::Arc;
+use web_time::Instant;
 
 pub async fn start_game(
     log_id: String,
-    profile: GameProfile,
+    mut profile: GameProfile,
     mut receiver: Receiver<GameCommand>,
     mut sender: Sender<Output>,
 ) {
     let bots = profile.bots.clone();
+    // The bots share the seed of the game, so a seeded game replays the same.
+    let seed = *profile.rng_seed.get_or_insert_with(rand::random);
+    let invalid_decks = profile.verify_decks(&CATALOG);
+    if !invalid_decks.is_empty() {
+        for (_, violations) in invalid_decks {
+            let error = Error::InvalidDeck { violations };
+            sender.send(Output::Error(error)).await.unwrap();
+        }
+        return;
+    }
+
     let mut players = profile
         .players
         .iter()
         .enumerate()
-        .map(|(id, _)| PlayerData {
+        .map(|(id, config)| PlayerData {
             id: id as u8,
-            bot: if bots.iter().any(|bot| bot.player == id as u8) {
-                Some(MctsBot::default())
-            } else {
-                None
-            },
+            bot: bots.iter().find(|bot| bot.player == id as u8).map(|bot| {
+                let prior = CardPrior::default().with_deck(bot.player, config.deck.clone());
+                ConfiguredBot::new(bot, prior, ConfiguredBot::seed(seed, bot.player))
+            }),
             next_action: None,
         })
         .collect::<Vec<_>>();
 
+    let time_control = profile.regulation.time_control;
     let mut env = Arc::new(Environment::new(profile, CATALOG.clone()));
     let mut available_actions: Option<PlayerAvailableActions> = None;
     let mut player_in_action = if let Ok(player) = env.state.players.player_in_turn() {
@@ -42,6 +58,11 @@ pub async fn start_game(
         return;
     };
 
+    // Like the server, the engine keeps the time and times out the players who run out of it.
+    let mut clock = time_control
+        .map(|control| ChessClock::new(control, env.state.players.iter().map(|player| player.id)));
+    let mut action_started = Instant::now();
+
     for player in &players {
         if player.bot.is_none() {
             sender
@@ -60,23 +81,55 @@ pub async fn start_game(
     while !env.game_condition().is_ended() {
         if let Some(available_actions) = &available_actions {
             if players[available_actions.player as usize].bot.is_none() {
-                if let Some(command) = receiver.next().await {
-                    match command.kind {
-                        GameCommandKind::NextAction { action } => {
-                            players[command.player as usize].next_action = Some(action);
+                let timer = match &clock {
+                    Some(clock) => Delay::new(
+                        clock
+                            .time_left(player_in_action)
+                            .saturating_sub(action_started.elapsed()),
+                    )
+                    .left_future(),
+                    None => future::pending().right_future(),
+                };
+                select! {
+                    command = receiver.next() => {
+                        let Some(command) = command else {
+                            return;
+                        };
+                        match command.kind {
+                            GameCommandKind::NextAction { action } => {
+                                if !matches!(action, Action::Timeout) {
+                                    players[command.player as usize].next_action = Some(action);
+                                }
+                            }
+                            GameCommandKind::Resync => {
+                                let clocks = clock_snapshot(&clock, action_started);
+                                let event = state_event(
+                                    &env,
+                                    command.player,
+                                    vec![],
+                                    Some(available_actions.clone()),
+                                    &clocks,
+                                );
+                                sender.send(Output::GameEvent(event)).await.unwrap();
+                            }
                         }
                     }
+                    _ = timer.fuse() => {
+                        players[player_in_action as usize].next_action = Some(Action::Timeout);
+                    }
                 }
             }
         }
 
         while !env.game_condition().is_ended() {
-            let conceded = players
-                .iter()
-                .find(|data| matches!(data.next_action, Some(Action::Concede)))
-                .map(|data| data.id);
-            let (player, next_action) = if let Some(player) = conceded {
-                (player, Some(Action::Concede))
+            let conceded = players.iter_mut().find_map(|data| match data.next_action {
+                Some(Action::Concede | Action::Timeout) => {
+                    data.next_action.take().map(|action| (data.id, action))
+                }
+                _ => None,
+            });
+            let (player, next_action) = if let Some((player, action)) = conceded {
+                (player, Some(action))
             } else if let Some(available_actions) = &available_actions {
                 if let Some(bot) = players[player_in_action as usize].bot.as_mut() {
                     let env = env.clone();
@@ -85,7 +138,14 @@ pub async fn start_game(
                         bot.compute_best_action(env, available_actions),
                     )
                 } else if let Some(action) = players[player_in_action as usize].next_action.take() {
-                    (player_in_action, Some(action))
+                    let in_time = clock.as_mut().is_none_or(|clock| {
+                        clock.consume(player_in_action, action_started.elapsed())
+                    });
+                    if in_time {
+                        (player_in_action, Some(action))
+                    } else {
+                        (player_in_action, Some(Action::Timeout))
+                    }
                 } else {
                     break;
                 }
@@ -93,35 +153,40 @@ pub async fn start_game(
                 (player_in_action, None)
             };
 
-            let report = Arc::make_mut(&mut env).process(player, next_action);
+            let report = if let Some(Action::Timeout) = next_action {
+                Arc::make_mut(&mut env).timeout(player)
+            } else {
+                Arc::make_mut(&mut env).process(player, next_action)
+            };
             available_actions.clone_from(&report.available_actions);
 
+            if let Some(clock) = &mut clock {
+                for log in &report.logs {
+                    if let GameLog::TurnChanged { player, .. } = log {
+                        clock.add_increment(*player);
+                    }
+                }
+            }
+
             if let Some(available_actions) = &report.available_actions {
                 player_in_action = available_actions.player;
+                action_started = Instant::now();
+                if let Some(clock) = &mut clock {
+                    clock.start(player_in_action);
+                }
             }
 
+            let clocks = clock_snapshot(&clock, action_started);
+
             for player in &players {
                 if player.bot.is_none() {
-                    let state = LocalGameState {
-                        env: env.local(player.id),
-                        logs: report
-                            .logs
-                            .iter()
-                            .map(|log| log.clone().redacted(player.id))
-                            .collect(),
-                        available_actions: report
-                            .available_actions
-                            .clone()
-                            .filter(|actions| actions.player == player.id),
-                    };
-                    let event = GameEvent {
-                        game_id: 0,
-                        player: player.id,
-                        event: GameEventKind::StateUpdated {
-                            state: Box::new(state),
-                        },
-                    };
-
+                    let event = state_event(
+                        &env,
+                        player.id,
+                        report.logs.clone(),
+                        report.available_actions.clone(),
+                        &clocks,
+                    );
                     sender.send(Output::GameEvent(event)).await.unwrap();
                 }
             }
@@ -129,6 +194,37 @@ pub async fn start_game(
     }
 }
 
+fn state_event(
+    env: &Environment,
+    player: u8,
+    logs: Vec<GameLog>,
+    available_actions: Option<PlayerAvailableActions>,
+    clocks: &[PlayerClock],
+) -> GameEvent {
+    let mut local = env.local(player);
+    local.clocks = clocks.to_vec();
+    let state = LocalGameState {
+        env: local,
+        logs: logs.into_iter().map(|log| log.redacted(player)).collect(),
+        available_actions: available_actions.filter(|actions| actions.player == player),
+    };
+    GameEvent {
+        game_id: 0,
+        player,
+        event: GameEventKind::StateUpdated {
+            state: Box::new(state),
+            hash: env.state.redacted_hash(player),
+        },
+    }
+}
+
+fn clock_snapshot(clock: &Option<ChessClock>, action_started: Instant) -> Vec<PlayerClock> {
+    clock
+        .as_ref()
+        .map(|clock| clock.snapshot(action_started.elapsed()))
+        .unwrap_or_default()
+}
+
 #[derive(Debug)]
 struct PlayerData<T> {
     id: u8,
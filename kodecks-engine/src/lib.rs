@@ -1,3 +1,4 @@
+pub mod draft;
 pub mod game;
 pub mod login;
 pub mod message;
@@ -1,7 +1,8 @@
-use crate::{room::RoomConfig, user::UserId};
+use crate::{draft::DraftConfig, room::RoomConfig, user::UserId};
 use bincode::{Decode, Encode};
 use kodecks::{
-    action::Action, env::LocalGameState, error::Error, player::PlayerConfig, profile::GameProfile,
+    action::Action, archetype::ArchetypeId, clock::PlayerClock, env::LocalGameState, error::Error,
+    player::PlayerConfig, pool::CardPool, profile::GameProfile,
 };
 use serde::{Deserialize, Serialize};
 
@@ -10,18 +11,25 @@ pub enum Input {
     Command(Command),
     RoomCommand(RoomCommand),
     GameCommand(GameCommand),
+    DraftCommand(DraftCommand),
 }
 
 #[derive(Debug, Clone, Encode, Decode)]
 pub enum Command {
     CreateGame {
         log_id: String,
-        profile: GameProfile,
+        profile: Box<GameProfile>,
     },
     CreateRoom {
         config: RoomConfig,
         host_player: PlayerConfig,
     },
+    CreateDraft {
+        config: DraftConfig,
+    },
+    JoinDraft {
+        draft_id: u32,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
@@ -46,13 +54,30 @@ pub struct GameCommand {
 
 #[derive(Debug, Clone, Encode, Decode)]
 pub enum GameCommandKind {
-    NextAction { action: Action },
+    NextAction {
+        action: Action,
+    },
+    /// Requests the full state after the local view diverged from the server.
+    Resync,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct DraftCommand {
+    pub draft_id: u32,
+    pub seat: u8,
+    pub kind: DraftCommandKind,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+pub enum DraftCommandKind {
+    Pick { card: ArchetypeId },
 }
 
 #[derive(Debug, Clone, Encode, Decode)]
 pub enum Output {
     GameEvent(GameEvent),
     RoomEvent(RoomEvent),
+    DraftEvent(DraftEvent),
     Error(Error),
 }
 
@@ -79,7 +104,41 @@ pub struct GameEvent {
 
 #[derive(Debug, Clone, Encode, Decode)]
 pub enum GameEventKind {
-    Created { log_id: String },
-    StateUpdated { state: Box<LocalGameState> },
-    PlayerThinking { thinking: u8, timeout: Option<u32> },
+    Created {
+        log_id: String,
+    },
+    StateUpdated {
+        state: Box<LocalGameState>,
+        hash: u64,
+    },
+    PlayerThinking {
+        thinking: u8,
+        timeout: Option<u32>,
+        clocks: Vec<PlayerClock>,
+    },
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct DraftEvent {
+    pub draft_id: u32,
+    pub seat: u8,
+    pub event: DraftEventKind,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+pub enum DraftEventKind {
+    /// Sent to the seated players until all the seats without a bot are taken.
+    Waiting {
+        joined: u8,
+        players: u8,
+    },
+    PackReceived {
+        round: u8,
+        pick: u8,
+        pack: Vec<ArchetypeId>,
+        timeout: Option<u32>,
+    },
+    Finished {
+        pool: CardPool,
+    },
 }
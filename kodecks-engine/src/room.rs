@@ -1,12 +1,36 @@
 use bincode::{Decode, Encode};
 use kodecks::{pool::CardPool, regulation::Regulation};
 use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash)]
 pub struct RoomConfig {
     pub regulation: Regulation,
     pub card_pool: CardPool,
     pub room_type: RoomType,
+    /// Number of players including the host. The game starts once this many players have joined.
+    #[serde(default = "default_players")]
+    pub players: u8,
+}
+
+impl Default for RoomConfig {
+    fn default() -> Self {
+        Self {
+            regulation: Regulation::default(),
+            card_pool: CardPool::default(),
+            room_type: RoomType::default(),
+            players: default_players(),
+        }
+    }
+}
+
+impl RoomConfig {
+    /// Numbers of players a room can be created for.
+    pub const PLAYERS: RangeInclusive<u8> = 2..=4;
+}
+
+fn default_players() -> u8 {
+    2
 }
 
 #[derive(
@@ -1,6 +1,7 @@
 #![cfg(target_family = "wasm")]
 
 use crate::{
+    draft::start_draft,
     game::start_game,
     message::{Command, Input, Output},
     Connection,
@@ -81,6 +82,7 @@ impl Connection for WebWorkerEngine {
 pub async fn EngineReactor(mut scope: ReactorScope<Vec<u8>, Vec<u8>>) {
     let (event_send, mut event_recv) = futures::channel::mpsc::channel(256);
     let mut sender = None;
+    let mut draft_sender = None;
     let config = bincode::config::standard();
     loop {
         select! {
@@ -99,13 +101,23 @@ pub async fn EngineReactor(mut scope: ReactorScope<Vec<u8>, Vec<u8>>) {
                         Input::Command(Command::CreateGame { log_id, profile }) => {
                             let (command_sender, receiver) = mpsc::channel(256);
                             sender = Some(command_sender);
-                            spawn_local(start_game(log_id, profile, receiver, event_send.clone()));
+                            spawn_local(start_game(log_id, *profile, receiver, event_send.clone()));
+                        }
+                        Input::Command(Command::CreateDraft { config }) => {
+                            let (command_sender, receiver) = mpsc::channel(256);
+                            draft_sender = Some(command_sender);
+                            spawn_local(start_draft(config, receiver, event_send.clone()));
                         }
                         Input::GameCommand(session_command) => {
                             if let Some(sender) = &mut sender {
                                 sender.send(session_command).await.unwrap();
                             }
                         }
+                        Input::DraftCommand(draft_command) => {
+                            if let Some(sender) = &mut draft_sender {
+                                sender.send(draft_command).await.unwrap();
+                            }
+                        }
                         _ => {}
                     }
                 } else {
@@ -1,4 +1,5 @@
 use crate::{
+    draft::DraftList,
     game::{GameList, PlayerData},
     room::RoomList,
     session::Session,
@@ -14,12 +15,14 @@ use kodecks::error::Error;
 use kodecks_catalog::CATALOG;
 use kodecks_engine::{
     message::{Command, Input, Output, RoomCommand, RoomCommandKind, RoomEvent, RoomEventKind},
+    room::RoomConfig,
     user::UserId,
 };
 use semver::{BuildMetadata, Version, VersionReq};
 use serde::Serialize;
 use std::{
     cmp::Ordering,
+    path::PathBuf,
     sync::{Arc, Mutex},
 };
 
@@ -30,15 +33,17 @@ pub struct AppState {
     tokens: DashMap<Token, UserId>,
     rooms: Mutex<RoomList>,
     games: Mutex<GameList>,
+    drafts: Mutex<DraftList>,
 }
 
 impl AppState {
-    pub fn new() -> Self {
+    pub fn new(replay_dir: Option<PathBuf>) -> Self {
         Self {
             sessions: DashMap::new(),
             tokens: DashMap::new(),
             rooms: Mutex::new(RoomList::default()),
-            games: Mutex::new(GameList::default()),
+            games: Mutex::new(GameList::new(replay_dir)),
+            drafts: Mutex::new(DraftList::default()),
         }
     }
 
@@ -110,12 +115,14 @@ impl AppState {
         self.sessions.remove(user_id);
         self.rooms.lock().unwrap().remove_by_owner(user_id);
         self.games.lock().unwrap().abandon(user_id);
+        self.drafts.lock().unwrap().abandon(user_id);
     }
 
     pub fn cleanup(&self) {
         self.sessions.retain(|_, session| !session.is_expired());
         self.tokens.retain(|_, id| self.sessions.contains_key(id));
         self.games.lock().unwrap().cleanup();
+        self.drafts.lock().unwrap().cleanup();
     }
 
     pub fn send(&self, user_id: &UserId, event: Output) -> bool {
@@ -132,10 +139,23 @@ impl AppState {
                 config,
                 host_player,
             }) => {
-                if !config.regulation.verify(&host_player.deck, &CATALOG)
-                    || !config.card_pool.verify(&host_player.deck, &CATALOG)
-                {
-                    self.send(user_id, Output::Error(Error::InvalidDeck));
+                if !RoomConfig::PLAYERS.contains(&config.players) {
+                    self.send(
+                        user_id,
+                        Output::Error(Error::InvalidPlayerCount {
+                            players: config.players,
+                        }),
+                    );
+                    return;
+                }
+
+                let violations = config.regulation.verify_with_pool(
+                    &host_player.deck,
+                    &config.card_pool,
+                    &CATALOG,
+                );
+                if !violations.is_empty() {
+                    self.send(user_id, Output::Error(Error::InvalidDeck { violations }));
                     return;
                 }
 
@@ -151,7 +171,9 @@ impl AppState {
                 rooms
                     .random_match_rooms()
                     .filter(|room| {
-                        room.owner != *user_id && room.config.regulation == config.regulation
+                        room.owner != *user_id
+                            && room.config.regulation == config.regulation
+                            && room.config.players == config.players
                     })
                     .for_each(|room| {
                         self.send(
@@ -168,34 +190,85 @@ impl AppState {
             Input::RoomCommand(RoomCommand { room_id, kind }) => {
                 let RoomCommandKind::Approve { guest } = kind;
                 let mut rooms = self.rooms.lock().unwrap();
-                if let Some(room) = rooms.get(&room_id) {
-                    let owner = room.owner.clone();
-                    let regulation = room.config.regulation.clone();
-                    let mut players = vec![];
-                    if let Some(room) = rooms.remove_by_owner(&owner) {
-                        if let Some(sender) = self
-                            .session_from_id(&owner)
-                            .and_then(|session| session.event_sender().clone())
-                        {
-                            players.push(PlayerData::new(owner, room.player, sender));
-                        }
-                    }
-                    if let Some(room) = rooms.remove_by_owner(&guest) {
-                        if let Some(sender) = self
-                            .session_from_id(&guest)
-                            .and_then(|session| session.event_sender().clone())
-                        {
-                            players.push(PlayerData::new(guest, room.player, sender));
+                let Some(owner) = rooms.get(&room_id).map(|room| room.owner.clone()) else {
+                    return;
+                };
+                if owner != *user_id || guest == owner {
+                    return;
+                }
+
+                // A guest joins with the player of their own room, unless others have already joined it.
+                if !rooms
+                    .get_by_owner(&guest)
+                    .is_some_and(|room| room.guests.is_empty())
+                {
+                    return;
+                }
+                let Some(guest_room) = rooms.remove_by_owner(&guest) else {
+                    return;
+                };
+                let Some(room) = rooms.get_mut(&room_id) else {
+                    return;
+                };
+                room.guests.push((guest, guest_room.player));
+                if room.guests.len() + 1 < room.config.players as usize {
+                    return;
+                }
+
+                if let Some(room) = rooms.remove_by_owner(&owner) {
+                    let members = std::iter::once((room.owner, room.player))
+                        .chain(room.guests)
+                        .collect::<Vec<_>>();
+                    let players = members
+                        .iter()
+                        .filter_map(|(user_id, config)| {
+                            self.session_from_id(user_id)
+                                .and_then(|session| session.event_sender().clone())
+                                .map(|sender| {
+                                    PlayerData::new(user_id.clone(), config.clone(), sender)
+                                })
+                        })
+                        .collect::<Vec<_>>();
+                    if players.len() == room.config.players as usize {
+                        self.games
+                            .lock()
+                            .unwrap()
+                            .create(room.config.regulation, players);
+                    } else {
+                        for (user_id, _) in &members {
+                            self.send(user_id, Output::Error(Error::PlayerUnavailable));
                         }
                     }
-                    if players.len() == 2 {
-                        self.games.lock().unwrap().create(regulation, players);
-                    }
+                }
+            }
+            Input::Command(Command::CreateDraft { config }) => {
+                if let Some(sender) = self
+                    .session_from_id(user_id)
+                    .and_then(|session| session.event_sender().clone())
+                {
+                    self.drafts
+                        .lock()
+                        .unwrap()
+                        .create(user_id.clone(), config, sender);
+                }
+            }
+            Input::Command(Command::JoinDraft { draft_id }) => {
+                if let Some(sender) = self
+                    .session_from_id(user_id)
+                    .and_then(|session| session.event_sender().clone())
+                {
+                    self.drafts
+                        .lock()
+                        .unwrap()
+                        .join(draft_id, user_id.clone(), sender);
                 }
             }
             Input::GameCommand(command) => {
                 self.games.lock().unwrap().handle_command(user_id, command);
             }
+            Input::DraftCommand(command) => {
+                self.drafts.lock().unwrap().handle_command(user_id, command);
+            }
             _ => {}
         }
     }
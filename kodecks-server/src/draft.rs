@@ -0,0 +1,233 @@
+use kodecks_catalog::CATALOG;
+use kodecks_engine::{
+    draft::{self, DraftConfig},
+    message::{DraftCommand, DraftCommandKind, DraftEvent, DraftEventKind, Output},
+    user::UserId,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+use tokio::{
+    select,
+    sync::mpsc::{self, Receiver, Sender},
+    time::{self, Instant},
+};
+use tracing::warn;
+
+const CHANNEL_TIMEOUT: Duration = Duration::from_secs(1);
+const MAX_SEATS: u8 = 8;
+const MAX_PACKS: u8 = 5;
+
+#[derive(Debug, Default)]
+pub struct DraftList {
+    counter: u32,
+    drafts: HashMap<u32, Draft>,
+}
+
+impl DraftList {
+    /// Creates a draft and seats the user at it.
+    /// The draft starts once users have joined all the seats without a bot.
+    pub fn create(&mut self, user_id: UserId, config: DraftConfig, sender: Sender<Output>) -> u32 {
+        let id = self.counter;
+        self.counter += 1;
+
+        let mut draft = Draft::new(id, config);
+        draft.join(user_id, sender);
+        self.drafts.insert(id, draft);
+        id
+    }
+
+    /// Seats the user at a draft which has not started yet.
+    pub fn join(&mut self, draft_id: u32, user_id: UserId, sender: Sender<Output>) {
+        if let Some(draft) = self.drafts.get_mut(&draft_id) {
+            draft.join(user_id, sender);
+        }
+    }
+
+    pub fn handle_command(&self, user_id: &UserId, command: DraftCommand) {
+        if let Some(draft) = self.drafts.get(&command.draft_id) {
+            draft.handle_command(user_id, command);
+        }
+    }
+
+    pub fn cleanup(&mut self) {
+        self.drafts.retain(|_, draft| match &draft.sender {
+            Some(sender) => !sender.is_closed(),
+            None => !draft.players.is_empty(),
+        });
+    }
+
+    /// Removes the user from the drafts waiting for players.
+    /// The picks of the seats left in running drafts are made for them.
+    pub fn abandon(&mut self, user_id: &UserId) {
+        for draft in self.drafts.values_mut() {
+            draft.leave(user_id);
+        }
+        self.cleanup();
+    }
+}
+
+#[derive(Debug)]
+pub struct Draft {
+    id: u32,
+    config: DraftConfig,
+    players: BTreeMap<u8, (UserId, Sender<Output>)>,
+    sender: Option<Sender<DraftCommand>>,
+}
+
+impl Draft {
+    pub fn new(id: u32, mut config: DraftConfig) -> Self {
+        config.seats = config.seats.clamp(1, MAX_SEATS);
+        config.packs = config.packs.min(MAX_PACKS);
+        config.bots.retain(|bot| *bot < config.seats);
+        if (0..config.seats).all(|seat| config.bots.contains(&seat)) {
+            config.bots.retain(|bot| *bot != 0);
+        }
+        Self {
+            id,
+            config,
+            players: BTreeMap::new(),
+            sender: None,
+        }
+    }
+
+    /// Seats the user at the first free seat, and starts the draft once all the seats are taken.
+    pub fn join(&mut self, user_id: UserId, sender: Sender<Output>) {
+        if self.sender.is_some() || self.players.values().any(|(id, _)| *id == user_id) {
+            return;
+        }
+        let Some(seat) = self.seats().find(|seat| !self.players.contains_key(seat)) else {
+            return;
+        };
+        self.players.insert(seat, (user_id, sender));
+        self.notify_players();
+
+        if self.players.len() == self.seats().count() {
+            let players = self
+                .players
+                .iter()
+                .map(|(seat, (_, sender))| (*seat, sender.clone()))
+                .collect();
+            let (sender, receiver) = mpsc::channel(MAX_SEATS as usize);
+            tokio::spawn(Self::start_draft(
+                self.id,
+                self.config.clone(),
+                players,
+                receiver,
+            ));
+            self.sender = Some(sender);
+        }
+    }
+
+    pub fn leave(&mut self, user_id: &UserId) {
+        if self.sender.is_none() {
+            self.players.retain(|_, (id, _)| id != user_id);
+            self.notify_players();
+        }
+    }
+
+    pub fn handle_command(&self, user_id: &UserId, command: DraftCommand) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        if self
+            .players
+            .get(&command.seat)
+            .is_some_and(|(id, _)| id == user_id)
+        {
+            let _ = sender.try_send(command);
+        }
+    }
+
+    /// The seats without a bot.
+    fn seats(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..self.config.seats).filter(|seat| !self.config.bots.contains(seat))
+    }
+
+    fn notify_players(&self) {
+        for (seat, (_, sender)) in &self.players {
+            let event = DraftEvent {
+                draft_id: self.id,
+                seat: *seat,
+                event: DraftEventKind::Waiting {
+                    joined: self.players.len() as u8,
+                    players: self.seats().count() as u8,
+                },
+            };
+            let _ = sender.try_send(Output::DraftEvent(event));
+        }
+    }
+
+    async fn start_draft(
+        draft_id: u32,
+        config: DraftConfig,
+        players: BTreeMap<u8, Sender<Output>>,
+        mut receiver: Receiver<DraftCommand>,
+    ) {
+        let mut draft = draft::Draft::new(config, CATALOG.clone());
+        while !draft.is_finished() {
+            if players.values().all(Sender::is_closed) {
+                return;
+            }
+            for (&seat, player) in &players {
+                let event = DraftEvent {
+                    draft_id,
+                    seat,
+                    event: DraftEventKind::PackReceived {
+                        round: draft.round(),
+                        pick: draft.pick_number(),
+                        pack: draft.pack(seat).to_vec(),
+                        timeout: draft.remaining_time().map(|time| time.as_secs() as u32),
+                    },
+                };
+                let result = player
+                    .send_timeout(Output::DraftEvent(event), CHANNEL_TIMEOUT)
+                    .await;
+                if let Err(err) = result {
+                    warn!("failed to send event: {}", err);
+                }
+            }
+
+            let pick_started = Instant::now();
+            let deadline = draft.remaining_time().map(|time| pick_started + time);
+            let step = (draft.round(), draft.pick_number());
+            // The seats whose players have left pick like bots.
+            for (&seat, player) in &players {
+                if player.is_closed() {
+                    draft.time_out(seat);
+                }
+            }
+            while !draft.is_finished() && (draft.round(), draft.pick_number()) == step {
+                select! {
+                    command = receiver.recv() => {
+                        let Some(command) = command else {
+                            return;
+                        };
+                        let DraftCommandKind::Pick { card } = command.kind;
+                        draft.pick(command.seat, card);
+                    }
+                    _ = time::sleep_until(deadline.unwrap_or(pick_started)), if deadline.is_some() => {
+                        draft.elapse(pick_started.elapsed());
+                    }
+                }
+            }
+        }
+
+        for (&seat, player) in &players {
+            let event = DraftEvent {
+                draft_id,
+                seat,
+                event: DraftEventKind::Finished {
+                    pool: draft.card_pool(seat),
+                },
+            };
+            let result = player
+                .send_timeout(Output::DraftEvent(event), CHANNEL_TIMEOUT)
+                .await;
+            if let Err(err) = result {
+                warn!("failed to send event: {}", err);
+            }
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use futures_util::future;
 use kodecks::{
     action::{Action, PlayerAvailableActions},
+    clock::{ChessClock, PlayerClock},
     env::{Environment, LocalGameState},
     log::GameLog,
-    player::PlayerConfig,
+    player::{PlayerConfig, PlayerItem},
     profile::GameProfile,
     regulation::Regulation,
+    replay::Replay,
 };
 use kodecks_catalog::CATALOG;
 use kodecks_engine::{
@@ -14,6 +16,7 @@ use kodecks_engine::{
 };
 use std::{
     collections::{HashMap, VecDeque},
+    path::PathBuf,
     sync::Arc,
     time::Duration,
 };
@@ -32,9 +35,17 @@ pub struct GameList {
     counter: u32,
     games: HashMap<u32, Game>,
     players: HashMap<UserId, u32>,
+    replay_dir: Option<PathBuf>,
 }
 
 impl GameList {
+    pub fn new(replay_dir: Option<PathBuf>) -> Self {
+        Self {
+            replay_dir,
+            ..Default::default()
+        }
+    }
+
     pub fn create(&mut self, regulation: Regulation, players: Vec<PlayerData>) -> u32 {
         let id = self.counter;
         self.counter += 1;
@@ -43,7 +54,7 @@ impl GameList {
             self.players.insert(player.user_id.clone(), id);
         }
 
-        let game = Game::new(id, regulation, players);
+        let game = Game::new(id, regulation, players, self.replay_dir.clone());
         self.games.insert(id, game);
         id
     }
@@ -111,7 +122,12 @@ pub struct Game {
 }
 
 impl Game {
-    pub fn new(game_id: u32, regulation: Regulation, players: Vec<PlayerData>) -> Self {
+    pub fn new(
+        game_id: u32,
+        regulation: Regulation,
+        players: Vec<PlayerData>,
+        replay_dir: Option<PathBuf>,
+    ) -> Self {
         let player_configs = players.iter().map(|player| player.config.clone()).collect();
         let profile = GameProfile {
             regulation,
@@ -126,13 +142,16 @@ impl Game {
         );
 
         let (sender, receiver) = mpsc::channel(1);
-        tokio::spawn(Self::start_game(
-            game_id,
-            log_id,
-            profile,
-            players.clone(),
-            receiver,
-        ));
+        let game_players = players.clone();
+        tokio::spawn(async move {
+            let replay = Self::start_game(game_id, &log_id, profile, game_players, receiver).await;
+            if let Some(dir) = replay_dir {
+                let path = dir.join(format!("{log_id}.replay"));
+                if let Err(err) = tokio::fs::write(&path, replay.encode()).await {
+                    warn!("failed to save replay {}: {}", path.display(), err);
+                }
+            }
+        });
 
         Self { sender, players }
     }
@@ -147,19 +166,20 @@ impl Game {
 
     async fn start_game(
         game_id: u32,
-        log_id: String,
+        log_id: &str,
         profile: GameProfile,
         mut players: Vec<PlayerData>,
         mut receiver: Receiver<GameCommand>,
-    ) {
+    ) -> Replay {
         let regulation = profile.regulation.clone();
 
-        let mut env = Arc::new(Environment::new(profile, CATALOG.clone()));
+        let mut replay = Replay::new(profile, &CATALOG);
+        let mut env = Arc::new(Environment::new(replay.profile.clone(), CATALOG.clone()));
         let mut available_actions: Option<PlayerAvailableActions> = None;
         let mut player_in_action = if let Ok(player) = env.state.players.player_in_turn() {
             player.id
         } else {
-            return;
+            return replay;
         };
 
         for player in env.state.players.iter() {
@@ -170,7 +190,7 @@ impl Game {
                         game_id,
                         player: player.id,
                         event: GameEventKind::Created {
-                            log_id: log_id.clone(),
+                            log_id: log_id.to_string(),
                         },
                     }),
                     CHANNEL_TIMEOUT,
@@ -184,6 +204,10 @@ impl Game {
             }
         }
 
+        let mut clock = regulation.time_control.map(|control| {
+            ChessClock::new(control, env.state.players.iter().map(|player| player.id))
+        });
+        let mut action_started = Instant::now();
         let mut next_action_deadline = Instant::now() + regulation.action_timeout;
         let mut next_phase_deadline = Instant::now() + regulation.phase_timeout;
 
@@ -198,17 +222,45 @@ impl Game {
                     command = receiver.recv() => {
                         if let Some(command) = command {
                             let player = &mut players[command.player as usize];
-                            let GameCommandKind::NextAction { action } = command.kind;
-                            player.next_actions.push_back(action);
-                            player.consecutive_timeouts = 0;
+                            match command.kind {
+                                GameCommandKind::NextAction { action } => {
+                                    if !matches!(action, Action::Timeout) {
+                                        player.next_actions.push_back(action);
+                                        player.consecutive_timeouts = 0;
+                                    }
+                                }
+                                GameCommandKind::Resync => {
+                                    let clocks = clock
+                                        .as_ref()
+                                        .map(|clock| clock.snapshot(action_started.elapsed()))
+                                        .unwrap_or_default();
+                                    let event = state_event(
+                                        game_id,
+                                        &env,
+                                        command.player,
+                                        &[],
+                                        Some(available_actions),
+                                        &clocks,
+                                    );
+                                    let result = player
+                                        .sender
+                                        .send_timeout(Output::GameEvent(event), CHANNEL_TIMEOUT)
+                                        .await;
+                                    if let Err(err) = result {
+                                        warn!("failed to send event: {}", err);
+                                    }
+                                }
+                            }
                         } else {
-                            return;
+                            return replay;
                         }
                     }
                     _ = action_timeout => {
                         let player = &mut players[player_in_action as usize];
                         player.consecutive_timeouts += 1;
-                        let action = if player.consecutive_timeouts >= regulation.max_consecutive_timeouts {
+                        let action = if clock.is_some() {
+                            Action::Timeout
+                        } else if player.consecutive_timeouts >= regulation.max_consecutive_timeouts {
                             Action::Concede
                         } else {
                             available_actions.actions.default_action(&env).unwrap_or(Action::Concede)
@@ -217,6 +269,7 @@ impl Game {
                     }
                     _ = player_thinking_timeout => {
                         let timeout = next_action_deadline.checked_duration_since(Instant::now()).map(|d| d.as_secs() as u32);
+                        let clocks = clock.as_ref().map(|clock| clock.snapshot(action_started.elapsed())).unwrap_or_default();
                         for player in env.state.players.iter() {
                             let result = players[player.id as usize]
                                 .sender
@@ -224,7 +277,7 @@ impl Game {
                                     Output::GameEvent(GameEvent {
                                         game_id,
                                         player: player.id,
-                                        event: GameEventKind::PlayerThinking { thinking: player_in_action, timeout },
+                                        event: GameEventKind::PlayerThinking { thinking: player_in_action, timeout, clocks: clocks.clone() },
                                     }),
                                     CHANNEL_TIMEOUT,
                                 )
@@ -241,66 +294,100 @@ impl Game {
             }
 
             while !env.game_condition().is_ended() {
-                let (player, next_action) =
-                    if matches!(players[0].next_actions.front(), Some(Action::Concede)) {
-                        (0, Some(Action::Concede))
-                    } else if matches!(players[1].next_actions.front(), Some(Action::Concede)) {
-                        (1, Some(Action::Concede))
-                    } else if let Some(available_actions) = &available_actions {
-                        let next_actions =
-                            &mut players[available_actions.player as usize].next_actions;
-                        while let Some(action) = next_actions.front() {
-                            if available_actions.actions.validate(action) {
-                                break;
-                            } else {
-                                next_actions.pop_front();
-                            }
+                let conceded = players.iter().position(|player| {
+                    matches!(
+                        player.next_actions.front(),
+                        Some(Action::Concede | Action::Timeout)
+                    )
+                });
+                let conceded = conceded.and_then(|player| {
+                    let action = players[player].next_actions.front().cloned();
+                    players[player].next_actions.clear();
+                    action.map(|action| (player as u8, action))
+                });
+
+                let (player, next_action) = if let Some((player, action)) =
+                    conceded.filter(|&(player, _)| {
+                        env.state
+                            .players
+                            .get(player)
+                            .is_ok_and(|player| !player.is_eliminated())
+                    }) {
+                    (player, Some(action))
+                } else if let Some(available_actions) = &available_actions {
+                    let next_actions = &mut players[available_actions.player as usize].next_actions;
+                    while let Some(action) = next_actions.front() {
+                        if available_actions.actions.validate(action) {
+                            break;
+                        } else {
+                            next_actions.pop_front();
                         }
-                        if let Some(action) = next_actions.pop_front() {
+                    }
+                    if let Some(action) = next_actions.pop_front() {
+                        let in_time = clock.as_mut().is_none_or(|clock| {
+                            clock.consume(player_in_action, action_started.elapsed())
+                        });
+                        if in_time {
                             (player_in_action, Some(action))
                         } else {
-                            break;
+                            (player_in_action, Some(Action::Timeout))
                         }
                     } else {
-                        (player_in_action, None)
-                    };
+                        break;
+                    }
+                } else {
+                    (player_in_action, None)
+                };
 
-                let report = Arc::make_mut(&mut env).process(player, next_action);
+                let report = if let Some(Action::Timeout) = next_action {
+                    Arc::make_mut(&mut env).timeout(player)
+                } else {
+                    Arc::make_mut(&mut env).process(player, next_action.clone())
+                };
+                replay.record(player, next_action, &report);
                 available_actions.clone_from(&report.available_actions);
 
+                if let Some(clock) = &mut clock {
+                    for log in &report.logs {
+                        if let GameLog::TurnChanged { player, .. } = log {
+                            clock.add_increment(*player);
+                        }
+                    }
+                }
+
                 if let Some(available_actions) = &report.available_actions {
                     player_in_action = available_actions.player;
-                    next_action_deadline = Instant::now() + regulation.action_timeout;
+                    action_started = Instant::now();
+                    if let Some(clock) = &mut clock {
+                        clock.start(player_in_action);
+                        next_action_deadline = action_started + clock.time_left(player_in_action);
+                        next_phase_deadline = next_action_deadline;
+                    } else {
+                        next_action_deadline = action_started + regulation.action_timeout;
+                    }
                 }
 
                 let phase_changed = report
                     .logs
                     .iter()
                     .any(|logs| matches!(logs, GameLog::PhaseChanged { .. }));
-                if phase_changed {
+                if phase_changed && clock.is_none() {
                     next_phase_deadline = Instant::now() + regulation.phase_timeout;
                 }
 
+                let clocks = clock
+                    .as_ref()
+                    .map(|clock| clock.snapshot(action_started.elapsed()))
+                    .unwrap_or_default();
                 for player in env.state.players.iter() {
-                    let state = LocalGameState {
-                        env: env.local(player.id),
-                        logs: report
-                            .logs
-                            .iter()
-                            .map(|log| log.clone().redacted(player.id))
-                            .collect(),
-                        available_actions: report
-                            .available_actions
-                            .clone()
-                            .filter(|actions| actions.player == player.id),
-                    };
-                    let event = GameEvent {
+                    let event = state_event(
                         game_id,
-                        player: player.id,
-                        event: GameEventKind::StateUpdated {
-                            state: Box::new(state),
-                        },
-                    };
+                        &env,
+                        player.id,
+                        &report.logs,
+                        report.available_actions.as_ref(),
+                        &clocks,
+                    );
                     let result = players[player.id as usize]
                         .sender
                         .send_timeout(Output::GameEvent(event), CHANNEL_TIMEOUT)
@@ -314,5 +401,36 @@ impl Game {
                 }
             }
         }
+        replay
+    }
+}
+
+fn state_event(
+    game_id: u32,
+    env: &Environment,
+    player: u8,
+    logs: &[GameLog],
+    available_actions: Option<&PlayerAvailableActions>,
+    clocks: &[PlayerClock],
+) -> GameEvent {
+    let mut local = env.local(player);
+    local.clocks = clocks.to_vec();
+    let state = LocalGameState {
+        env: local,
+        logs: logs
+            .iter()
+            .map(|log| log.clone().redacted(player))
+            .collect(),
+        available_actions: available_actions
+            .filter(|actions| actions.player == player)
+            .cloned(),
+    };
+    GameEvent {
+        game_id,
+        player,
+        event: GameEventKind::StateUpdated {
+            state: Box::new(state),
+            hash: env.state.redacted_hash(player),
+        },
     }
 }
@@ -12,6 +12,7 @@ use http::{
 use kodecks::error::Error;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
     sync::Arc,
 };
 use tokio::try_join;
@@ -27,6 +28,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilte
 mod app;
 mod auth;
 mod background;
+mod draft;
 mod game;
 mod login;
 mod room;
@@ -47,6 +49,9 @@ pub struct Options {
     /// The port to listen on.
     #[bpaf(argument("PORT"), env("PORT"), fallback(8080), display_fallback)]
     port: u16,
+    /// The directory to save game replays to.
+    #[bpaf(argument("DIR"), env("REPLAY_DIR"))]
+    replay_dir: Option<PathBuf>,
 }
 
 #[tokio::main]
@@ -77,7 +82,7 @@ async fn main() -> anyhow::Result<()> {
             .unwrap(),
     );
 
-    let state = Arc::new(app::AppState::new());
+    let state = Arc::new(app::AppState::new(opt.replay_dir.clone()));
 
     let authorized = Router::new()
         .route("/logout", get(login::logout))
@@ -9,6 +9,8 @@ pub struct Room {
     pub owner: UserId,
     pub config: RoomConfig,
     pub player: PlayerConfig,
+    /// Approved guests waiting for the room to fill up.
+    pub guests: Vec<(UserId, PlayerConfig)>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -32,6 +34,7 @@ impl RoomList {
                 owner: owner.clone(),
                 config,
                 player,
+                guests: vec![],
             },
         );
         self.owners.insert(owner, id.clone());
@@ -46,6 +49,14 @@ impl RoomList {
         self.rooms.get(id)
     }
 
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut Room> {
+        self.rooms.get_mut(id)
+    }
+
+    pub fn get_by_owner(&self, owner: &UserId) -> Option<&Room> {
+        self.owners.get(owner).and_then(|id| self.rooms.get(id))
+    }
+
     pub fn remove_by_owner(&mut self, owner: &UserId) -> Option<Room> {
         self.owners
             .remove(owner)
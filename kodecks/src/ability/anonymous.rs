@@ -10,10 +10,23 @@ use std::{
     mem,
     ops::{Add, Sub},
 };
+use strum::{Display, EnumIter};
 use tinystr::tinystr;
 
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Display,
+    EnumIter,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
 )]
 #[non_exhaustive]
 #[serde(rename_all = "snake_case")]
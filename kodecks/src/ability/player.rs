@@ -1,13 +1,18 @@
 use crate::score::Score;
 
 use super::Ability;
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
 use std::{
     mem,
     ops::{Add, Sub},
 };
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Encode, Decode,
+)]
 #[non_exhaustive]
+#[serde(rename_all = "snake_case")]
 pub enum PlayerAbility {
     Propagate(i32),
     Draw,
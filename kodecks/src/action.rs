@@ -4,6 +4,7 @@ use crate::{
     env::Environment,
     id::{TimedCardId, TimedObjectId},
     message::{Message, MessageDialog},
+    target::Target,
 };
 use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
@@ -18,6 +19,8 @@ pub enum AvailableAction {
     },
     Attack {
         attackers: Vec<TimedObjectId>,
+        #[serde(default)]
+        targets: Vec<Target>,
     },
     Block {
         attackers: Vec<TimedObjectId>,
@@ -26,6 +29,10 @@ pub enum AvailableAction {
     CastCard {
         cards: Vec<TimedObjectId>,
     },
+    /// Cards in hand that can be returned to the deck.
+    Mulligan {
+        cards: Vec<TimedObjectId>,
+    },
     EndTurn,
     Continue,
 }
@@ -43,8 +50,9 @@ impl Ord for AvailableAction {
             AvailableAction::Attack { .. } => 1,
             AvailableAction::Block { .. } => 2,
             AvailableAction::CastCard { .. } => 3,
-            AvailableAction::EndTurn => 4,
-            AvailableAction::Continue => 5,
+            AvailableAction::Mulligan { .. } => 4,
+            AvailableAction::EndTurn => 5,
+            AvailableAction::Continue => 6,
         };
         index(self).cmp(&index(other))
     }
@@ -111,17 +119,23 @@ impl AvailableActionList {
                     .iter()
                     .any(|action| matches!(action, AvailableAction::SelectCard { cards, .. } if cards.contains(card)))
             }
-            Action::Attack { attackers } => {
+            Action::Attack { attackers, target } => {
                 self.0
                     .iter()
-                    .any(|action| matches!(action, AvailableAction::Attack { attackers: available } if attackers.iter().all(|a| available.contains(a))))
+                    .any(|action| matches!(action, AvailableAction::Attack { attackers: available, targets } if attackers.iter().all(|a| available.contains(a)) && target.is_none_or(|t| targets.contains(&t))))
             }
             Action::Block { pairs } => {
                 self.0
                     .iter()
                     .any(|action| matches!(action, AvailableAction::Block { blockers, .. } if pairs.iter().all(|(_, b)| blockers.contains(b))))
             }
+            Action::Mulligan { cards } => {
+                self.0
+                    .iter()
+                    .any(|action| matches!(action, AvailableAction::Mulligan { cards: available } if cards.iter().all(|c| available.contains(c))))
+            }
             Action::EndTurn => self.0.iter().any(|action| matches!(action, AvailableAction::EndTurn)),
+            Action::Timeout => false,
             Action::Continue => self.0.iter().any(|action| matches!(action, AvailableAction::Continue)),
             _ => true,
         }
@@ -135,7 +149,7 @@ impl AvailableActionList {
     pub fn attackers(&self) -> Vec<TimedObjectId> {
         self.iter()
             .filter_map(|action| {
-                if let AvailableAction::Attack { attackers } = action {
+                if let AvailableAction::Attack { attackers, .. } = action {
                     Some(attackers)
                 } else {
                     None
@@ -146,6 +160,34 @@ impl AvailableActionList {
             .collect()
     }
 
+    pub fn attack_targets(&self) -> Vec<Target> {
+        self.iter()
+            .filter_map(|action| {
+                if let AvailableAction::Attack { targets, .. } = action {
+                    Some(targets)
+                } else {
+                    None
+                }
+            })
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    pub fn mulligan_cards(&self) -> Vec<TimedObjectId> {
+        self.iter()
+            .filter_map(|action| {
+                if let AvailableAction::Mulligan { cards } = action {
+                    Some(cards)
+                } else {
+                    None
+                }
+            })
+            .flatten()
+            .copied()
+            .collect()
+    }
+
     pub fn blockers(&self) -> Vec<TimedObjectId> {
         self.iter()
             .filter_map(|action| {
@@ -204,11 +246,14 @@ impl AvailableActionList {
                         return Some(Action::SelectCard { card });
                     }
                 }
-                AvailableAction::CastCard { .. } => {
+                AvailableAction::CastCard { .. } | AvailableAction::Mulligan { .. } => {
                     continue;
                 }
                 AvailableAction::Attack { .. } => {
-                    return Some(Action::Attack { attackers: vec![] });
+                    return Some(Action::Attack {
+                        attackers: vec![],
+                        target: None,
+                    });
                 }
                 AvailableAction::Block { .. } => {
                     return Some(Action::Block { pairs: vec![] });
@@ -255,12 +300,21 @@ pub enum Action {
     },
     Attack {
         attackers: Vec<TimedObjectId>,
+        /// The player to attack. Defaults to the next player in turn order.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target: Option<Target>,
     },
     Block {
         pairs: Vec<(TimedObjectId, TimedObjectId)>,
     },
+    /// Returns the cards to the deck. Under `MulliganPolicy::Redraw` the whole hand is returned.
+    Mulligan {
+        cards: Vec<TimedObjectId>,
+    },
     EndTurn,
     Concede,
+    /// Issued by the host when the player runs out of time. Not accepted from players.
+    Timeout,
     Continue,
     DebugCommand {
         commands: Vec<ActionCommand>,
@@ -279,7 +333,13 @@ impl From<Action> for Value {
                 obj.insert(tinystr!(32, "card"), Value::Custom(CustomType::Card(card)));
                 "select_card"
             }
-            Action::Attack { attackers } => {
+            Action::Attack { attackers, target } => {
+                if let Some(Target::Player(player)) = target {
+                    obj.insert(
+                        tinystr!(32, "target"),
+                        Value::Custom(CustomType::Player(player)),
+                    );
+                }
                 obj.insert(
                     tinystr!(32, "attackers"),
                     Value::Array(
@@ -314,8 +374,21 @@ impl From<Action> for Value {
                 );
                 "block"
             }
+            Action::Mulligan { cards } => {
+                obj.insert(
+                    tinystr!(32, "cards"),
+                    Value::Array(
+                        cards
+                            .into_iter()
+                            .map(|card| Value::Custom(CustomType::Card(card)))
+                            .collect(),
+                    ),
+                );
+                "mulligan"
+            }
             Action::EndTurn => "end_turn",
             Action::Concede => "concede",
+            Action::Timeout => "timeout",
             Action::Continue => "continue",
             Action::DebugCommand { .. } => "debug_command",
         };
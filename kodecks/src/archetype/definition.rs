@@ -0,0 +1,175 @@
+use super::{effect::EffectDef, ArchetypeId, CardArchetype, CardAttribute, EffectFactory};
+use crate::{card::safe_name, dsl::script::exp::Module};
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+use thiserror::Error;
+
+pub const CARD_FILE_EXTENSION: &str = "json";
+pub const SCRIPT_FILE_EXTENSION: &str = "jq";
+
+/// A card defined in a data file instead of a Rust module.
+///
+/// The attributes use the same names as `CardAttribute`.
+/// The effect is given by `script`, or by a `.jq` file next to the card file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CardDefinition {
+    pub id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub attribute: CardAttribute,
+    #[serde(default)]
+    pub script: Option<String>,
+}
+
+impl CardDefinition {
+    pub fn load<P>(path: P) -> Result<Self, CardDefinitionError>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let data = fs::read_to_string(path).map_err(|err| CardDefinitionError::Io {
+            path: path.to_path_buf(),
+            message: err.to_string(),
+        })?;
+        let mut def = Self::from_str(&data).map_err(|err| err.with_path(path))?;
+        if def.script.is_none() {
+            let script_path = path.with_extension(SCRIPT_FILE_EXTENSION);
+            if script_path.is_file() {
+                let script =
+                    fs::read_to_string(&script_path).map_err(|err| CardDefinitionError::Io {
+                        path: script_path,
+                        message: err.to_string(),
+                    })?;
+                def.script = Some(script);
+            }
+        }
+        Ok(def)
+    }
+
+    pub fn build(&self) -> Result<CardArchetype, CardDefinitionError> {
+        if self.id.is_empty() || self.id.len() > 8 || !self.id.is_ascii() {
+            return Err(CardDefinitionError::InvalidId {
+                id: self.id.clone(),
+            });
+        }
+        let safe_name = safe_name(&self.name).map_err(|_| CardDefinitionError::InvalidName {
+            name: self.name.clone(),
+        })?;
+        let effect = if let Some(script) = &self.script {
+            let module =
+                Module::from_str(script).map_err(|error| CardDefinitionError::InvalidScript {
+                    id: self.id.clone(),
+                    error,
+                })?;
+            EffectFactory::Script(Arc::new(EffectDef::new(module)))
+        } else {
+            CardArchetype::default().effect
+        };
+        Ok(CardArchetype {
+            id: ArchetypeId::new(&self.id),
+            name: self.name.clone(),
+            safe_name,
+            attribute: self.attribute.clone(),
+            effect,
+        })
+    }
+}
+
+impl FromStr for CardDefinition {
+    type Err = CardDefinitionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(|err| CardDefinitionError::InvalidFormat {
+            path: PathBuf::new(),
+            message: err.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum CardDefinitionError {
+    #[error("Failed to read {path:?}: {message}")]
+    Io { path: PathBuf, message: String },
+    #[error("Invalid card definition {path:?}: {message}")]
+    InvalidFormat { path: PathBuf, message: String },
+    #[error("Invalid archetype id: {id}")]
+    InvalidId { id: String },
+    #[error("Invalid card name: {name}")]
+    InvalidName { name: String },
+    #[error("Invalid script in {id}: {error}")]
+    InvalidScript {
+        id: String,
+        error: crate::dsl::script::error::Error,
+    },
+    #[error("Duplicate archetype id: {id}")]
+    DuplicateId { id: ArchetypeId },
+    #[error("Duplicate card name: {name}")]
+    DuplicateName { name: String },
+    #[error("Duplicate card set: {code}")]
+    DuplicateSet { code: String },
+    #[error("{id} in {set} collides with a card in {other}")]
+    Collision {
+        id: String,
+        set: String,
+        other: String,
+    },
+}
+
+impl CardDefinitionError {
+    fn with_path(self, path: &Path) -> Self {
+        match self {
+            Self::InvalidFormat { message, .. } => Self::InvalidFormat {
+                path: path.to_path_buf(),
+                message,
+            },
+            err => err,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{archetype::CardType, color::Color, event::EventFilter};
+
+    #[test]
+    fn test_card_definition() {
+        let def = CardDefinition::from_str(
+            r#"{
+                "id": "prot",
+                "name": "Prototype Newt",
+                "color": "green",
+                "cost": 2,
+                "card_type": "creature",
+                "creature_type": "mutant",
+                "power": 300,
+                "abilities": ["toxic"],
+                "script": "def on_casted($event): [];"
+            }"#,
+        )
+        .unwrap();
+        let archetype = def.build().unwrap();
+        assert_eq!(archetype.id, ArchetypeId::new("prot"));
+        assert_eq!(archetype.safe_name, "prototype-newt");
+        assert_eq!(archetype.attribute.color, Color::GREEN);
+        assert_eq!(archetype.attribute.card_type, CardType::Creature);
+        assert_eq!(archetype.attribute.power, Some(300));
+        assert_eq!(archetype.attribute.shards, 0);
+        assert_eq!(
+            archetype.effect.create().event_filter(),
+            EventFilter::CASTED
+        );
+
+        let def = CardDefinition::from_str(r#"{ "id": "prot", "name": "Newt", "script": "def" }"#)
+            .unwrap();
+        assert!(matches!(
+            def.build(),
+            Err(CardDefinitionError::InvalidScript { .. })
+        ));
+    }
+}
@@ -1,8 +1,14 @@
+use crate::action::Action;
+use crate::computed::ComputedAttributeModifier;
 use crate::dsl::script::exp::Module;
 use crate::dsl::script::value::Value;
 use crate::dsl::SmallStr;
-use crate::effect::{Effect, EffectActivateContext};
+use crate::effect::{
+    ContinuousCardEffectContext, Effect, EffectActivateContext, EffectId, EffectReport,
+    EffectTriggerContext,
+};
 use crate::event::{CardEvent, EventFilter};
+use serde::Deserialize;
 
 #[derive(Debug, Clone)]
 pub struct EffectDef {
@@ -10,41 +16,36 @@ pub struct EffectDef {
     module: Module,
 }
 
+/// Script functions called when a card event occurs, with the event they listen to.
+pub const EVENT_HANDLERS: &[(&str, EventFilter)] = &[
+    ("on_casted", EventFilter::CASTED),
+    ("on_destroyed", EventFilter::DESTROYED),
+    ("on_returned_to_hand", EventFilter::RETURNED_TO_HAND),
+    ("on_returned_to_deck", EventFilter::RETURNED_TO_DECK),
+    ("on_dealt_damage", EventFilter::DEALT_DAMAGE),
+    ("on_attacking", EventFilter::ATTACKING),
+    ("on_blocking", EventFilter::BLOCKING),
+    ("on_attacked", EventFilter::ATTACKED),
+    ("on_any_casted", EventFilter::ANY_CASTED),
+];
+
 impl EffectDef {
     pub fn new(module: Module) -> Self {
         let mut event_filter = EventFilter::empty();
-        if module.has_def("on_casted", 1) {
-            event_filter |= EventFilter::CASTED;
-        }
-        if module.has_def("on_destroyed", 1) {
-            event_filter |= EventFilter::DESTROYED;
-        }
-        if module.has_def("on_returned_to_hand", 1) {
-            event_filter |= EventFilter::RETURNED_TO_HAND;
-        }
-        if module.has_def("on_returned_to_deck", 1) {
-            event_filter |= EventFilter::RETURNED_TO_DECK;
-        }
-        if module.has_def("on_dealt_damage", 1) {
-            event_filter |= EventFilter::DEALT_DAMAGE;
-        }
-        if module.has_def("on_attacking", 1) {
-            event_filter |= EventFilter::ATTACKING;
-        }
-        if module.has_def("on_blocking", 1) {
-            event_filter |= EventFilter::BLOCKING;
-        }
-        if module.has_def("on_attacked", 1) {
-            event_filter |= EventFilter::ATTACKED;
-        }
-        if module.has_def("on_any_casted", 1) {
-            event_filter |= EventFilter::ANY_CASTED;
+        for (name, filter) in EVENT_HANDLERS {
+            if module.has_def(name, 1) {
+                event_filter |= *filter;
+            }
         }
         Self {
             event_filter,
             module,
         }
     }
+
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
 }
 
 impl Effect for EffectDef {
@@ -82,4 +83,50 @@ impl Effect for EffectDef {
         let _: serde_json::Value = self.module.call(ctx, "trigger", vec![id])?;
         Ok(())
     }
+
+    fn resolve(
+        &mut self,
+        id: EffectId,
+        args: &[Value],
+        ctx: &mut EffectTriggerContext,
+        action: Option<Action>,
+    ) -> anyhow::Result<EffectReport> {
+        let event: Value = if let Some(action) = action {
+            action.into()
+        } else {
+            Default::default()
+        };
+        let mut new_args: Vec<Value> = vec![id.to_string().into(), event];
+        new_args.extend(args.iter().cloned());
+        let report: Option<EffectReport> = self.module.call(ctx, "stack", new_args)?;
+        Ok(report.unwrap_or_default())
+    }
+
+    fn apply_card(
+        &self,
+        _id: EffectId,
+        args: &[Value],
+        ctx: &mut ContinuousCardEffectContext,
+    ) -> anyhow::Result<bool> {
+        let modifier: Option<ModifierOrBool> =
+            self.module.call(ctx, "continuous", args.to_vec())?;
+        if let Some(modifier) = modifier {
+            match modifier {
+                ModifierOrBool::Modifier(modifier) => {
+                    ctx.computed.apply_modifier(modifier);
+                }
+                ModifierOrBool::Bool(value) => {
+                    return Ok(value);
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum ModifierOrBool {
+    Bool(bool),
+    Modifier(ComputedAttributeModifier),
 }
@@ -1,7 +1,9 @@
 use crate::{
     ability::{AnonymousAbility, KeywordAbility},
+    card::CardScore,
     color::Color,
     effect::{Effect, NoEffect},
+    score::Score,
 };
 use bincode::{
     de::{BorrowDecoder, Decoder},
@@ -10,11 +12,15 @@ use bincode::{
     BorrowDecode, Decode, Encode,
 };
 use core::fmt;
+use effect::EffectDef;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use strum::Display;
 use tinystr::TinyAsciiStr;
 
+pub mod definition;
 pub mod effect;
+mod search;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -75,7 +81,7 @@ pub struct CardArchetype {
     pub name: String,
     pub safe_name: String,
     pub attribute: CardAttribute,
-    pub effect: fn() -> Box<dyn Effect>,
+    pub effect: EffectFactory,
 }
 
 impl PartialOrd for CardArchetype {
@@ -99,16 +105,64 @@ impl Default for CardArchetype {
             name: String::new(),
             safe_name: String::new(),
             attribute: CardAttribute::default(),
-            effect: no_effect(),
+            effect: EffectFactory::Native(NoEffect::NEW),
         }
     }
 }
 
-fn no_effect() -> fn() -> Box<dyn Effect> {
-    NoEffect::NEW
+impl Score for CardArchetype {
+    type Output = CardScore;
+
+    fn score(&self) -> CardScore {
+        let power = self.attribute.power.unwrap_or(0) as i32;
+        let abilities = self
+            .attribute
+            .abilities
+            .iter()
+            .map(KeywordAbility::score)
+            .sum::<i32>()
+            + self
+                .attribute
+                .anon_abilities
+                .iter()
+                .map(AnonymousAbility::score)
+                .sum::<i32>();
+        CardScore { power, abilities }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Creates the effect instance attached to each card of an archetype.
+#[derive(Debug, Clone)]
+pub enum EffectFactory {
+    /// An effect implemented in Rust.
+    Native(fn() -> Box<dyn Effect>),
+    /// An effect backed by a script module loaded at runtime.
+    Script(Arc<EffectDef>),
+}
+
+impl EffectFactory {
+    pub fn create(&self) -> Box<dyn Effect> {
+        match self {
+            Self::Native(new) => new(),
+            Self::Script(def) => Box::new((**def).clone()),
+        }
+    }
+}
+
+impl PartialEq for EffectFactory {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Native(lhs), Self::Native(rhs)) => std::ptr::fn_addr_eq(*lhs, *rhs),
+            (Self::Script(lhs), Self::Script(rhs)) => Arc::ptr_eq(lhs, rhs),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for EffectFactory {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+#[serde(default)]
 pub struct CardAttribute {
     pub color: Color,
     pub cost: u8,
@@ -118,6 +172,7 @@ pub struct CardAttribute {
     pub anon_abilities: Vec<AnonymousAbility>,
     pub power: Option<u32>,
     pub shards: u8,
+    pub rarity: Rarity,
     pub is_token: bool,
     pub styles: Vec<CardStyle>,
 }
@@ -133,6 +188,7 @@ impl Default for CardAttribute {
             anon_abilities: Vec::new(),
             power: None,
             shards: 0,
+            rarity: Rarity::Common,
             is_token: false,
             styles: Vec::new(),
         }
@@ -176,6 +232,31 @@ pub enum CardType {
     Hex,
 }
 
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Display,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    Hash,
+    Encode,
+    Decode,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Rarity {
+    #[default]
+    Common,
+    Uncommon,
+    Rare,
+    Mythic,
+}
+
 #[derive(
     Debug,
     Clone,
@@ -200,7 +281,7 @@ pub enum CreatureType {
     Program,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
 pub struct CardStyle {
     pub artwork: u8,
     pub artist: Option<String>,
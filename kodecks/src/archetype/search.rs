@@ -0,0 +1,144 @@
+use super::CardArchetype;
+use crate::{color::Color, dsl::filter::Searchable};
+use std::str::FromStr;
+
+impl Searchable for CardArchetype {
+    fn matches_text(&self, text: &str) -> Option<u32> {
+        [self.name.to_ascii_lowercase(), self.id.to_string()]
+            .iter()
+            .filter_map(|name| {
+                if name == text {
+                    Some(3)
+                } else if name.starts_with(text) {
+                    Some(2)
+                } else if name.contains(text) {
+                    Some(1)
+                } else {
+                    None
+                }
+            })
+            .max()
+    }
+
+    fn matches_tag(&self, key: &str, value: &str) -> Option<u32> {
+        let attr = &self.attribute;
+        let matched = match key {
+            "color" => Color::from_str(value).ok()? == attr.color,
+            "type" => attr.card_type.to_string().eq_ignore_ascii_case(value),
+            "rarity" => attr.rarity.to_string().eq_ignore_ascii_case(value),
+            "creature" => attr
+                .creature_type
+                .is_some_and(|ty| ty.to_string().eq_ignore_ascii_case(value)),
+            "ability" => attr
+                .abilities
+                .iter()
+                .map(|ability| format!("{:?}", ability))
+                .chain(
+                    attr.anon_abilities
+                        .iter()
+                        .map(|ability| format!("{:?}", ability)),
+                )
+                .any(|ability| ability.eq_ignore_ascii_case(value)),
+            _ => false,
+        };
+        matched.then_some(1)
+    }
+
+    fn matches_cmp(&self, lhs: &str, op: &str, rhs: &str) -> Option<u32> {
+        let attr = &self.attribute;
+        let lhs = match lhs {
+            "cost" => attr.cost as u32,
+            "power" => attr.power?,
+            "shards" => attr.shards as u32,
+            _ => return None,
+        };
+        let rhs: u32 = rhs.parse().ok()?;
+        let matched = match op {
+            "=" => lhs == rhs,
+            ">" => lhs > rhs,
+            "<" => lhs < rhs,
+            ">=" => lhs >= rhs,
+            "<=" => lhs <= rhs,
+            _ => false,
+        };
+        matched.then_some(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ability::KeywordAbility,
+        archetype::{ArchetypeId, CardAttribute, CardType, CreatureType, Rarity},
+        dsl::filter::Filter,
+    };
+
+    fn archetype(id: &str, name: &str, attribute: CardAttribute) -> CardArchetype {
+        CardArchetype {
+            id: ArchetypeId::new(id),
+            name: name.to_string(),
+            safe_name: id.to_string(),
+            attribute,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_search_archetype() {
+        let cards = [
+            archetype(
+                "vmsh",
+                "Volcanic Wyrm",
+                CardAttribute {
+                    color: Color::RED,
+                    cost: 4,
+                    card_type: CardType::Creature,
+                    creature_type: Some(CreatureType::Mutant),
+                    power: Some(400),
+                    rarity: Rarity::Rare,
+                    ..Default::default()
+                },
+            ),
+            archetype(
+                "wyrm",
+                "Wyrm",
+                CardAttribute {
+                    color: Color::BLUE,
+                    cost: 2,
+                    card_type: CardType::Creature,
+                    creature_type: Some(CreatureType::Robot),
+                    abilities: vec![KeywordAbility::Stealth],
+                    power: Some(200),
+                    ..Default::default()
+                },
+            ),
+            archetype(
+                "hex",
+                "Wyrm Breath",
+                CardAttribute {
+                    color: Color::RED,
+                    cost: 1,
+                    ..Default::default()
+                },
+            ),
+        ];
+        let search = |query: &str| {
+            Filter::new(query)
+                .unwrap()
+                .search(cards.clone())
+                .map(|card| card.id.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(search("wyrm"), vec!["wyrm", "hex", "vmsh"]);
+        assert_eq!(search("color:red"), vec!["vmsh", "hex"]);
+        assert_eq!(search("type:creature power<300"), vec!["wyrm"]);
+        assert_eq!(
+            search("creature:mutant or ability:stealth"),
+            vec!["vmsh", "wyrm"]
+        );
+        assert_eq!(search("cost>=2 -color:blue"), vec!["vmsh"]);
+        assert_eq!(search("rarity:rare"), vec!["vmsh"]);
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{
+    archetype::{ArchetypeId, Rarity},
+    catalog::Catalog,
+    pool::CardPool,
+};
+use bincode::{Decode, Encode};
+use rand::{rngs::SmallRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A group of cards in a booster pack.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash)]
+pub struct BoosterSlot {
+    pub count: u8,
+    /// The rarities a card of the slot can have, with their weights.
+    pub rarities: Vec<(Rarity, u16)>,
+}
+
+impl BoosterSlot {
+    pub fn new(count: u8, rarity: Rarity) -> Self {
+        Self {
+            count,
+            rarities: vec![(rarity, 1)],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash)]
+pub struct BoosterLayout {
+    pub slots: Vec<BoosterSlot>,
+}
+
+impl BoosterLayout {
+    pub fn size(&self) -> usize {
+        self.slots.iter().map(|slot| slot.count as usize).sum()
+    }
+}
+
+impl Default for BoosterLayout {
+    fn default() -> Self {
+        Self {
+            slots: vec![
+                BoosterSlot::new(6, Rarity::Common),
+                BoosterSlot::new(3, Rarity::Uncommon),
+                BoosterSlot {
+                    count: 1,
+                    rarities: vec![(Rarity::Rare, 7), (Rarity::Mythic, 1)],
+                },
+            ],
+        }
+    }
+}
+
+/// Opens booster packs. The same seed always gives the same packs.
+#[derive(Debug, Clone)]
+pub struct BoosterGenerator {
+    layout: BoosterLayout,
+    cards: BTreeMap<Rarity, Vec<ArchetypeId>>,
+    all: Vec<ArchetypeId>,
+    rng: SmallRng,
+}
+
+impl BoosterGenerator {
+    /// Creates a generator for the non-token cards of the catalog,
+    /// or of the set with the code if `set` is given.
+    pub fn new(catalog: &Catalog, set: Option<&str>, layout: BoosterLayout, seed: u64) -> Self {
+        let mut cards: BTreeMap<Rarity, Vec<ArchetypeId>> = BTreeMap::new();
+        let mut all = vec![];
+        let archetypes = catalog
+            .sets
+            .iter()
+            .filter(|card_set| set.is_none_or(|code| card_set.code() == code))
+            .flat_map(|card_set| card_set.iter())
+            .filter(|archetype| !archetype.attribute.is_token);
+        for archetype in archetypes {
+            cards
+                .entry(archetype.attribute.rarity)
+                .or_default()
+                .push(archetype.id);
+            all.push(archetype.id);
+        }
+        Self {
+            layout,
+            cards,
+            all,
+            rng: SmallRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Opens a pack following the layout.
+    ///
+    /// A slot whose rarities have no cards is filled from all cards.
+    /// A pack does not contain the same card twice unless there are not enough cards.
+    pub fn open(&mut self) -> Vec<ArchetypeId> {
+        let mut pack = vec![];
+        for slot in &self.layout.slots {
+            let rarities = slot
+                .rarities
+                .iter()
+                .filter(|(rarity, _)| self.cards.contains_key(rarity))
+                .collect::<Vec<_>>();
+            for _ in 0..slot.count {
+                let candidates = match rarities.choose_weighted(&mut self.rng, |slot| slot.1) {
+                    Ok((rarity, _)) => &self.cards[rarity],
+                    Err(_) => &self.all,
+                };
+                let fresh = candidates
+                    .iter()
+                    .filter(|card| !pack.contains(*card))
+                    .collect::<Vec<_>>();
+                let card = fresh
+                    .choose(&mut self.rng)
+                    .copied()
+                    .or_else(|| candidates.choose(&mut self.rng));
+                if let Some(card) = card {
+                    pack.push(*card);
+                }
+            }
+        }
+        pack
+    }
+}
+
+/// Settings of a sealed game, where each player builds a deck from the packs they open.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash)]
+pub struct SealedConfig {
+    pub seed: u64,
+    /// The number of packs each player opens.
+    pub packs: u8,
+    #[serde(default)]
+    pub layout: BoosterLayout,
+    /// Opens packs of the set with the code instead of the whole catalog.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub set: Option<String>,
+}
+
+impl SealedConfig {
+    /// Returns the cards opened by each player. The players open their packs in order.
+    pub fn open(&self, catalog: &Catalog, players: usize) -> Vec<Vec<ArchetypeId>> {
+        let mut generator =
+            BoosterGenerator::new(catalog, self.set.as_deref(), self.layout.clone(), self.seed);
+        (0..players)
+            .map(|_| (0..self.packs).flat_map(|_| generator.open()).collect())
+            .collect()
+    }
+
+    /// Returns the pool of each player, made from the cards they opened.
+    pub fn card_pools(&self, catalog: &Catalog, players: usize) -> Vec<CardPool> {
+        self.open(catalog, players)
+            .into_iter()
+            .map(CardPool::from_cards)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        archetype::{CardArchetype, CardAttribute},
+        card::CardEntry,
+        deck::{DeckItem, DeckList},
+        error::DeckViolation,
+    };
+    use std::sync::LazyLock;
+
+    static CARDS: LazyLock<Vec<CardArchetype>> = LazyLock::new(|| {
+        let rarities = [
+            Rarity::Common,
+            Rarity::Common,
+            Rarity::Common,
+            Rarity::Common,
+            Rarity::Uncommon,
+            Rarity::Uncommon,
+            Rarity::Rare,
+        ];
+        rarities
+            .iter()
+            .enumerate()
+            .map(|(i, rarity)| CardArchetype {
+                id: ArchetypeId::new(&format!("c{i}")),
+                name: format!("Card {i}"),
+                safe_name: format!("card-{i}"),
+                attribute: CardAttribute {
+                    rarity: *rarity,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .collect()
+    });
+
+    #[test]
+    fn test_sealed() {
+        let catalog = Catalog::new(&[
+            || &CARDS[0],
+            || &CARDS[1],
+            || &CARDS[2],
+            || &CARDS[3],
+            || &CARDS[4],
+            || &CARDS[5],
+            || &CARDS[6],
+        ]);
+        let rarity = |card: &ArchetypeId| catalog[*card].attribute.rarity;
+
+        let layout = BoosterLayout::default();
+        let pack = BoosterGenerator::new(&catalog, None, layout.clone(), 1).open();
+        assert_eq!(pack.len(), layout.size());
+        assert_eq!(
+            pack.iter().map(rarity).collect::<Vec<_>>(),
+            [
+                [Rarity::Common; 6].as_slice(),
+                &[Rarity::Uncommon; 3],
+                &[Rarity::Rare],
+            ]
+            .concat()
+        );
+        // There are only 4 commons, so the first 4 are all different.
+        for card in &pack[..4] {
+            assert_eq!(pack[..4].iter().filter(|other| *other == card).count(), 1);
+        }
+
+        let config = SealedConfig {
+            seed: 7,
+            packs: 2,
+            layout,
+            set: None,
+        };
+        let opened = config.open(&catalog, 2);
+        assert_eq!(opened, config.open(&catalog, 2));
+        assert_eq!(opened[0].len(), 20);
+
+        let pools = config.card_pools(&catalog, 2);
+        let rare = ArchetypeId::new("c6");
+        let count = opened[0].iter().filter(|card| **card == rare).count() as u32;
+        let deck = DeckList {
+            cards: (0..=count)
+                .map(|_| DeckItem {
+                    card: CardEntry {
+                        archetype_id: rare,
+                        style: 0,
+                    },
+                    base_id: None,
+                })
+                .collect(),
+            ..Default::default()
+        };
+        assert_eq!(
+            pools[0].verify(&deck, &catalog),
+            vec![DeckViolation::CardLimited {
+                card: rare,
+                count: count + 1,
+                max: count as u8,
+            }]
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     ability::{AnonymousAbility, KeywordAbility},
     archetype::{ArchetypeId, CardArchetype},
+    catalog::Catalog,
     color::Color,
     computed::{ComputedAttribute, ComputedFlags},
     deck::DeckItem,
@@ -13,7 +14,12 @@ use crate::{
     score::Score,
     zone::ZoneKind,
 };
-use bincode::{Decode, Encode};
+use bincode::{
+    de::{BorrowDecoder, Decoder},
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    BorrowDecode, Decode, Encode,
+};
 use core::fmt;
 use num::Zero;
 use serde::{Deserialize, Serialize};
@@ -50,7 +56,7 @@ impl Card {
         style: u8,
         owner: u8,
     ) -> Self {
-        let effect = (archetype.effect)();
+        let effect = archetype.effect.create();
         let computed = (&*archetype).into();
         Self {
             id: counter.allocate(item.base_id),
@@ -71,7 +77,7 @@ impl Card {
     }
 
     pub fn new_token(id: ObjectId, archetype: Arc<CardArchetype>, owner: u8) -> Self {
-        let effect = (archetype.effect)();
+        let effect = archetype.effect.create();
         let computed = (&*archetype).into();
         Self {
             id,
@@ -164,6 +170,10 @@ impl Card {
         dyn_clone::clone_box(&**&self.effect)
     }
 
+    pub(crate) fn effect_ref(&self) -> &dyn Effect {
+        &*self.effect
+    }
+
     pub fn revealed(&self) -> PlayerMask {
         self.revealed
     }
@@ -220,6 +230,84 @@ impl Card {
         self.id = counter.allocate(Some(self.id));
         self.revealed.set_all(false);
     }
+
+    /// Replaces the archetype and the effect, keeping the id, the zone and the visibility.
+    pub(crate) fn replace_archetype(&mut self, archetype: Arc<CardArchetype>) {
+        let effect = archetype.effect.create();
+        self.event_filter = effect.event_filter();
+        self.effect = effect;
+        self.archetype = archetype;
+        self.reset_computed();
+    }
+
+    /// Replaces the archetype and the effect with the ones in the catalog.
+    ///
+    /// Deserialized cards only know their archetype id until this is called.
+    pub fn relink(&mut self, catalog: &Catalog) {
+        let archetype = catalog[self.archetype.id].clone();
+        let effect = archetype.effect.create();
+        self.event_filter = effect.event_filter();
+        self.effect = effect;
+        self.archetype = archetype;
+        self.set_computed(self.computed.clone());
+    }
+
+    fn from_snapshot(snapshot: CardSnapshot) -> Self {
+        let archetype = Arc::new(CardArchetype {
+            id: snapshot.archetype_id,
+            ..Default::default()
+        });
+        let effect = archetype.effect.create();
+        let computed = snapshot.computed.unwrap_or_else(|| (&*archetype).into());
+        Self {
+            id: snapshot.id,
+            owner: snapshot.owner,
+            zone: snapshot.zone,
+            archetype,
+            style: snapshot.style,
+            computed,
+            flags: ComputedFlags::empty(),
+            event_filter: effect.event_filter(),
+            effect,
+            revealed: snapshot.revealed,
+            timestamp: snapshot.timestamp,
+            field_state: snapshot.field_state,
+            battle_state: snapshot.battle_state,
+            is_token: snapshot.is_token,
+        }
+    }
+}
+
+impl Serialize for Card {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.snapshot().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Self::from_snapshot(CardSnapshot::deserialize(
+            deserializer,
+        )?))
+    }
+}
+
+impl Encode for Card {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        Encode::encode(&self.snapshot(), encoder)
+    }
+}
+
+impl Decode for Card {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self::from_snapshot(Decode::decode(decoder)?))
+    }
+}
+
+impl<'de> BorrowDecode<'de> for Card {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self::from_snapshot(Decode::decode(decoder)?))
+    }
 }
 
 impl Clone for Card {
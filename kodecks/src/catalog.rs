@@ -1,10 +1,24 @@
-use crate::archetype::{ArchetypeId, CardArchetype};
+use crate::{
+    archetype::{
+        definition::{CardDefinition, CardDefinitionError, CARD_FILE_EXTENSION},
+        ArchetypeId, CardArchetype, EffectFactory,
+    },
+    dsl::filter::{Filter, Searchable, SyntaxError},
+    env::Fnv1a,
+};
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    fs,
     ops::Index,
+    path::{Path, PathBuf},
     sync::{Arc, LazyLock},
 };
 
+const DEFAULT_SET_CODE: &str = "core";
+const DEFAULT_SET_NAME: &str = "Core Set";
+const SET_FILE_NAME: &str = "set.json";
+
 pub type CardList = [fn() -> &'static CardArchetype];
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -19,6 +33,89 @@ impl Catalog {
         }
     }
 
+    /// Combines card sets into a catalog, in release order.
+    pub fn from_sets<I>(sets: I) -> Result<Self, CardDefinitionError>
+    where
+        I: IntoIterator<Item = CardSet>,
+    {
+        let mut catalog = Self { sets: vec![] };
+        for set in sets {
+            catalog.add_set(set)?;
+        }
+        Ok(catalog)
+    }
+
+    /// Loads all card definition files in `dir` as a card set,
+    /// and each subdirectory containing card definitions as another set.
+    pub fn load_from_dir<P>(dir: P) -> Result<Self, CardDefinitionError>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        let mut subdirs = read_dir(dir)?
+            .into_iter()
+            .filter(|path| path.is_dir())
+            .collect::<Vec<_>>();
+        subdirs.sort();
+
+        let mut sets = vec![];
+        for subdir in subdirs {
+            let set = CardSet::load_from_dir(&subdir)?;
+            if set.iter().next().is_some() {
+                sets.push(set);
+            }
+        }
+        let set = CardSet::load_from_dir(dir)?;
+        if set.iter().next().is_some() || sets.is_empty() {
+            sets.push(set);
+        }
+        Self::from_sets(sets)
+    }
+
+    /// Adds a card set, keeping the sets in release order.
+    ///
+    /// Fails if the set code is already used,
+    /// or an id or a name of the new cards is already in another set.
+    pub fn add_set(&mut self, set: CardSet) -> Result<(), CardDefinitionError> {
+        if self.set(set.code()).is_some() {
+            return Err(CardDefinitionError::DuplicateSet {
+                code: set.code().to_string(),
+            });
+        }
+        let keys = set
+            .iter()
+            .flat_map(|archetype| [archetype.id.to_string(), archetype.safe_name.clone()]);
+        for key in keys {
+            if let Some(other) = self.sets.iter().find(|other| other.contains(&key)) {
+                return Err(CardDefinitionError::Collision {
+                    id: key,
+                    set: set.code().to_string(),
+                    other: other.code().to_string(),
+                });
+            }
+        }
+        let index = self
+            .sets
+            .partition_point(|other| other.info.release <= set.info.release);
+        self.sets.insert(index, set);
+        Ok(())
+    }
+
+    pub fn set<S>(&self, code: S) -> Option<&CardSet>
+    where
+        S: AsRef<str>,
+    {
+        self.sets.iter().find(|set| set.code() == code.as_ref())
+    }
+
+    /// Returns the set which contains the card.
+    pub fn set_of<S>(&self, id: S) -> Option<&CardSet>
+    where
+        S: AsRef<str>,
+    {
+        self.sets.iter().find(|set| set.contains(id.as_ref()))
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &Arc<CardArchetype>> {
         self.sets.iter().flat_map(|set| set.iter())
     }
@@ -37,6 +134,39 @@ impl Catalog {
         let id = id.as_ref();
         self.sets.iter().any(|set| set.contains(id))
     }
+
+    /// Returns archetypes matching a filter query, the most relevant first.
+    ///
+    /// In addition to the tags supported by `CardArchetype`,
+    /// `set:CODE` matches the cards of a card set.
+    pub fn search<'q>(&self, query: &'q str) -> Result<Vec<&Arc<CardArchetype>>, SyntaxError<'q>> {
+        let filter = Filter::new(query)?;
+        let entries = self.sets.iter().flat_map(|set| {
+            set.iter()
+                .map(move |archetype| SearchEntry { set, archetype })
+        });
+        Ok(filter
+            .search(entries)
+            .map(|entry| entry.archetype)
+            .collect())
+    }
+
+    /// Returns a fingerprint of all archetypes, their attributes and their effect scripts.
+    ///
+    /// Native effects are compiled into the program and are not part of the fingerprint,
+    /// so a change to one of them must come with a change to the archetype.
+    /// The attributes are hashed in their bincode encoding, which only changes with the data.
+    pub fn version(&self) -> u64 {
+        let mut hasher = Fnv1a::new();
+        for archetype in self.iter() {
+            let script = match &archetype.effect {
+                EffectFactory::Script(def) => def.module().source(),
+                EffectFactory::Native(_) => "",
+            };
+            hasher.encode(&(archetype.id, &archetype.attribute, script));
+        }
+        hasher.finish()
+    }
 }
 
 impl Index<&str> for Catalog {
@@ -57,18 +187,140 @@ impl Index<ArchetypeId> for Catalog {
     }
 }
 
+struct SearchEntry<'a> {
+    set: &'a CardSet,
+    archetype: &'a Arc<CardArchetype>,
+}
+
+impl Searchable for SearchEntry<'_> {
+    fn matches_text(&self, text: &str) -> Option<u32> {
+        self.archetype.matches_text(text)
+    }
+
+    fn matches_tag(&self, key: &str, value: &str) -> Option<u32> {
+        if key == "set" {
+            self.set.code().eq_ignore_ascii_case(value).then_some(1)
+        } else {
+            self.archetype.matches_tag(key, value)
+        }
+    }
+
+    fn matches_cmp(&self, lhs: &str, op: &str, rhs: &str) -> Option<u32> {
+        self.archetype.matches_cmp(lhs, op, rhs)
+    }
+}
+
+fn read_dir(dir: &Path) -> Result<Vec<PathBuf>, CardDefinitionError> {
+    let entries = fs::read_dir(dir).map_err(|err| CardDefinitionError::Io {
+        path: dir.to_path_buf(),
+        message: err.to_string(),
+    })?;
+    Ok(entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect())
+}
+
+/// Metadata of a card set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SetInfo {
+    pub code: String,
+    pub name: String,
+    /// The position of the set in the release order, starting from 0 for the core set.
+    pub release: u32,
+}
+
+impl Default for SetInfo {
+    fn default() -> Self {
+        Self {
+            code: DEFAULT_SET_CODE.to_string(),
+            name: DEFAULT_SET_NAME.to_string(),
+            release: 0,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CardSet {
+    info: SetInfo,
     map: HashMap<String, usize>,
     list: Vec<Arc<CardArchetype>>,
 }
 
 impl CardSet {
     pub fn new(cards: &CardList) -> Self {
-        let mut list = cards
-            .iter()
-            .map(|archetype| Arc::new(archetype().clone()))
+        Self::from_archetypes(
+            cards
+                .iter()
+                .map(|archetype| Arc::new(archetype().clone()))
+                .collect(),
+        )
+    }
+
+    /// Builds a card set from card definition files.
+    pub fn from_files<I, P>(paths: I) -> Result<Self, CardDefinitionError>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut ids = HashSet::new();
+        let mut names = HashSet::new();
+        let mut list = vec![];
+        for path in paths {
+            let archetype = CardDefinition::load(path)?.build()?;
+            if !ids.insert(archetype.id) {
+                return Err(CardDefinitionError::DuplicateId { id: archetype.id });
+            }
+            if !names.insert(archetype.safe_name.clone()) {
+                return Err(CardDefinitionError::DuplicateName {
+                    name: archetype.safe_name,
+                });
+            }
+            list.push(Arc::new(archetype));
+        }
+        Ok(Self::from_archetypes(list))
+    }
+
+    /// Loads all card definition files in `dir`.
+    ///
+    /// The metadata is read from `set.json` in `dir` if it exists.
+    /// Otherwise, the name of the directory is used as the set code.
+    pub fn load_from_dir<P>(dir: P) -> Result<Self, CardDefinitionError>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        let mut paths = read_dir(dir)?
+            .into_iter()
+            .filter(|path| {
+                path.is_file()
+                    && path.extension().and_then(|ext| ext.to_str()) == Some(CARD_FILE_EXTENSION)
+                    && path.file_name().and_then(|name| name.to_str()) != Some(SET_FILE_NAME)
+            })
             .collect::<Vec<_>>();
+        paths.sort();
+        let set = Self::from_files(paths)?;
+
+        let info_path = dir.join(SET_FILE_NAME);
+        if info_path.is_file() {
+            let data = fs::read_to_string(&info_path).map_err(|err| CardDefinitionError::Io {
+                path: info_path.clone(),
+                message: err.to_string(),
+            })?;
+            let info =
+                serde_json::from_str(&data).map_err(|err| CardDefinitionError::InvalidFormat {
+                    path: info_path,
+                    message: err.to_string(),
+                })?;
+            Ok(set.with_info(info))
+        } else if let Some(code) = dir.file_name().and_then(|name| name.to_str()) {
+            Ok(set.with_code(code))
+        } else {
+            Ok(set)
+        }
+    }
+
+    pub(crate) fn from_archetypes(mut list: Vec<Arc<CardArchetype>>) -> Self {
         list.sort();
         let map = list
             .iter()
@@ -80,7 +332,32 @@ impl CardSet {
                 ]
             })
             .collect();
-        Self { map, list }
+        Self {
+            info: SetInfo::default(),
+            map,
+            list,
+        }
+    }
+
+    pub fn with_code<S>(mut self, code: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.info.code = code.into();
+        self
+    }
+
+    pub fn with_info(mut self, info: SetInfo) -> Self {
+        self.info = info;
+        self
+    }
+
+    pub fn info(&self) -> &SetInfo {
+        &self.info
+    }
+
+    pub fn code(&self) -> &str {
+        &self.info.code
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &Arc<CardArchetype>> {
@@ -101,3 +378,149 @@ impl CardSet {
         self.map.contains_key(id.as_ref())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archetype::CardAttribute;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_catalog_search() {
+        static CARDS: LazyLock<Vec<CardArchetype>> = LazyLock::new(|| {
+            ["alpha", "beta", "gamma"]
+                .iter()
+                .zip(1..)
+                .map(|(name, cost)| CardArchetype {
+                    id: ArchetypeId::new(&name[..4]),
+                    name: name.to_string(),
+                    safe_name: name.to_string(),
+                    attribute: CardAttribute {
+                        cost,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .collect()
+        });
+        let mut catalog = Catalog::new(&[|| &CARDS[0], || &CARDS[1]]);
+        catalog
+            .sets
+            .push(CardSet::new(&[|| &CARDS[2]]).with_code("ext"));
+
+        let ids = |query: &str| {
+            catalog
+                .search(query)
+                .unwrap()
+                .iter()
+                .map(|archetype| archetype.id.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("cost>=2"), vec!["beta", "gamm"]);
+        assert_eq!(ids("set:ext or alph"), vec!["alph", "gamm"]);
+        assert_eq!(ids("set:core -a"), Vec::<String>::new());
+        assert!(catalog.search("cost>=").is_err());
+    }
+
+    #[test]
+    fn test_catalog_sets() {
+        static CARDS: LazyLock<Vec<CardArchetype>> = LazyLock::new(|| {
+            ["alpha", "beta", "alpha"]
+                .iter()
+                .map(|name| CardArchetype {
+                    id: ArchetypeId::new(&name[..4]),
+                    name: name.to_string(),
+                    safe_name: name.to_string(),
+                    ..Default::default()
+                })
+                .collect()
+        });
+        let ext = CardSet::new(&[|| &CARDS[1]]).with_info(SetInfo {
+            code: "ext".to_string(),
+            name: "Expansion".to_string(),
+            release: 1,
+        });
+        let catalog = Catalog::from_sets([ext.clone(), CardSet::new(&[|| &CARDS[0]])]).unwrap();
+        assert_eq!(
+            catalog
+                .sets
+                .iter()
+                .map(|set| set.code())
+                .collect::<Vec<_>>(),
+            vec!["core", "ext"]
+        );
+        assert_eq!(catalog.set_of("beta").map(|set| set.code()), Some("ext"));
+        assert_eq!(catalog.set("ext").unwrap().info().name, "Expansion");
+
+        let mut catalog = catalog;
+        assert_eq!(
+            catalog.add_set(ext),
+            Err(CardDefinitionError::DuplicateSet {
+                code: "ext".to_string()
+            })
+        );
+        assert_eq!(
+            catalog.add_set(CardSet::new(&[|| &CARDS[2]]).with_code("promo")),
+            Err(CardDefinitionError::Collision {
+                id: "alph".to_string(),
+                set: "promo".to_string(),
+                other: "core".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_card_set_from_files() {
+        let dir = std::env::temp_dir().join(format!("kodecks-set-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let write = |file: &str, id: &str, name: &str| {
+            let path = dir.join(file);
+            fs::write(
+                &path,
+                format!(r#"{{ "id": "{id}", "name": "{name}", "power": 100 }}"#),
+            )
+            .unwrap();
+            path
+        };
+        let newt = write("newt.json", "newt", "Newt");
+        let toad = write("toad.json", "toad", "Toad");
+        let copy = write("copy.json", "copy", "Newt");
+
+        let set = CardSet::from_files([&newt, &toad]).unwrap();
+        assert!(set.get("newt").is_some() && set.get("toad").is_some());
+        assert_eq!(
+            CardSet::from_files([&newt, &newt]),
+            Err(CardDefinitionError::DuplicateId {
+                id: ArchetypeId::new("newt")
+            })
+        );
+        assert_eq!(
+            CardSet::from_files([&newt, &copy]),
+            Err(CardDefinitionError::DuplicateName {
+                name: "newt".to_string()
+            })
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_catalog_version() {
+        let catalog = |script: &str| {
+            let def = CardDefinition::from_str(&format!(
+                r#"{{ "id": "newt", "name": "Newt", "power": 100, "script": "{script}" }}"#
+            ))
+            .unwrap();
+            let set = CardSet::from_archetypes(vec![Arc::new(def.build().unwrap())]);
+            Catalog::from_sets([set]).unwrap()
+        };
+        let base = catalog("def on_casted($event): [];");
+        assert_eq!(
+            base.version(),
+            catalog("def on_casted($event): [];").version()
+        );
+        assert_ne!(
+            base.version(),
+            catalog("def on_destroyed($event): [];").version()
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Tournament-style time control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode)]
+pub struct TimeControl {
+    /// Total time available to each player.
+    pub time_bank: Duration,
+    /// Time added to the player's bank at the start of their turn.
+    pub increment: Duration,
+    /// Time a player can spend on each action without consuming the bank.
+    pub grace_period: Duration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct PlayerClock {
+    pub player: u8,
+    pub remaining: Duration,
+    pub running: bool,
+}
+
+/// Keeps track of the time banks of all players.
+///
+/// The clock does not read the system time by itself;
+/// callers pass the time elapsed since the player was asked to act.
+#[derive(Debug, Clone)]
+pub struct ChessClock {
+    control: TimeControl,
+    clocks: Vec<PlayerClock>,
+}
+
+impl ChessClock {
+    pub fn new<I>(control: TimeControl, players: I) -> Self
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        Self {
+            control,
+            clocks: players
+                .into_iter()
+                .map(|player| PlayerClock {
+                    player,
+                    remaining: control.time_bank,
+                    running: false,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the time the player can spend on the next action, including the grace period.
+    pub fn time_left(&self, player: u8) -> Duration {
+        self.get(player)
+            .map(|clock| clock.remaining + self.control.grace_period)
+            .unwrap_or_default()
+    }
+
+    /// Starts the clock of the player and stops all others.
+    pub fn start(&mut self, player: u8) {
+        for clock in &mut self.clocks {
+            clock.running = clock.player == player;
+        }
+    }
+
+    /// Charges the time spent beyond the grace period to the player's bank.
+    ///
+    /// Returns `false` if the player has run out of time.
+    pub fn consume(&mut self, player: u8, elapsed: Duration) -> bool {
+        let grace_period = self.control.grace_period;
+        if let Some(clock) = self.get_mut(player) {
+            let charged = elapsed.saturating_sub(grace_period);
+            let timeout = charged > clock.remaining;
+            clock.remaining = clock.remaining.saturating_sub(charged);
+            clock.running = false;
+            !timeout
+        } else {
+            true
+        }
+    }
+
+    pub fn add_increment(&mut self, player: u8) {
+        let increment = self.control.increment;
+        if let Some(clock) = self.get_mut(player) {
+            clock.remaining += increment;
+        }
+    }
+
+    /// Returns the clocks with the running one reduced by `elapsed`.
+    pub fn snapshot(&self, elapsed: Duration) -> Vec<PlayerClock> {
+        let charged = elapsed.saturating_sub(self.control.grace_period);
+        self.clocks
+            .iter()
+            .map(|clock| PlayerClock {
+                remaining: if clock.running {
+                    clock.remaining.saturating_sub(charged)
+                } else {
+                    clock.remaining
+                },
+                ..*clock
+            })
+            .collect()
+    }
+
+    fn get(&self, player: u8) -> Option<&PlayerClock> {
+        self.clocks.iter().find(|clock| clock.player == player)
+    }
+
+    fn get_mut(&mut self, player: u8) -> Option<&mut PlayerClock> {
+        self.clocks.iter_mut().find(|clock| clock.player == player)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chess_clock() {
+        let control = TimeControl {
+            time_bank: Duration::from_secs(60),
+            increment: Duration::from_secs(5),
+            grace_period: Duration::from_secs(10),
+        };
+        let mut clock = ChessClock::new(control, [0, 1]);
+        assert_eq!(clock.time_left(0), Duration::from_secs(70));
+
+        clock.start(0);
+        assert_eq!(
+            clock.snapshot(Duration::from_secs(30))[0].remaining,
+            Duration::from_secs(40)
+        );
+        assert!(clock.consume(0, Duration::from_secs(8)));
+        assert!(clock.consume(0, Duration::from_secs(30)));
+        assert_eq!(clock.time_left(0), Duration::from_secs(50));
+
+        clock.add_increment(0);
+        assert_eq!(clock.time_left(0), Duration::from_secs(55));
+        assert!(!clock.consume(0, Duration::from_secs(56)));
+        assert_eq!(clock.time_left(1), Duration::from_secs(70));
+    }
+}
@@ -1,69 +1,49 @@
 use crate::{
     ability::{AbilityList, PlayerAbility},
+    archetype::ArchetypeId,
     card::Card,
     computed::ComputedAttribute,
-    effect::ContinuousCardEffectContext,
+    dsl::script::value::Value,
+    effect::{ContinuousCardEffectContext, EffectId},
     env::GameState,
     id::ObjectId,
     target::Target,
     zone::ZoneKind,
 };
-use core::fmt;
-use dyn_clone::DynClone;
-use std::sync::Arc;
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
 use tracing::error;
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct ContinuousItem {
     source: ObjectId,
+    archetype: ArchetypeId,
     timestamp: u16,
-    func: Arc<Box<dyn ContinuousEffect>>,
+    id: EffectId,
+    args: Vec<Value>,
     target: Target,
     is_active: bool,
 }
 
-impl fmt::Debug for ContinuousItem {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("ContinuousEffect")
-            .field("source", &self.source)
-            .field("timestamp", &self.timestamp)
-            .finish()
-    }
-}
-
-pub trait ContinuousEffect: Send + Sync + DynClone {
-    fn apply_card(&mut self, _ctx: &mut ContinuousCardEffectContext) -> anyhow::Result<bool> {
-        Ok(true)
-    }
-
-    fn apply_player(
-        &mut self,
-        _state: &GameState,
-        _player: u8,
-        _abilities: &mut AbilityList<PlayerAbility>,
-    ) -> anyhow::Result<bool> {
-        Ok(true)
-    }
-}
-
-dyn_clone::clone_trait_object!(ContinuousEffect);
-
 impl ContinuousItem {
-    pub fn new<F>(source: &Card, effect: F, target: Target) -> Self
-    where
-        F: ContinuousEffect + 'static,
-    {
+    pub fn new(source: &Card, id: EffectId, args: Vec<Value>, target: Target) -> Self {
         Self {
             source: source.id(),
+            archetype: source.archetype().id,
             timestamp: source.timestamp(),
-            func: Arc::new(Box::new(effect)),
+            id,
+            args,
             target,
             is_active: true,
         }
     }
+
+    pub fn archetype(&self) -> ArchetypeId {
+        self.archetype
+    }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct ContinuousEffectList {
     effects: Vec<ContinuousItem>,
 }
@@ -84,6 +64,10 @@ impl ContinuousEffectList {
         self.effects.sort_by_key(|effect| effect.timestamp);
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &ContinuousItem> {
+        self.effects.iter()
+    }
+
     pub fn apply_card(&mut self, state: &GameState, card: &Card) -> ComputedAttribute {
         let mut computed = ComputedAttribute::from(&**card.archetype());
         for effect in self
@@ -108,7 +92,9 @@ impl ContinuousEffectList {
                         target: card,
                         computed: &mut computed,
                     };
-                    dyn_clone::arc_make_mut(&mut effect.func).apply_card(&mut ctx)
+                    source
+                        .effect_ref()
+                        .apply_card(effect.id, &effect.args, &mut ctx)
                 });
             match result {
                 Ok(true) => {}
@@ -126,11 +112,18 @@ impl ContinuousEffectList {
     pub fn apply_player(&mut self, state: &GameState, player: u8) -> AbilityList<PlayerAbility> {
         let mut abilities = AbilityList::new();
         for effect in self.effects.iter_mut().rev() {
-            let result = dyn_clone::arc_make_mut(&mut effect.func).apply_player(
-                state,
-                player,
-                &mut abilities,
-            );
+            let result = state
+                .find_card(effect.source)
+                .map_err(|err| err.into())
+                .and_then(|source| {
+                    source.effect_ref().apply_player(
+                        effect.id,
+                        &effect.args,
+                        state,
+                        player,
+                        &mut abilities,
+                    )
+                });
             match result {
                 Ok(true) => {}
                 Ok(false) => {
@@ -147,4 +140,10 @@ impl ContinuousEffectList {
     pub fn update(&mut self) {
         self.effects.retain(|effect| effect.is_active);
     }
+
+    /// Removes the effects originating from any of `sources`.
+    pub fn remove_sources(&mut self, sources: &[ObjectId]) {
+        self.effects
+            .retain(|effect| !sources.contains(&effect.source));
+    }
 }
@@ -0,0 +1,210 @@
+use super::DeckList;
+use crate::{archetype::CardType, catalog::Catalog, color::Color, regulation::Regulation};
+use std::collections::BTreeMap;
+
+/// Game parameters used to compute the draw odds of a deck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnalysisConfig {
+    pub initial_hand_size: u8,
+    /// Whether the player draws a card in their first turn, i.e. does not go first.
+    pub draw_first_turn: bool,
+    /// The number of turns to compute the odds for.
+    pub turns: u8,
+}
+
+impl AnalysisConfig {
+    pub fn new(regulation: &Regulation) -> Self {
+        Self {
+            initial_hand_size: regulation.initial_hand_size,
+            draw_first_turn: false,
+            turns: 6,
+        }
+    }
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        Self::new(&Regulation::STANDARD)
+    }
+}
+
+/// Statistics of a deck. Cards missing from the catalog are ignored.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeckAnalysis {
+    pub size: u32,
+    /// The number of cards of each cost.
+    pub cost_curve: BTreeMap<u8, u32>,
+    /// The number of cards of each color.
+    pub colors: BTreeMap<Color, u32>,
+    pub creatures: u32,
+    pub hexes: u32,
+    /// The number of creatures of each power.
+    pub power_curve: BTreeMap<u32, u32>,
+    /// The total shards generated by charging every card of the deck, by color.
+    pub shards: BTreeMap<Color, u32>,
+    /// The probability of having drawn a card of cost 0 by each turn, starting from turn 1.
+    ///
+    /// Other cards need shards of their color, which come from charging a card in hand
+    /// once per turn. Which card is charged is up to the player, so they are left out and
+    /// `shards` describes what charging yields instead.
+    pub free_cast_odds: Vec<f64>,
+}
+
+impl DeckAnalysis {
+    pub fn new(deck: &DeckList, catalog: &Catalog, config: &AnalysisConfig) -> Self {
+        let mut analysis = Self::default();
+        for item in &deck.cards {
+            let Some(archetype) = catalog.get(item.card.archetype_id) else {
+                continue;
+            };
+            let attr = &archetype.attribute;
+            analysis.size += 1;
+            *analysis.cost_curve.entry(attr.cost).or_default() += 1;
+            *analysis.colors.entry(attr.color).or_default() += 1;
+            *analysis.shards.entry(attr.color).or_default() += attr.shards as u32;
+            match attr.card_type {
+                CardType::Creature => analysis.creatures += 1,
+                CardType::Hex => analysis.hexes += 1,
+            }
+            if let Some(power) = attr.power {
+                *analysis.power_curve.entry(power).or_default() += 1;
+            }
+        }
+        let free = analysis.cost_curve.get(&0).copied().unwrap_or_default();
+        analysis.free_cast_odds = (1..=config.turns)
+            .map(|turn| free_cast_odds(analysis.size, free, config, turn))
+            .collect();
+        analysis
+    }
+
+    /// The expected number of shards generated by charging a random card of the deck.
+    pub fn expected_shards(&self) -> f64 {
+        if self.size == 0 {
+            return 0.0;
+        }
+        self.shards.values().sum::<u32>() as f64 / self.size as f64
+    }
+}
+
+/// Returns the probability of having drawn at least one of `free` cards of cost 0
+/// from a deck of `size` cards by the main phase of `turn`.
+fn free_cast_odds(size: u32, free: u32, config: &AnalysisConfig, turn: u8) -> f64 {
+    let draws = if config.draw_first_turn {
+        turn
+    } else {
+        turn - 1
+    };
+    let seen = (config.initial_hand_size as u32 + draws as u32).min(size);
+    1.0 - hypergeometric_none(size, free, seen)
+}
+
+/// Returns the probability of drawing none of `successes` cards
+/// when drawing `draws` cards from `population` cards without replacement.
+fn hypergeometric_none(population: u32, successes: u32, draws: u32) -> f64 {
+    (0..draws)
+        .map(|i| population.saturating_sub(successes + i) as f64 / (population - i) as f64)
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        archetype::{ArchetypeId, CardArchetype, CardAttribute},
+        card::CardEntry,
+        deck::DeckItem,
+    };
+    use std::{iter, sync::LazyLock};
+
+    fn creature() -> &'static CardArchetype {
+        static ARCHETYPE: LazyLock<CardArchetype> = LazyLock::new(|| CardArchetype {
+            id: ArchetypeId::new("crtr"),
+            name: "Creature".to_string(),
+            safe_name: "creature".to_string(),
+            attribute: CardAttribute {
+                color: Color::RED,
+                cost: 2,
+                card_type: CardType::Creature,
+                power: Some(200),
+                shards: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        &ARCHETYPE
+    }
+
+    fn hex() -> &'static CardArchetype {
+        static ARCHETYPE: LazyLock<CardArchetype> = LazyLock::new(|| CardArchetype {
+            id: ArchetypeId::new("hex"),
+            name: "Hex".to_string(),
+            safe_name: "hex".to_string(),
+            attribute: CardAttribute {
+                color: Color::BLUE,
+                cost: 0,
+                card_type: CardType::Hex,
+                shards: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        &ARCHETYPE
+    }
+
+    fn items(id: &str, count: usize) -> impl Iterator<Item = DeckItem> {
+        iter::repeat_n(
+            DeckItem {
+                card: CardEntry {
+                    archetype_id: ArchetypeId::new(id),
+                    style: 0,
+                },
+                base_id: None,
+            },
+            count,
+        )
+    }
+
+    #[test]
+    fn test_deck_analysis() {
+        let catalog = Catalog::new(&[creature, hex]);
+        let deck = DeckList {
+            cards: items("crtr", 16).chain(items("hex", 4)).collect(),
+            ..Default::default()
+        };
+        let config = AnalysisConfig {
+            initial_hand_size: 4,
+            draw_first_turn: false,
+            turns: 3,
+        };
+        let analysis = DeckAnalysis::new(&deck, &catalog, &config);
+
+        assert_eq!(analysis.size, 20);
+        assert_eq!(analysis.cost_curve, BTreeMap::from([(0, 4), (2, 16)]));
+        assert_eq!(
+            analysis.colors,
+            BTreeMap::from([(Color::RED, 16), (Color::BLUE, 4)])
+        );
+        assert_eq!((analysis.creatures, analysis.hexes), (16, 4));
+        assert_eq!(analysis.power_curve, BTreeMap::from([(200, 16)]));
+        assert_eq!(analysis.expected_shards(), 1.2);
+
+        // Turn 1: no hex of cost 0 in the 4 cards of the initial hand.
+        let none = (16.0 / 20.0) * (15.0 / 19.0) * (14.0 / 18.0) * (13.0 / 17.0);
+        assert!((analysis.free_cast_odds[0] - (1.0 - none)).abs() < 1e-9);
+        // Going first, one more card is drawn in each of the following turns.
+        let none = none * (12.0 / 16.0);
+        assert!((analysis.free_cast_odds[1] - (1.0 - none)).abs() < 1e-9);
+        let none = none * (11.0 / 15.0);
+        assert!((analysis.free_cast_odds[2] - (1.0 - none)).abs() < 1e-9);
+
+        let on_draw = DeckAnalysis::new(
+            &deck,
+            &catalog,
+            &AnalysisConfig {
+                draw_first_turn: true,
+                ..config
+            },
+        );
+        assert_eq!(on_draw.free_cast_odds[0], analysis.free_cast_odds[1]);
+    }
+}
@@ -0,0 +1,280 @@
+use crate::{
+    archetype::ArchetypeId,
+    card::{safe_name, CardEntry},
+    catalog::Catalog,
+    env::Fnv1a,
+    id::ObjectId,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::iter;
+use thiserror::Error;
+
+mod analysis;
+
+pub use analysis::*;
+
+/// The version of the binary format written by `DeckList::to_code`.
+const DECK_CODE_VERSION: u8 = 1;
+const DECK_CODE_CHECKSUM_LEN: usize = 4;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash)]
+pub struct DeckList {
+    pub id: String,
+    pub name: String,
+    pub cards: Vec<DeckItem>,
+}
+
+impl fmt::Display for DeckList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in &self.cards {
+            writeln!(f, "{}", item)?;
+        }
+        Ok(())
+    }
+}
+
+impl DeckList {
+    pub fn parse(s: &str, catalog: &Catalog) -> Option<Self> {
+        let mut name = String::new();
+        let mut cards = Vec::new();
+        for line in s.lines().map(|s| s.trim()) {
+            if line.starts_with('#') {
+                name = line.trim_start_matches('#').trim().to_string();
+            } else {
+                cards.extend(DeckItem::parse(line, catalog));
+            }
+        }
+        let id = nanoid::nanoid!();
+        Some(Self { id, name, cards })
+    }
+
+    /// Encodes the cards of the deck into a short string which can be shared.
+    ///
+    /// The code is the format version, followed by an entry per card and style
+    /// (id length, id, count and style), and a checksum, encoded in base64url.
+    /// The name and the id of the deck are not included.
+    pub fn to_code(&self) -> String {
+        let mut entries: Vec<(CardEntry, u8)> = vec![];
+        for item in &self.cards {
+            match entries
+                .iter_mut()
+                .find(|(card, count)| *card == item.card && *count < u8::MAX)
+            {
+                Some((_, count)) => *count += 1,
+                None => entries.push((item.card.clone(), 1)),
+            }
+        }
+
+        let mut bytes = vec![DECK_CODE_VERSION];
+        for (card, count) in entries {
+            let id = card.archetype_id.as_str().as_bytes();
+            bytes.push(id.len() as u8);
+            bytes.extend_from_slice(id);
+            bytes.push(count);
+            bytes.push(card.style);
+        }
+        bytes.extend_from_slice(&deck_code_checksum(&bytes));
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    /// Decodes a deck from a code created by `to_code`.
+    ///
+    /// All cards must exist in the catalog. A new id is assigned to the deck.
+    pub fn from_code(code: &str, catalog: &Catalog) -> Result<Self, DeckCodeError> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(code.trim())
+            .map_err(|_| DeckCodeError::InvalidEncoding)?;
+        if bytes.len() < 1 + DECK_CODE_CHECKSUM_LEN {
+            return Err(DeckCodeError::Malformed);
+        }
+        let (payload, checksum) = bytes.split_at(bytes.len() - DECK_CODE_CHECKSUM_LEN);
+        if checksum != deck_code_checksum(payload) {
+            return Err(DeckCodeError::ChecksumMismatch);
+        }
+        let (&version, mut rest) = payload.split_first().ok_or(DeckCodeError::Malformed)?;
+        if version != DECK_CODE_VERSION {
+            return Err(DeckCodeError::UnsupportedVersion { version });
+        }
+
+        let mut cards = vec![];
+        let mut unknown = vec![];
+        while let Some((&len, tail)) = rest.split_first() {
+            let len = len as usize;
+            if len == 0 || len > 8 || tail.len() < len + 2 {
+                return Err(DeckCodeError::Malformed);
+            }
+            let id = std::str::from_utf8(&tail[..len]).map_err(|_| DeckCodeError::Malformed)?;
+            let archetype_id = ArchetypeId::new(id);
+            let (count, style) = (tail[len], tail[len + 1]);
+            rest = &tail[len + 2..];
+
+            if !catalog.contains(archetype_id) {
+                if !unknown.contains(&archetype_id) {
+                    unknown.push(archetype_id);
+                }
+                continue;
+            }
+            cards.extend(iter::repeat_n(
+                DeckItem {
+                    card: CardEntry {
+                        archetype_id,
+                        style,
+                    },
+                    base_id: None,
+                },
+                count as usize,
+            ));
+        }
+        if !unknown.is_empty() {
+            return Err(DeckCodeError::UnknownCards { ids: unknown });
+        }
+
+        Ok(Self {
+            id: nanoid::nanoid!(),
+            name: String::new(),
+            cards,
+        })
+    }
+
+    /// Returns the number of copies of each card, in the order of first appearance.
+    pub fn count_cards(&self) -> Vec<(ArchetypeId, u32)> {
+        let mut counts: Vec<(ArchetypeId, u32)> = vec![];
+        for item in &self.cards {
+            let id = item.card.archetype_id;
+            match counts.iter_mut().find(|(card, _)| *card == id) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((id, 1)),
+            }
+        }
+        counts
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash)]
+pub struct DeckItem {
+    #[serde(flatten)]
+    pub card: CardEntry,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_id: Option<ObjectId>,
+}
+
+impl DeckItem {
+    pub fn parse(s: &str, catalog: &Catalog) -> Vec<Self> {
+        let (name, count) = match s.rsplit_once(' ') {
+            Some((name, count)) => {
+                if let Ok(count) = count.parse() {
+                    (name, count)
+                } else {
+                    (s, 1)
+                }
+            }
+            _ => (s, 1),
+        };
+
+        if name.is_empty() {
+            return vec![];
+        }
+        let name = safe_name(name).unwrap();
+        let card = &catalog[name.as_str()];
+        iter::repeat(Self {
+            card: CardEntry {
+                archetype_id: card.id,
+                style: 0,
+            },
+            base_id: None,
+        })
+        .take(count)
+        .collect()
+    }
+}
+
+fn deck_code_checksum(bytes: &[u8]) -> [u8; DECK_CODE_CHECKSUM_LEN] {
+    let hash = Fnv1a::new().write(bytes).finish();
+    (hash as u32).to_le_bytes()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DeckCodeError {
+    #[error("Invalid deck code encoding")]
+    InvalidEncoding,
+    #[error("Malformed deck code")]
+    Malformed,
+    #[error("Deck code checksum mismatch")]
+    ChecksumMismatch,
+    #[error("Unsupported deck code version: {version}")]
+    UnsupportedVersion { version: u8 },
+    #[error("Unknown cards: {}", ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(", "))]
+    UnknownCards { ids: Vec<ArchetypeId> },
+}
+
+impl fmt::Display for DeckItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.card.archetype_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::archetype::CardArchetype;
+    use std::sync::LazyLock;
+
+    fn archetype() -> &'static CardArchetype {
+        static ARCHETYPE: LazyLock<CardArchetype> = LazyLock::new(|| CardArchetype {
+            id: ArchetypeId::new("test"),
+            name: "Test".to_string(),
+            safe_name: "test".to_string(),
+            ..Default::default()
+        });
+        &ARCHETYPE
+    }
+
+    fn item(id: &str, style: u8) -> DeckItem {
+        DeckItem {
+            card: CardEntry {
+                archetype_id: ArchetypeId::new(id),
+                style,
+            },
+            base_id: None,
+        }
+    }
+
+    #[test]
+    fn test_deck_code() {
+        let catalog = Catalog::new(&[archetype]);
+        let deck = DeckList {
+            cards: vec![item("test", 0), item("test", 1), item("test", 0)],
+            ..Default::default()
+        };
+        let code = deck.to_code();
+        let decoded = DeckList::from_code(&code, &catalog).unwrap();
+        let mut cards = deck.cards.clone();
+        cards.sort_by_key(|item| item.card.style);
+        assert_eq!(decoded.cards, cards);
+
+        let mut bytes = URL_SAFE_NO_PAD.decode(&code).unwrap();
+        bytes[2] ^= 1;
+        assert_eq!(
+            DeckList::from_code(&URL_SAFE_NO_PAD.encode(bytes), &catalog),
+            Err(DeckCodeError::ChecksumMismatch)
+        );
+        assert_eq!(
+            DeckList::from_code("!", &catalog),
+            Err(DeckCodeError::InvalidEncoding)
+        );
+
+        let deck = DeckList {
+            cards: vec![item("abc", 0), item("test", 0), item("xyz", 2)],
+            ..Default::default()
+        };
+        assert_eq!(
+            DeckList::from_code(&deck.to_code(), &catalog),
+            Err(DeckCodeError::UnknownCards {
+                ids: vec![ArchetypeId::new("abc"), ArchetypeId::new("xyz")]
+            })
+        );
+    }
+}
@@ -244,7 +244,7 @@ impl Filter {
             .into_iter()
             .filter_map(|item| self.0.matches(&item).map(|score| (item, score)))
             .collect::<Vec<_>>();
-        results.sort_by_key(|(_, score)| *score);
+        results.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
         results.into_iter().map(|(item, _)| item)
     }
 }
@@ -374,4 +374,14 @@ mod tests {
             .collect::<Vec<_>>();
         assert_eq!(result, vec!["foo bar baz"]);
     }
+
+    #[test]
+    fn test_search_order() {
+        // Higher scores come first and ties keep the input order.
+        let result = Filter::new("foo or (foo bar)")
+            .unwrap()
+            .search(vec!["foo", "foo baz", "foo bar", "bar"])
+            .collect::<Vec<_>>();
+        assert_eq!(result, vec!["foo bar", "foo", "foo baz"]);
+    }
 }
@@ -0,0 +1,337 @@
+use super::exp::{Exp, Function, Module, Path};
+use crate::archetype::effect::EVENT_HANDLERS;
+use std::{collections::HashSet, ops::RangeInclusive};
+use thiserror::Error;
+
+const ANY: RangeInclusive<usize> = 0..=usize::MAX;
+
+/// Functions provided by the game environments, with the accepted numbers of arguments.
+const BUILTINS: &[(&str, RangeInclusive<usize>)] = &[
+    ("debug", ANY),
+    ("deck", 0..=1),
+    ("hand", 0..=1),
+    ("graveyard", 0..=1),
+    ("field", 0..=1),
+    ("opponent", 0..=1),
+    ("name", 0..=1),
+    ("zone", 0..=1),
+    ("controller", 0..=1),
+    ("owner", 0..=1),
+    ("color", 0..=1),
+    ("card_type", 0..=1),
+    ("creature_type", 0..=1),
+    ("cost", 0..=1),
+    ("power", 0..=1),
+    ("shards", 0..=1),
+    ("abilities", 0..=1),
+    ("anon_abilities", 0..=1),
+    ("is_token", 0..=1),
+    ("life", 0..=1),
+    ("push_stack", 1..=usize::MAX),
+    ("push_continuous", 2..=usize::MAX),
+    ("trigger_stack", 1..=1),
+    ("destroy", 1..=1),
+    ("return_to_hand", 1..=1),
+    ("shuffle_into_deck", 1..=1),
+    ("set_field_state", 2..=2),
+    ("inflict_damage", 2..=2),
+    ("generate_shards", 3..=3),
+    ("consume_shards", 3..=3),
+    ("generate_token", 2..=2),
+];
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum Diagnostic {
+    #[error("{function}: call to undefined function {name}/{arity}")]
+    UndefinedFunction {
+        function: String,
+        name: String,
+        arity: usize,
+    },
+    #[error("{function}: {name} does not take {arity} arguments")]
+    ArityMismatch {
+        function: String,
+        name: String,
+        arity: usize,
+    },
+    #[error("{function}: no card event matches this handler")]
+    UnknownEvent { function: String },
+    #[error("{function}: handler is never called")]
+    UnreachableHandler { function: String },
+    #[error("{function}: {name}/{arity} is called but not defined")]
+    MissingHandler {
+        function: String,
+        name: String,
+        arity: usize,
+    },
+}
+
+impl Module {
+    /// Checks the module for errors that would otherwise only show up during a game.
+    pub fn check(&self) -> Vec<Diagnostic> {
+        let functions = self.functions();
+        let defs = functions
+            .iter()
+            .map(|func| (func.name.as_str(), func.args.len()))
+            .collect::<HashSet<_>>();
+
+        let mut diagnostics = vec![];
+        let mut calls = vec![];
+        for func in &functions {
+            let id = function_id(func);
+            if func.name.starts_with("on_") {
+                match EVENT_HANDLERS.iter().find(|(name, _)| *name == func.name) {
+                    Some(_) if func.args.len() != 1 => {
+                        diagnostics.push(Diagnostic::ArityMismatch {
+                            function: id.clone(),
+                            name: func.name.clone(),
+                            arity: func.args.len(),
+                        });
+                    }
+                    None => {
+                        diagnostics.push(Diagnostic::UnknownEvent {
+                            function: id.clone(),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+
+            let filter_args = func
+                .args
+                .iter()
+                .filter(|arg| !arg.starts_with('$'))
+                .map(|arg| arg.as_str())
+                .collect::<HashSet<_>>();
+            visit(&func.body, &mut |exp| {
+                let Exp::CustomFunction(name, args) = exp else {
+                    return;
+                };
+                let arity = args.len();
+                calls.push((name.as_str(), arity));
+                if defs.contains(&(name.as_str(), arity))
+                    || (arity == 0 && filter_args.contains(name.as_str()))
+                {
+                    return;
+                }
+                let builtin = BUILTINS
+                    .iter()
+                    .find(|(builtin, _)| *builtin == name.as_str());
+                match builtin {
+                    Some((_, range)) if range.contains(&arity) => {}
+                    Some(_) => diagnostics.push(Diagnostic::ArityMismatch {
+                        function: id.clone(),
+                        name: name.clone(),
+                        arity,
+                    }),
+                    None if defs.iter().any(|(def, _)| *def == name.as_str()) => {
+                        diagnostics.push(Diagnostic::ArityMismatch {
+                            function: id.clone(),
+                            name: name.clone(),
+                            arity,
+                        })
+                    }
+                    None => diagnostics.push(Diagnostic::UndefinedFunction {
+                        function: id.clone(),
+                        name: name.clone(),
+                        arity,
+                    }),
+                }
+            });
+        }
+
+        // `push_stack(id; args...)` resolves to `stack(id; action; args...)`,
+        // and `push_continuous(id; target; args...)` to `continuous(id; args...)`.
+        let handlers = [
+            ("trigger_stack", "trigger", 0),
+            ("push_stack", "stack", 1),
+            ("push_continuous", "continuous", -1),
+        ];
+        for (caller, handler, offset) in handlers {
+            let called = calls
+                .iter()
+                .filter(|(name, _)| *name == caller)
+                .map(|(_, arity)| arity.saturating_add_signed(offset))
+                .collect::<HashSet<_>>();
+            for func in functions.iter().filter(|func| func.name == handler) {
+                if !called.contains(&func.args.len()) {
+                    diagnostics.push(Diagnostic::UnreachableHandler {
+                        function: function_id(func),
+                    });
+                }
+            }
+            let mut missing = called
+                .into_iter()
+                .filter(|arity| !defs.contains(&(handler, *arity)))
+                .collect::<Vec<_>>();
+            missing.sort();
+            for arity in missing {
+                diagnostics.push(Diagnostic::MissingHandler {
+                    function: caller.to_string(),
+                    name: handler.to_string(),
+                    arity,
+                });
+            }
+        }
+
+        diagnostics
+    }
+}
+
+fn function_id(func: &Function) -> String {
+    format!("{}/{}", func.name, func.args.len())
+}
+
+fn visit<'a, F>(exp: &'a Exp, f: &mut F)
+where
+    F: FnMut(&'a Exp),
+{
+    f(exp);
+    match exp {
+        Exp::Path(lhs, parts) => {
+            visit(lhs, f);
+            for part in parts {
+                match part {
+                    Path::Index(index, _) => visit(index, f),
+                    Path::Range(lhs, rhs, _) => {
+                        for exp in [lhs, rhs].into_iter().flatten() {
+                            visit(exp, f);
+                        }
+                    }
+                }
+            }
+        }
+        Exp::Arr(Some(exp))
+        | Exp::Assign(_, exp)
+        | Exp::Neg(exp)
+        | Exp::Select(exp)
+        | Exp::Map(exp)
+        | Exp::Any(Some(exp))
+        | Exp::All(Some(exp))
+        | Exp::Error(exp)
+        | Exp::Spanned(_, exp) => visit(exp, f),
+        Exp::Obj(pairs) => {
+            for (key, value) in pairs {
+                visit(key, f);
+                if let Some(value) = value {
+                    visit(value, f);
+                }
+            }
+        }
+        Exp::Pipe(lhs, _, rhs)
+        | Exp::Comma(lhs, rhs)
+        | Exp::TryCatch(lhs, rhs)
+        | Exp::BinOp(lhs, _, rhs)
+        | Exp::Alt(lhs, rhs) => {
+            visit(lhs, f);
+            visit(rhs, f);
+        }
+        Exp::IfThenElse(branches, els) => {
+            for (cond, then) in branches {
+                visit(cond, f);
+                visit(then, f);
+            }
+            if let Some(els) = els {
+                visit(els, f);
+            }
+        }
+        Exp::Str(args)
+        | Exp::CustomFunction(_, args)
+        | Exp::Sort(args)
+        | Exp::Unique(args)
+        | Exp::Max(args)
+        | Exp::Min(args) => {
+            for arg in args {
+                visit(arg, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_check() {
+        let module = Module::from_str(
+            r#"
+                def on_casted($event): trigger_stack("main");
+                def on_cast($event): .;
+                def trigger($id): push_stack($id; 1);
+                def stack($id; $action; $n): [field | destroy(.[0])] | foo(1);
+                def stack($id; $action): .;
+                def bar(f): f | power(.; 1);
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            module.check(),
+            vec![
+                Diagnostic::ArityMismatch {
+                    function: "bar/1".to_string(),
+                    name: "power".to_string(),
+                    arity: 2,
+                },
+                Diagnostic::UnknownEvent {
+                    function: "on_cast/1".to_string(),
+                },
+                Diagnostic::UndefinedFunction {
+                    function: "stack/3".to_string(),
+                    name: "foo".to_string(),
+                    arity: 1,
+                },
+                Diagnostic::UnreachableHandler {
+                    function: "stack/2".to_string(),
+                },
+            ]
+        );
+    }
+
+    /// Returns the names matched by the arms of `match name` in the `invoke` methods in `source`.
+    fn invoked_names(source: &str) -> HashSet<&str> {
+        const ARM_INDENT: &str = "            ";
+        source
+            .split("fn invoke(")
+            .skip(1)
+            .filter_map(|body| body.split("\n    }\n").next())
+            .flat_map(|body| body.lines())
+            .filter_map(|line| {
+                let line = line.strip_prefix(ARM_INDENT)?;
+                if line.starts_with(' ') {
+                    return None;
+                }
+                let pattern = line.split("=>").next()?;
+                let names = pattern
+                    .split('|')
+                    .map(str::trim)
+                    .filter(|token| !token.is_empty())
+                    .map(|token| token.strip_prefix('"')?.strip_suffix('"'))
+                    .collect::<Option<Vec<_>>>()?;
+                Some(names)
+            })
+            .flatten()
+            .filter(|name| {
+                !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_builtins_match_invoke() {
+        let invoked = [
+            include_str!("../../env/state.rs"),
+            include_str!("../../effect.rs"),
+        ]
+        .into_iter()
+        .flat_map(invoked_names)
+        .collect::<HashSet<_>>();
+        let builtins = BUILTINS
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<HashSet<_>>();
+        assert_eq!(builtins, invoked);
+    }
+}
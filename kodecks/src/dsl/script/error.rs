@@ -1,3 +1,4 @@
+use std::{fmt, sync::Arc};
 use thiserror::Error;
 
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
@@ -27,3 +28,100 @@ pub enum Error {
     #[error("Error: {0}")]
     Custom(String),
 }
+
+/// Byte range of an expression in the script source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    /// Returns the span of `slice` if it is borrowed from `source`.
+    pub fn of(source: &str, slice: &str) -> Option<Self> {
+        let start = (slice.as_ptr() as usize).checked_sub(source.as_ptr() as usize)?;
+        let end = start + slice.len();
+        if end <= source.len() {
+            Some(Self { start, end })
+        } else {
+            None
+        }
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}
+
+/// Where an evaluation error occurred, collected while the error propagates.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Trace {
+    /// The innermost expression that failed.
+    pub span: Option<Span>,
+    /// Functions that were being evaluated, innermost first.
+    pub stack: Vec<String>,
+}
+
+impl Trace {
+    pub fn locate(&mut self, span: Span) {
+        self.span.get_or_insert(span);
+    }
+
+    pub fn enter(&mut self, function: String) {
+        self.stack.push(function);
+    }
+
+    pub fn clear(&mut self) {
+        self.span = None;
+        self.stack.clear();
+    }
+}
+
+/// An error raised while calling a script function.
+///
+/// The `Display` implementation renders the failing expression with carets under it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalError {
+    pub error: Error,
+    pub trace: Trace,
+    /// The execution budget that was left when the error occurred.
+    pub execution_limit: usize,
+    pub script: Arc<str>,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error: {}", self.error)?;
+        if let Some(span) = self.trace.span.filter(|span| span.end <= self.script.len()) {
+            let script = &*self.script;
+            let line_start = script[..span.start].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = script[span.start..]
+                .find('\n')
+                .map_or(script.len(), |i| span.start + i);
+            let line = script[..span.start].matches('\n').count() + 1;
+            let column = script[line_start..span.start].chars().count() + 1;
+            let indent = script[line_start..span.start]
+                .chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect::<String>();
+            let width = script[span.start..span.end.min(line_end)]
+                .chars()
+                .count()
+                .max(1);
+            let gutter = " ".repeat(line.to_string().len());
+            write!(f, "\n{gutter}--> {line}:{column}")?;
+            write!(f, "\n{gutter} |")?;
+            write!(f, "\n{line} | {}", &script[line_start..line_end])?;
+            write!(f, "\n{gutter} | {indent}{}", "^".repeat(width))?;
+        }
+        for function in &self.trace.stack {
+            write!(f, "\n  = in {function}")?;
+        }
+        write!(f, "\n  = execution budget left: {}", self.execution_limit)
+    }
+}
+
+impl std::error::Error for EvalError {}
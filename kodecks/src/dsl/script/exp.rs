@@ -1,5 +1,5 @@
 use super::{
-    error::Error,
+    error::{Error, EvalError, Span, Trace},
     value::{Constant, Value},
 };
 use crate::{
@@ -62,6 +62,7 @@ pub enum Exp {
     Max(Vec<Self>),
     Min(Vec<Self>),
     Empty,
+    Spanned(Span, Box<Self>),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -84,7 +85,7 @@ impl FromStr for Exp {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let term = jaq_core::load::parse(s, |p| p.term()).ok_or(Error::InvalidSyntax)?;
-        (&term).try_into()
+        Self::parse(s, &term)
     }
 }
 
@@ -101,25 +102,21 @@ enum LiteralPath {
     Range(Option<i64>, Option<i64>, bool),
 }
 
-impl<'a> TryFrom<&'a (Part<Term<&'a str>>, Opt)> for Path {
-    type Error = Error;
-
-    fn try_from(
-        (part, opt): &'a (Part<Term<&'a str>>, Opt),
-    ) -> Result<Self, <Self as TryFrom<&'a (Part<Term<&'a str>>, Opt)>>::Error> {
+impl Path {
+    fn parse(source: &str, (part, opt): &(Part<Term<&str>>, Opt)) -> Result<Self, Error> {
         match part {
             Part::Index(exp) => Ok(Self::Index(
-                Box::new(Exp::try_from(exp)?),
+                Box::new(Exp::parse(source, exp)?),
                 matches!(opt, Opt::Optional),
             )),
             Part::Range(lhs, rhs) => Ok(Self::Range(
                 if let Some(lhs) = lhs {
-                    Some(Box::new(Exp::try_from(lhs)?))
+                    Some(Box::new(Exp::parse(source, lhs)?))
                 } else {
                     None
                 },
                 if let Some(rhs) = rhs {
-                    Some(Box::new(Exp::try_from(rhs)?))
+                    Some(Box::new(Exp::parse(source, rhs)?))
                 } else {
                     None
                 },
@@ -129,34 +126,70 @@ impl<'a> TryFrom<&'a (Part<Term<&'a str>>, Opt)> for Path {
     }
 }
 
-impl<'a> TryFrom<&'a Term<&'a str>> for Exp {
-    type Error = Error;
+impl Exp {
+    /// Converts a term parsed from `source`, keeping the location of fallible expressions.
+    pub fn parse(source: &str, term: &Term<&str>) -> Result<Self, Error> {
+        let exp = Self::parse_term(source, term)?;
+        let span = if exp.is_fallible() {
+            term_span(source, term)
+        } else {
+            None
+        };
+        Ok(if let Some(span) = span {
+            Self::Spanned(span, Box::new(exp))
+        } else {
+            exp
+        })
+    }
 
-    fn try_from(
-        term: &'a Term<&'a str>,
-    ) -> Result<Self, <Self as TryFrom<&'a Term<&'a str>>>::Error> {
+    fn is_fallible(&self) -> bool {
+        matches!(
+            self,
+            Self::Path(..)
+                | Self::Variable(_)
+                | Self::BinOp(..)
+                | Self::Neg(_)
+                | Self::Map(_)
+                | Self::Any(_)
+                | Self::All(_)
+                | Self::Error(_)
+                | Self::CustomFunction(..)
+                | Self::Length
+                | Self::Keys
+                | Self::Abs
+                | Self::Add
+                | Self::Reverse
+                | Self::Sort(_)
+                | Self::Unique(_)
+                | Self::Max(_)
+                | Self::Min(_)
+        )
+    }
+
+    fn parse_term(source: &str, term: &Term<&str>) -> Result<Self, Error> {
         match term {
             Term::Id => Ok(Self::Ident),
             Term::Path(lhs, parts) => {
-                let lhs = Self::try_from(&**lhs)?;
+                let lhs = Self::parse(source, lhs)?;
                 let parts = parts
                     .0
                     .iter()
-                    .map(Path::try_from)
+                    .map(|part| Path::parse(source, part))
                     .collect::<Result<_, _>>()?;
                 Ok(Self::Path(Box::new(lhs), parts))
             }
             Term::Var(s) => Ok(Self::Variable(s.to_string())),
             Term::BinOp(lhs, op, rhs) => match (lhs.as_ref(), op, rhs.as_ref()) {
-                (Term::Var(s), BinaryOp::Assign, rhs) => {
-                    Ok(Self::Assign(s.to_string(), Box::new(Self::try_from(rhs)?)))
-                }
+                (Term::Var(s), BinaryOp::Assign, rhs) => Ok(Self::Assign(
+                    s.to_string(),
+                    Box::new(Self::parse(source, rhs)?),
+                )),
                 (lhs, BinaryOp::Comma, rhs) => Ok(Self::Comma(
-                    Box::new(Self::try_from(lhs)?),
-                    Box::new(Self::try_from(rhs)?),
+                    Box::new(Self::parse(source, lhs)?),
+                    Box::new(Self::parse(source, rhs)?),
                 )),
                 (lhs, op, rhs) => Ok(Self::BinOp(
-                    Box::new(Self::try_from(lhs)?),
+                    Box::new(Self::parse(source, lhs)?),
                     match op {
                         BinaryOp::Math(Math::Add) => BinOp::Add,
                         BinaryOp::Math(Math::Sub) => BinOp::Sub,
@@ -171,19 +204,19 @@ impl<'a> TryFrom<&'a Term<&'a str>> for Exp {
                         BinaryOp::Cmp(Cmp::Lt) => BinOp::Lt,
                         BinaryOp::Alt => {
                             return Ok(Self::Alt(
-                                Box::new(Self::try_from(lhs)?),
-                                Box::new(Self::try_from(rhs)?),
+                                Box::new(Self::parse(source, lhs)?),
+                                Box::new(Self::parse(source, rhs)?),
                             ))
                         }
                         _ => return Err(Error::InvalidSyntax),
                     },
-                    Box::new(Self::try_from(rhs)?),
+                    Box::new(Self::parse(source, rhs)?),
                 )),
             },
-            Term::Neg(exp) => Ok(Self::Neg(Box::new(Self::try_from(&**exp)?))),
+            Term::Neg(exp) => Ok(Self::Neg(Box::new(Self::parse(source, exp)?))),
             Term::Pipe(lhs, var, rhs) => {
-                let lhs = Self::try_from(&**lhs)?;
-                let rhs = Self::try_from(&**rhs)?;
+                let lhs = Self::parse(source, lhs)?;
+                let rhs = Self::parse(source, rhs)?;
                 let var = var.as_ref().map(|s| s.to_string());
                 Ok(Self::Pipe(Box::new(lhs), var, Box::new(rhs)))
             }
@@ -197,20 +230,20 @@ impl<'a> TryFrom<&'a Term<&'a str>> for Exp {
                 return Err(Error::InvalidSyntax);
             })),
             Term::TryCatch(lhs, rhs) => Ok(Self::TryCatch(
-                Box::new(Self::try_from(&**lhs)?),
+                Box::new(Self::parse(source, lhs)?),
                 Box::new(
                     rhs.as_ref()
-                        .map(|rhs| Self::try_from(&**rhs))
+                        .map(|rhs| Self::parse(source, rhs))
                         .unwrap_or(Ok(Self::Empty))?,
                 ),
             )),
             Term::IfThenElse(ifthen, els) => {
                 let ifthen = ifthen
                     .iter()
-                    .map(|(lhs, rhs)| Ok((Self::try_from(lhs)?, Self::try_from(rhs)?)))
+                    .map(|(lhs, rhs)| Ok((Self::parse(source, lhs)?, Self::parse(source, rhs)?)))
                     .collect::<Result<_, _>>()?;
                 let els = if let Some(els) = els {
-                    Some(Box::new(Self::try_from(&**els)?))
+                    Some(Box::new(Self::parse(source, els)?))
                 } else {
                     None
                 };
@@ -227,7 +260,7 @@ impl<'a> TryFrom<&'a Term<&'a str>> for Exp {
                             args.push(Self::Value(Value::Constant((*s).into())));
                         }
                         StrPart::Term(t) => {
-                            args.push(Self::try_from(t)?);
+                            args.push(Self::parse(source, t)?);
                         }
                     }
                 }
@@ -235,7 +268,7 @@ impl<'a> TryFrom<&'a Term<&'a str>> for Exp {
             }
             Term::Arr(arr) => {
                 if let Some(arr) = arr {
-                    Ok(Self::Arr(Some(Box::new(Self::try_from(&**arr)?))))
+                    Ok(Self::Arr(Some(Box::new(Self::parse(source, arr)?))))
                 } else {
                     Ok(Self::Arr(None))
                 }
@@ -244,9 +277,9 @@ impl<'a> TryFrom<&'a Term<&'a str>> for Exp {
                 let mut obj = vec![];
                 for (lhs, rhs) in pairs {
                     obj.push((
-                        Box::new(Self::try_from(lhs)?),
+                        Box::new(Self::parse(source, lhs)?),
                         if let Some(rhs) = rhs {
-                            Some(Box::new(Self::try_from(rhs)?))
+                            Some(Box::new(Self::parse(source, rhs)?))
                         } else {
                             None
                         },
@@ -269,35 +302,35 @@ impl<'a> TryFrom<&'a Term<&'a str>> for Exp {
             Term::Call("false", _) => Ok(Self::Value(Value::Constant(Constant::Bool(false)))),
             Term::Call("error", msg) => {
                 if let Some(msg) = msg.first() {
-                    Ok(Self::Error(Box::new(Self::try_from(msg)?)))
+                    Ok(Self::Error(Box::new(Self::parse(source, msg)?)))
                 } else {
                     Err(Error::InvalidSyntax)
                 }
             }
             Term::Call("select", exp) => {
                 if let Some(exp) = exp.first() {
-                    Ok(Self::Select(Box::new(Self::try_from(exp)?)))
+                    Ok(Self::Select(Box::new(Self::parse(source, exp)?)))
                 } else {
                     Err(Error::InvalidSyntax)
                 }
             }
             Term::Call("map", exp) => {
                 if let Some(exp) = exp.first() {
-                    Ok(Self::Map(Box::new(Self::try_from(exp)?)))
+                    Ok(Self::Map(Box::new(Self::parse(source, exp)?)))
                 } else {
                     Err(Error::InvalidSyntax)
                 }
             }
             Term::Call("any", exp) => {
                 if let Some(exp) = exp.first() {
-                    Ok(Self::Any(Some(Box::new(Self::try_from(exp)?))))
+                    Ok(Self::Any(Some(Box::new(Self::parse(source, exp)?))))
                 } else {
                     Ok(Self::Any(None))
                 }
             }
             Term::Call("all", exp) => {
                 if let Some(exp) = exp.first() {
-                    Ok(Self::All(Some(Box::new(Self::try_from(exp)?))))
+                    Ok(Self::All(Some(Box::new(Self::parse(source, exp)?))))
                 } else {
                     Ok(Self::All(None))
                 }
@@ -306,33 +339,36 @@ impl<'a> TryFrom<&'a Term<&'a str>> for Exp {
             Term::Call("sort" | "sort_by", exp) => {
                 let exp = exp
                     .iter()
-                    .map(Self::try_from)
+                    .map(|exp| Self::parse(source, exp))
                     .collect::<Result<Vec<_>, _>>()?;
                 Ok(Self::Sort(exp))
             }
             Term::Call("unique" | "unique_by", exp) => {
                 let exp = exp
                     .iter()
-                    .map(Self::try_from)
+                    .map(|exp| Self::parse(source, exp))
                     .collect::<Result<Vec<_>, _>>()?;
                 Ok(Self::Unique(exp))
             }
             Term::Call("max" | "max_by", exp) => {
                 let exp = exp
                     .iter()
-                    .map(Self::try_from)
+                    .map(|exp| Self::parse(source, exp))
                     .collect::<Result<Vec<_>, _>>()?;
                 Ok(Self::Max(exp))
             }
             Term::Call("min" | "min_by", exp) => {
                 let exp = exp
                     .iter()
-                    .map(Self::try_from)
+                    .map(|exp| Self::parse(source, exp))
                     .collect::<Result<Vec<_>, _>>()?;
                 Ok(Self::Min(exp))
             }
             Term::Call(name, args) => {
-                let args = args.iter().map(Self::try_from).collect::<Result<_, _>>()?;
+                let args = args
+                    .iter()
+                    .map(|exp| Self::parse(source, exp))
+                    .collect::<Result<_, _>>()?;
                 Ok(Self::CustomFunction(name.to_string(), args))
             }
             _ => Err(Error::InvalidSyntax),
@@ -340,6 +376,64 @@ impl<'a> TryFrom<&'a Term<&'a str>> for Exp {
     }
 }
 
+/// Returns the location of the term in `source`.
+///
+/// The parser borrows names and literals from the source,
+/// so the span is the union of the positions of these slices.
+fn term_span(source: &str, term: &Term<&str>) -> Option<Span> {
+    let union = |terms: &mut dyn Iterator<Item = &Term<&str>>| {
+        terms
+            .filter_map(|term| term_span(source, term))
+            .reduce(Span::union)
+    };
+    match term {
+        Term::Num(s) | Term::Var(s) => Span::of(source, s),
+        Term::Str(_, parts) => parts
+            .iter()
+            .filter_map(|part| match part {
+                StrPart::Str(s) => Span::of(source, s),
+                StrPart::Term(term) => term_span(source, term),
+                StrPart::Char(_) => None,
+            })
+            .reduce(Span::union),
+        Term::Arr(arr) => arr.as_deref().and_then(|arr| term_span(source, arr)),
+        Term::Obj(pairs) => union(
+            &mut pairs
+                .iter()
+                .flat_map(|(key, value)| Some(key).into_iter().chain(value)),
+        ),
+        Term::Neg(term) => term_span(source, term),
+        Term::Pipe(lhs, _, rhs) | Term::BinOp(lhs, _, rhs) => {
+            union(&mut [&**lhs, &**rhs].into_iter())
+        }
+        Term::TryCatch(lhs, rhs) => union(&mut Some(&**lhs).into_iter().chain(rhs.as_deref())),
+        Term::IfThenElse(branches, els) => union(
+            &mut branches
+                .iter()
+                .flat_map(|(cond, then)| [cond, then])
+                .chain(els.as_deref()),
+        ),
+        Term::Call(name, args) => {
+            let mut span = Span::of(source, name)?;
+            if let Some(args) = union(&mut args.iter()) {
+                span = span.union(args);
+                // Include the closing parenthesis, which is not part of any argument.
+                if let Some(i) = source[span.end..].find(')') {
+                    span.end += i + 1;
+                }
+            }
+            Some(span)
+        }
+        Term::Path(lhs, parts) => union(&mut Some(&**lhs).into_iter().chain(
+            parts.0.iter().flat_map(|(part, _)| match part {
+                Part::Index(index) => vec![index],
+                Part::Range(lhs, rhs) => lhs.iter().chain(rhs).collect(),
+            }),
+        )),
+        _ => None,
+    }
+}
+
 impl<'de> Deserialize<'de> for Exp {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         let s = String::deserialize(deserializer)?;
@@ -371,6 +465,13 @@ impl<'a> ExpExt<'a, &'a Value> for Exp {
     where
         T: ExpEnv,
     {
+        if let Self::Spanned(span, exp) = self {
+            // Spans only annotate the expression and do not count towards the execution limit.
+            return exp
+                .eval(ctx)
+                .inspect_err(|_| ctx.params.trace.locate(*span));
+        }
+
         ctx.params.consume_exec(1)?;
 
         match self {
@@ -685,6 +786,7 @@ impl<'a> ExpExt<'a, &'a Value> for Exp {
                 match val {
                     Ok(val) => Ok(val),
                     Err(_) => {
+                        ctx.params.trace.clear();
                         let mut new_ctx = ExpContext {
                             env: ctx.env,
                             input: ctx.input,
@@ -753,7 +855,9 @@ impl<'a> ExpExt<'a, &'a Value> for Exp {
                                 new_args.push(Value::Function(Box::new(func)));
                             }
                         }
-                        func.invoke(ctx, new_args)
+                        func.invoke(ctx, new_args).inspect_err(|_| {
+                            ctx.params.trace.enter(format!("{name}/{}", args.len()))
+                        })
                     } else {
                         Ok(vec![value.clone()])
                     }
@@ -855,6 +959,7 @@ impl<'a> ExpExt<'a, &'a Value> for Exp {
                 }
             }
             Self::Not => Ok(vec![(!ctx.input).into()]),
+            Self::Spanned(..) => unreachable!(),
         }
     }
 }
@@ -911,6 +1016,7 @@ pub struct ExpParams {
     pub parent: Option<Arc<Self>>,
     pub vars: Vec<HashMap<String, Exp>>,
     pub execution_limit: usize,
+    pub trace: Trace,
 }
 
 impl ExpParams {
@@ -999,6 +1105,7 @@ impl Default for ExpParams {
             parent: None,
             vars: vec![HashMap::new()],
             execution_limit: EXECUTION_LIMIT,
+            trace: Trace::default(),
         }
     }
 }
@@ -1018,37 +1125,72 @@ impl<'a, T, I> ExpContext<'a, T, I> {
 #[derive(Debug, Clone)]
 pub struct Module {
     params: Arc<ExpParams>,
+    source: Arc<str>,
 }
 
 impl Module {
     pub fn new(params: Arc<ExpParams>) -> Self {
-        Self { params }
+        Self {
+            params,
+            source: "".into(),
+        }
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
     }
 
-    pub fn call<T, C>(&self, env: &mut T, name: &str, args: Vec<Value>) -> Result<C, Error>
+    pub fn call<T, C>(&self, env: &mut T, name: &str, args: Vec<Value>) -> Result<C, EvalError>
     where
         T: ExpEnv,
         C: DeserializeOwned + Default,
     {
-        if let Ok(Exp::Value(Value::Function(func))) = self.params.get_def(name, args.len()) {
-            let input = Value::default();
-            let mut params = ExpParams::new(Some(self.params.clone()));
-            let mut ctx = ExpContext::new(env, &input, &mut params);
-            let mut val = func.invoke(&mut ctx, args)?;
-            if let Some(last) = val.pop() {
-                Ok(serde_json::from_value(last.try_into()?)
-                    .map_err(|_| Error::InvalidConversion)?)
+        let mut params = ExpParams::new(Some(self.params.clone()));
+        let result =
+            if let Ok(Exp::Value(Value::Function(func))) = self.params.get_def(name, args.len()) {
+                let arity = args.len();
+                let input = Value::default();
+                let mut ctx = ExpContext::new(env, &input, &mut params);
+                func.invoke(&mut ctx, args)
+                    .inspect_err(|_| ctx.params.trace.enter(format!("{name}/{arity}")))
+                    .and_then(|mut val| {
+                        if let Some(last) = val.pop() {
+                            serde_json::from_value(last.try_into()?)
+                                .map_err(|_| Error::InvalidConversion)
+                        } else {
+                            Ok(Default::default())
+                        }
+                    })
             } else {
-                Ok(Default::default())
-            }
-        } else {
-            Err(Error::UndefinedFilter)
-        }
+                Err(Error::UndefinedFilter)
+            };
+        result.map_err(|error| EvalError {
+            error,
+            trace: params.trace,
+            execution_limit: params.execution_limit,
+            script: self.source.clone(),
+        })
     }
 
     pub fn has_def(&self, name: &str, arity: usize) -> bool {
         self.params.get_def(name, arity).is_ok()
     }
+
+    /// Returns the functions defined in the module, sorted by name and arity.
+    pub fn functions(&self) -> Vec<&Function> {
+        let mut functions = self
+            .params
+            .vars
+            .iter()
+            .flat_map(|vars| vars.values())
+            .filter_map(|exp| match exp {
+                Exp::Value(Value::Function(func)) => Some(&**func),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        functions.sort_by(|a, b| a.name.cmp(&b.name).then(a.args.len().cmp(&b.args.len())));
+        functions
+    }
 }
 
 impl FromStr for Module {
@@ -1062,7 +1204,7 @@ impl FromStr for Module {
             let func = Function {
                 name: name.to_string(),
                 args: def.args.iter().map(|s| s.to_string()).collect(),
-                body: (&def.body).try_into()?,
+                body: Exp::parse(s, &def.body)?,
             };
             params.set_def(
                 &name,
@@ -1072,6 +1214,7 @@ impl FromStr for Module {
         }
         Ok(Self {
             params: Arc::new(params),
+            source: s.into(),
         })
     }
 }
@@ -1150,6 +1293,56 @@ mod tests {
         assert_eq!(n, Some(2));
     }
 
+    #[test]
+    fn test_eval_error() {
+        let source = r#"
+def inner($x): $x | error("boom");
+def outer: 1 | inner(.);
+def retry: (try error("x") catch 1) | $undefined;
+"#;
+        let module = Module::from_str(source).unwrap();
+        let mut env = TestEnv {};
+
+        let err = module
+            .call::<_, Option<u32>>(&mut env, "outer", vec![])
+            .unwrap_err();
+        assert_eq!(err.error, Error::Custom("boom".to_string()));
+        assert_eq!(err.trace.stack, vec!["inner/1", "outer/0"]);
+        let start = source.find("error(\"boom\")").unwrap();
+        assert_eq!(
+            err.trace.span,
+            Some(Span {
+                start,
+                end: start + 13
+            })
+        );
+        assert!(err.execution_limit < EXECUTION_LIMIT);
+        assert_eq!(
+            err.to_string().lines().take(5).collect::<Vec<_>>(),
+            vec![
+                "error: Error: boom",
+                " --> 2:21",
+                "  |",
+                "2 | def inner($x): $x | error(\"boom\");",
+                "  |                     ^^^^^^^^^^^^^",
+            ]
+        );
+
+        let err = module
+            .call::<_, Option<u32>>(&mut env, "retry", vec![])
+            .unwrap_err();
+        assert_eq!(err.error, Error::UndefinedVariable);
+        assert_eq!(err.trace.stack, vec!["retry/0"]);
+        let start = source.find("$undefined").unwrap();
+        assert_eq!(
+            err.trace.span,
+            Some(Span {
+                start,
+                end: start + 10
+            })
+        );
+    }
+
     #[test]
     fn test_exp() {
         let mut env = TestEnv {};
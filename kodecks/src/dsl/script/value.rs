@@ -9,6 +9,13 @@ use crate::{
     prelude::ComputedFlags,
     zone::CardZone,
 };
+use bincode::{
+    de::{BorrowDecoder, Decoder},
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    BorrowDecode, Decode, Encode,
+};
+use serde::{de, ser, Deserialize, Serialize};
 use serde_json::Number;
 use std::{
     collections::BTreeMap,
@@ -93,6 +100,104 @@ impl TryFrom<Value> for serde_json::Value {
     }
 }
 
+#[derive(Serialize, Deserialize, Encode, Decode)]
+#[serde(rename_all = "snake_case")]
+enum ValueData {
+    Null,
+    Bool(bool),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    String(String),
+    Array(Vec<ValueData>),
+    Object(BTreeMap<String, ValueData>),
+    Card(TimedObjectId),
+    Player(u8),
+}
+
+impl TryFrom<&Value> for ValueData {
+    type Error = Error;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Constant(Constant::Null) => Ok(ValueData::Null),
+            Value::Constant(Constant::Bool(b)) => Ok(ValueData::Bool(*b)),
+            Value::Constant(Constant::U64(n)) => Ok(ValueData::U64(*n)),
+            Value::Constant(Constant::I64(n)) => Ok(ValueData::I64(*n)),
+            Value::Constant(Constant::F64(n)) => Ok(ValueData::F64(*n)),
+            Value::Constant(Constant::String(s)) => Ok(ValueData::String(s.to_string())),
+            Value::Array(a) => Ok(ValueData::Array(
+                a.iter()
+                    .map(ValueData::try_from)
+                    .collect::<Result<_, _>>()?,
+            )),
+            Value::Object(o) => Ok(ValueData::Object(
+                o.iter()
+                    .map(|(k, v)| Ok((k.to_string(), ValueData::try_from(v)?)))
+                    .collect::<Result<_, _>>()?,
+            )),
+            Value::Custom(CustomType::Card(card)) => Ok(ValueData::Card(*card)),
+            Value::Custom(CustomType::Player(player)) => Ok(ValueData::Player(*player)),
+            Value::Function(_) => Err(Error::InvalidConversion),
+        }
+    }
+}
+
+impl From<ValueData> for Value {
+    fn from(value: ValueData) -> Self {
+        match value {
+            ValueData::Null => Value::Constant(Constant::Null),
+            ValueData::Bool(b) => Value::Constant(Constant::Bool(b)),
+            ValueData::U64(n) => Value::Constant(Constant::U64(n)),
+            ValueData::I64(n) => Value::Constant(Constant::I64(n)),
+            ValueData::F64(n) => Value::Constant(Constant::F64(n)),
+            ValueData::String(s) => s.into(),
+            ValueData::Array(a) => Value::Array(a.into_iter().map(Value::from).collect()),
+            ValueData::Object(o) => Value::Object(
+                o.into_iter()
+                    .map(|(k, v)| (TinyAsciiStr::from_bytes_lossy(k.as_bytes()), Value::from(v)))
+                    .collect(),
+            ),
+            ValueData::Card(card) => Value::Custom(CustomType::Card(card)),
+            ValueData::Player(player) => Value::Custom(CustomType::Player(player)),
+        }
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ValueData::try_from(self)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(ValueData::deserialize(deserializer)?.into())
+    }
+}
+
+impl Encode for Value {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        ValueData::try_from(self)
+            .map_err(|err| EncodeError::OtherString(err.to_string()))?
+            .encode(encoder)
+    }
+}
+
+impl Decode for Value {
+    fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(<ValueData as Decode>::decode(decoder)?.into())
+    }
+}
+
+impl<'de> BorrowDecode<'de> for Value {
+    fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(<ValueData as Decode>::decode(decoder)?.into())
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 pub enum Constant {
     #[default]
@@ -617,6 +722,22 @@ impl Value {
         }
     }
 
+    pub fn as_card(&self) -> Option<TimedObjectId> {
+        match self {
+            Value::Custom(CustomType::Card(card)) => Some(*card),
+            _ => None,
+        }
+    }
+
+    /// Returns the player id of a player value or a plain number.
+    pub fn as_player(&self) -> Option<u8> {
+        match self {
+            Value::Custom(CustomType::Player(player)) => Some(*player),
+            Value::Constant(constant) => constant.as_u64().and_then(|n| n.try_into().ok()),
+            _ => None,
+        }
+    }
+
     pub fn abs(&self) -> Result<Self, Error> {
         match self {
             Value::Constant(constant) => Ok(Value::Constant(constant.abs()?)),
@@ -728,6 +849,17 @@ impl Value {
                         }
                         Some(Value::Object(map))
                     }
+                    "card_type" => Some(
+                        card.computed()
+                            .card_type
+                            .to_string()
+                            .to_ascii_lowercase()
+                            .into(),
+                    ),
+                    "creature_type" => card
+                        .computed()
+                        .creature_type
+                        .map(|kind| kind.to_string().to_ascii_lowercase().into()),
                     "cost" => Some(card.computed().cost.value().into()),
                     "power" => card.computed().power.map(|power| power.value().into()),
                     "shards" => Some(card.computed().shards.value().into()),
@@ -1,18 +1,21 @@
 use crate::{
     action::{Action, PlayerAvailableActions},
+    archetype::ArchetypeId,
     card::Card,
+    color::Color,
     command::ActionCommand,
-    continuous::{ContinuousEffect, ContinuousItem},
+    continuous::ContinuousItem,
     dsl::script::{
         error::Error,
-        exp::{ExpEnv, ExpParams, Module},
+        exp::{ExpEnv, ExpParams},
         value::{CustomType, Value},
     },
     env::GameState,
-    event::{CardEvent, EventFilter},
-    id::{CardId, ObjectId, ObjectIdCounter, TimedCardId},
+    event::{CardEvent, EventFilter, EventReason},
+    field::FieldState,
+    id::{CardId, ObjectId, ObjectIdCounter, TimedCardId, TimedObjectId},
     player::{Player, PlayerList},
-    prelude::{ComputedAttribute, ComputedAttributeModifier},
+    prelude::{AbilityList, ComputedAttribute, PlayerAbility},
     stack::StackItem,
     target::Target,
 };
@@ -26,15 +29,10 @@ use dyn_clone::DynClone;
 use serde::{Deserialize, Serialize};
 use std::{
     fmt::{self, Debug},
-    sync::Arc,
+    str::FromStr,
 };
 use tinystr::TinyAsciiStr;
 
-pub type StackEffectHandler = dyn Fn(&mut EffectTriggerContext, Option<Action>) -> anyhow::Result<EffectReport>
-    + Send
-    + Sync
-    + 'static;
-
 #[derive(Debug, Default, Deserialize, Serialize)]
 pub struct EffectReport {
     #[serde(default)]
@@ -155,25 +153,23 @@ impl<'a> EffectTriggerContext<'a> {
         self.source
     }
 
-    pub fn push_continuous<F>(&mut self, effect: F, target: Target)
+    pub fn push_continuous<T>(&mut self, id: T, args: Vec<Value>, target: Target)
     where
-        F: ContinuousEffect + 'static,
+        T: Into<EffectId>,
     {
         self.continuous
-            .push(ContinuousItem::new(self.source, effect, target));
+            .push(ContinuousItem::new(self.source, id.into(), args, target));
     }
 
-    pub fn push_stack<F>(&mut self, id: &str, handler: F)
+    pub fn push_stack<T>(&mut self, id: T, args: Vec<Value>)
     where
-        F: Fn(&mut EffectTriggerContext, Option<Action>) -> anyhow::Result<EffectReport>
-            + Send
-            + Sync
-            + 'static,
+        T: Into<EffectId>,
     {
         self.stack.push(StackItem {
             source: self.source.id(),
-            id: id.to_string(),
-            handler: Arc::new(Box::new(handler)),
+            archetype: self.source.archetype().id,
+            id: id.into(),
+            args,
         });
     }
 
@@ -218,24 +214,12 @@ impl ExpEnv for EffectTriggerContext<'_> {
                     return Err(Error::InvalidArgumentCount);
                 }
                 let id = args[0].to_string();
-                let params = Arc::new(params.clone());
                 let args = args.into_iter().skip(1).collect::<Vec<_>>();
-                self.push_stack(&id.clone(), move |ctx, action| {
-                    let event: Value = if let Some(action) = action {
-                        action.into()
-                    } else {
-                        Default::default()
-                    };
-                    let mut new_args: Vec<Value> = vec![id.clone().into(), event];
-                    new_args.extend(args.clone());
-                    let module = Module::new(params.clone());
-                    let report: Option<EffectReport> = module.call(ctx, "stack", new_args)?;
-                    Ok(report.unwrap_or_default())
-                });
+                self.push_stack(id, args);
                 Ok(vec![input.clone()])
             }
             "push_continuous" => {
-                if args.is_empty() {
+                if args.len() < 2 {
                     return Err(Error::InvalidArgumentCount);
                 }
                 let target = match args[1] {
@@ -243,22 +227,130 @@ impl ExpEnv for EffectTriggerContext<'_> {
                     Value::Custom(CustomType::Player(player)) => Target::Player(player),
                     _ => return Err(Error::InvalidConversion),
                 };
-                let params = Arc::new(params.clone());
-                let module = Module::new(params.clone());
+                let id = args[0].to_string();
                 let args = args
                     .into_iter()
                     .enumerate()
                     .filter_map(|(i, v)| if i == 1 { None } else { Some(v) })
                     .collect::<Vec<_>>();
-                let effect = ModuleContinuousEffect { module, args };
-                self.push_continuous(effect, target);
+                self.push_continuous(id, args, target);
                 Ok(vec![input.clone()])
             }
+            "destroy" | "return_to_hand" | "shuffle_into_deck" => {
+                let [target] = args.as_slice() else {
+                    return Err(Error::InvalidArgumentCount);
+                };
+                let source = self.source.id();
+                let target = card_arg(target)?;
+                let reason = EventReason::Effect;
+                command_value(match name {
+                    "destroy" => ActionCommand::DestroyCard {
+                        source,
+                        target,
+                        reason,
+                    },
+                    "return_to_hand" => ActionCommand::ReturnCardToHand {
+                        source,
+                        target,
+                        reason,
+                    },
+                    _ => ActionCommand::ShuffleCardIntoDeck { source, target },
+                })
+            }
+            "set_field_state" => {
+                let [target, state] = args.as_slice() else {
+                    return Err(Error::InvalidArgumentCount);
+                };
+                let state = match state.to_string().as_str() {
+                    "active" => FieldState::Active,
+                    "exhausted" => FieldState::Exhausted,
+                    _ => return Err(Error::InvalidConversion),
+                };
+                command_value(ActionCommand::SetFieldState {
+                    source: self.source.id(),
+                    target: card_arg(target)?,
+                    state,
+                    reason: EventReason::Effect,
+                })
+            }
+            "inflict_damage" => {
+                let [target, amount] = args.as_slice() else {
+                    return Err(Error::InvalidArgumentCount);
+                };
+                command_value(ActionCommand::InflictDamage {
+                    target: player_arg(target)?,
+                    amount: number_arg(amount)?,
+                })
+            }
+            "generate_shards" | "consume_shards" => {
+                let [player, color, amount] = args.as_slice() else {
+                    return Err(Error::InvalidArgumentCount);
+                };
+                let player = player_arg(player)?;
+                let source = self.source.id();
+                let color =
+                    Color::from_str(&color.to_string()).map_err(|_| Error::InvalidConversion)?;
+                let amount = number_arg(amount)?;
+                command_value(if name == "generate_shards" {
+                    ActionCommand::GenerateShards {
+                        player,
+                        source,
+                        color,
+                        amount,
+                    }
+                } else {
+                    ActionCommand::ConsumeShards {
+                        player,
+                        source,
+                        color,
+                        amount,
+                    }
+                })
+            }
+            "generate_token" => {
+                let [player, archetype] = args.as_slice() else {
+                    return Err(Error::InvalidArgumentCount);
+                };
+                let player = player_arg(player)?;
+                let archetype = ArchetypeId::new(&archetype.to_string());
+                command_value(ActionCommand::GenerateCardToken {
+                    token: self.new_id(),
+                    archetype,
+                    player,
+                })
+            }
             _ => self.state.invoke(name, args, params, input),
         }
     }
 }
 
+fn card_arg(value: &Value) -> Result<TimedObjectId, Error> {
+    value.as_card().ok_or(Error::InvalidConversion)
+}
+
+fn player_arg(value: &Value) -> Result<u8, Error> {
+    value.as_player().ok_or(Error::InvalidConversion)
+}
+
+fn number_arg<T>(value: &Value) -> Result<T, Error>
+where
+    T: TryFrom<u64>,
+{
+    match value {
+        Value::Constant(constant) => constant
+            .as_u64()
+            .and_then(|n| n.try_into().ok())
+            .ok_or(Error::InvalidConversion),
+        _ => Err(Error::InvalidConversion),
+    }
+}
+
+/// Converts a command into the value expected in the `commands` of an `EffectReport`.
+fn command_value(command: ActionCommand) -> Result<Vec<Value>, Error> {
+    let value = serde_json::to_value(command).map_err(|_| Error::InvalidConversion)?;
+    Ok(vec![value.into()])
+}
+
 pub struct ContinuousCardEffectContext<'a> {
     pub state: &'a GameState,
     pub source: &'a Card,
@@ -388,6 +480,11 @@ impl ExpEnv for EffectActivateContext<'_> {
     }
 }
 
+/// The behavior of a card.
+///
+/// Each card holds its own instance, which resolves the stack items and applies the
+/// continuous effects it pushed. The instance is created again from the archetype when a
+/// snapshot is restored, so any state which must survive that belongs in the item arguments.
 pub trait Effect: Send + Sync + DynClone {
     fn event_filter(&self) -> EventFilter {
         EventFilter::empty()
@@ -397,10 +494,45 @@ pub trait Effect: Send + Sync + DynClone {
         castable
     }
 
-    fn trigger(&mut self, _id: EffectId, _ctx: &mut EffectTriggerContext) -> anyhow::Result<()> {
+    /// Pushes the stack items for an id passed to `trigger_stack` or `trigger_continuous`.
+    ///
+    /// By default, a stack item without arguments is pushed for the id.
+    /// An effect overriding this must push its own items.
+    fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> anyhow::Result<()> {
+        ctx.push_stack(id, vec![]);
         Ok(())
     }
 
+    fn resolve(
+        &mut self,
+        _id: EffectId,
+        _args: &[Value],
+        _ctx: &mut EffectTriggerContext,
+        _action: Option<Action>,
+    ) -> anyhow::Result<EffectReport> {
+        Ok(EffectReport::default())
+    }
+
+    fn apply_card(
+        &self,
+        _id: EffectId,
+        _args: &[Value],
+        _ctx: &mut ContinuousCardEffectContext,
+    ) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+
+    fn apply_player(
+        &self,
+        _id: EffectId,
+        _args: &[Value],
+        _state: &GameState,
+        _player: u8,
+        _abilities: &mut AbilityList<PlayerAbility>,
+    ) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+
     fn activate(
         &mut self,
         _event: CardEvent,
@@ -409,33 +541,3 @@ pub trait Effect: Send + Sync + DynClone {
         Ok(())
     }
 }
-#[derive(Debug, Clone)]
-struct ModuleContinuousEffect {
-    module: Module,
-    args: Vec<Value>,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-#[serde(untagged)]
-enum ModifierOrBool {
-    Bool(bool),
-    Modifier(ComputedAttributeModifier),
-}
-
-impl ContinuousEffect for ModuleContinuousEffect {
-    fn apply_card(&mut self, ctx: &mut ContinuousCardEffectContext) -> anyhow::Result<bool> {
-        let modifier: Option<ModifierOrBool> =
-            self.module.call(ctx, "continuous", self.args.clone())?;
-        if let Some(modifier) = modifier {
-            match modifier {
-                ModifierOrBool::Modifier(modifier) => {
-                    ctx.computed.apply_modifier(modifier);
-                }
-                ModifierOrBool::Bool(value) => {
-                    return Ok(value);
-                }
-            }
-        }
-        Ok(true)
-    }
-}
@@ -5,6 +5,9 @@ use crate::{
     id::TimedCardId,
     message::Message,
     phase::Phase,
+    player::PlayerItem,
+    regulation::MulliganPolicy,
+    target::Target,
     variable::VariableList,
     zone::CardZone,
 };
@@ -24,7 +27,42 @@ impl Environment {
             return None;
         }
 
+        if let Phase::Mulligan = &self.state.phase {
+            let player = self.state.mulligan_player()?;
+            let can_mulligan = match self.state.regulation.mulligan {
+                MulliganPolicy::Disabled => false,
+                MulliganPolicy::Redraw => {
+                    player.mulligan.count + 1 < self.state.regulation.initial_hand_size
+                }
+                MulliganPolicy::Bottom => player.mulligan.count == 0,
+            };
+            return Some(PlayerAvailableActions {
+                player: player.id,
+                actions: filter_vec![
+                    if can_mulligan {
+                        Some(AvailableAction::Mulligan {
+                            cards: player.hand.iter().map(|card| card.timed_id()).collect(),
+                        })
+                    } else {
+                        None
+                    },
+                    Some(AvailableAction::Continue),
+                ]
+                .into_iter()
+                .collect(),
+                instructions: Some(Message {
+                    id: "message-mulligan".to_string(),
+                    ..Default::default()
+                }),
+                message_dialog: None,
+            });
+        }
+
         let active_player = self.state.players.player_in_turn().ok()?;
+        if active_player.is_eliminated() {
+            return None;
+        }
+
         let attackers = active_player
             .field
             .active_cards()
@@ -69,7 +107,15 @@ impl Environment {
                         })
                     },
                     if !attackers.is_empty() {
-                        Some(AvailableAction::Attack { attackers })
+                        Some(AvailableAction::Attack {
+                            attackers,
+                            targets: self
+                                .state
+                                .players
+                                .opponents(active_player.id)
+                                .map(|player| Target::Player(player.id))
+                                .collect(),
+                        })
                     } else {
                         None
                     },
@@ -90,7 +136,11 @@ impl Environment {
                 return None;
             }
 
-            let player_in_action = self.state.players.next_player(active_player.id).ok()?;
+            let player_in_action = self.state.defender().ok()?;
+            if player_in_action.is_eliminated() {
+                return None;
+            }
+
             let blockers = player_in_action
                 .field
                 .active_cards()
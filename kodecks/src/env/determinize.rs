@@ -0,0 +1,204 @@
+use super::{Environment, GameRng};
+use crate::{deck::DeckList, zone::CardZone};
+use rand::{seq::SliceRandom, Rng};
+use std::collections::{BTreeMap, HashSet};
+
+/// What a viewer assumes about the cards it cannot see.
+///
+/// The hidden cards of a player whose deck list is known are drawn from the list,
+/// without the cards of the player the viewer has already seen.
+/// The other hidden cards, and the ones left when a list runs out,
+/// are drawn uniformly from the non-token cards of the catalog.
+#[derive(Debug, Clone, Default)]
+pub struct CardPrior {
+    /// The deck lists known to the viewer, by player.
+    pub decks: BTreeMap<u8, DeckList>,
+}
+
+impl CardPrior {
+    pub fn with_deck(mut self, player: u8, deck: DeckList) -> Self {
+        self.decks.insert(player, deck);
+        self
+    }
+}
+
+impl Environment {
+    /// Returns a copy of the environment where the cards hidden from the viewer are sampled from the prior.
+    ///
+    /// The known cards are the ones in the view of the viewer, i.e. [`Environment::local`],
+    /// and the cards revealed to it in the decks. Every other card in the hands and the decks
+    /// gets a sampled identity, so the copy does not depend on the actual hidden cards.
+    /// The random seed is replaced as well, so later shuffles cannot be foreseen.
+    pub fn determinize(&self, viewer: u8, prior: &CardPrior, rng: &mut impl Rng) -> Self {
+        let view = self.local(viewer);
+        let catalog = self
+            .catalog
+            .iter()
+            .filter(|archetype| !archetype.attribute.is_token)
+            .collect::<Vec<_>>();
+        let mut env = self.clone();
+        for player in env.state.players.iter_mut() {
+            let hidden = view
+                .players
+                .get(player.id)
+                .into_iter()
+                .flat_map(|local| &local.hand)
+                .filter(|card| card.archetype_id.is_empty())
+                .map(|card| card.id)
+                .chain(
+                    player
+                        .deck
+                        .iter()
+                        .filter(|card| !card.revealed().contains(viewer))
+                        .map(|card| card.id()),
+                )
+                .collect::<HashSet<_>>();
+            let seen = view
+                .cards()
+                .filter(|card| card.owner == player.id && !card.archetype_id.is_empty())
+                .map(|card| card.archetype_id)
+                .chain(
+                    player
+                        .deck
+                        .iter()
+                        .filter(|card| !hidden.contains(&card.id()))
+                        .map(|card| card.archetype().id),
+                )
+                .collect::<Vec<_>>();
+
+            let mut pool = prior
+                .decks
+                .get(&player.id)
+                .map(|deck| {
+                    deck.cards
+                        .iter()
+                        .map(|item| item.card.archetype_id)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            for card in seen {
+                if let Some(index) = pool.iter().position(|id| *id == card) {
+                    pool.swap_remove(index);
+                }
+            }
+            pool.shuffle(rng);
+
+            let cards = player
+                .hand
+                .iter_mut()
+                .chain(player.deck.iter_mut())
+                .filter(|card| hidden.contains(&card.id()));
+            for card in cards {
+                let archetype = pool
+                    .pop()
+                    .and_then(|id| self.catalog.get(id))
+                    .or_else(|| catalog.choose(rng).copied());
+                if let Some(archetype) = archetype {
+                    card.replace_archetype(archetype.clone());
+                }
+            }
+        }
+        env.rng = GameRng::new(rng.gen());
+        env
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        archetype::ArchetypeId,
+        player::PlayerConfig,
+        profile::GameProfile,
+        testing::{archetype, catalog, deck},
+        zone::CardZone,
+    };
+    use rand::{rngs::SmallRng, SeedableRng};
+    use std::sync::Arc;
+
+    fn own_deck() -> DeckList {
+        deck(&[("c0", 5), ("c1", 5), ("c2", 5), ("c3", 5)])
+    }
+
+    fn start_game(opponent: &DeckList) -> Environment {
+        let catalog = Arc::new(catalog((0..4).map(|i| archetype(&format!("c{i}")))));
+        let profile = GameProfile {
+            players: vec![
+                PlayerConfig { deck: own_deck() },
+                PlayerConfig {
+                    deck: opponent.clone(),
+                },
+            ],
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let mut env = Environment::new(profile, catalog);
+        while env
+            .state
+            .players
+            .iter()
+            .any(|player| player.hand.is_empty())
+        {
+            let player = env.state.players.player_in_turn().unwrap().id;
+            env.process(player, None);
+        }
+        env
+    }
+
+    /// Returns the archetypes in the hand, and in the hand and the deck of the player.
+    fn archetypes(env: &Environment, player: u8) -> (Vec<ArchetypeId>, Vec<ArchetypeId>) {
+        let player = env.state.players.get(player).unwrap();
+        let mut hand = player
+            .hand
+            .iter()
+            .map(|card| card.archetype().id)
+            .collect::<Vec<_>>();
+        let mut all = player
+            .deck
+            .iter()
+            .map(|card| card.archetype().id)
+            .chain(hand.iter().copied())
+            .collect::<Vec<_>>();
+        hand.sort();
+        all.sort();
+        (hand, all)
+    }
+
+    #[test]
+    fn test_determinize() {
+        let opponent = deck(&[("c0", 10), ("c1", 10)]);
+        let env = start_game(&opponent);
+        let prior = CardPrior::default()
+            .with_deck(0, own_deck())
+            .with_deck(1, opponent);
+
+        let mut rng = SmallRng::seed_from_u64(0);
+        let sampled = (0..8)
+            .map(|_| env.determinize(0, &prior, &mut rng))
+            .collect::<Vec<_>>();
+        for other in &sampled {
+            assert_eq!(other.state.redacted_hash(0), env.state.redacted_hash(0));
+            assert_eq!(archetypes(other, 0), archetypes(&env, 0));
+            assert_eq!(archetypes(other, 1).1, archetypes(&env, 1).1);
+        }
+        assert!(sampled
+            .iter()
+            .any(|other| archetypes(other, 1).0 != archetypes(&env, 1).0));
+    }
+
+    #[test]
+    fn test_determinize_hidden_cards() {
+        // The opponents differ only in the cards the viewer cannot see.
+        let a = start_game(&deck(&[("c0", 20)]));
+        let b = start_game(&deck(&[("c1", 20)]));
+        assert_eq!(a.state.redacted_hash(0), b.state.redacted_hash(0));
+
+        let prior = CardPrior::default();
+        let a = a.determinize(0, &prior, &mut SmallRng::seed_from_u64(0));
+        let b = b.determinize(0, &prior, &mut SmallRng::seed_from_u64(0));
+        assert_eq!(archetypes(&a, 1), archetypes(&b, 1));
+        // Without a deck list, the hidden cards are drawn from the whole catalog.
+        let (_, all) = archetypes(&a, 1);
+        assert!(all.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+}
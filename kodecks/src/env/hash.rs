@@ -0,0 +1,280 @@
+use super::{GameState, LocalEnvironment};
+use crate::{
+    card::CardSnapshot,
+    player::{LocalPlayerState, Player},
+    shard::ShardList,
+    zone::CardZone,
+};
+use bincode::Encode;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// FNV-1a over raw bytes or bincode-encoded values.
+///
+/// The encoding is fixed-endian and does not depend on the pointer width,
+/// so the native server and the wasm client agree on the result.
+pub(crate) struct Fnv1a(u64);
+
+impl Fnv1a {
+    pub(crate) fn new() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) -> &mut Self {
+        for byte in bytes {
+            self.0 = (self.0 ^ *byte as u64).wrapping_mul(FNV_PRIME);
+        }
+        self
+    }
+
+    pub(crate) fn encode<T: Encode>(&mut self, value: &T) -> &mut Self {
+        let bytes = bincode::encode_to_vec(value, bincode::config::standard()).unwrap();
+        self.write(&bytes)
+    }
+
+    fn unordered<I>(&mut self, hashes: I) -> &mut Self
+    where
+        I: IntoIterator<Item = u64>,
+    {
+        let mut hashes = hashes.into_iter().collect::<Vec<_>>();
+        hashes.sort_unstable();
+        self.encode(&(hashes.len() as u64));
+        for hash in hashes {
+            self.write(&hash.to_le_bytes());
+        }
+        self
+    }
+
+    pub(crate) fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+fn hash_card(card: &CardSnapshot) -> u64 {
+    Fnv1a::new().encode(card).finish()
+}
+
+fn hash_shards(shards: &ShardList) -> u64 {
+    Fnv1a::new()
+        .unordered(
+            shards
+                .iter()
+                .filter(|(_, amount)| *amount > 0)
+                .map(|shard| Fnv1a::new().encode(&shard).finish()),
+        )
+        .finish()
+}
+
+fn hash_player(player: &Player) -> u64 {
+    let mut hasher = Fnv1a::new();
+    hasher
+        .encode(&player.id)
+        .encode(&player.stats)
+        .encode(&hash_shards(&player.shards))
+        .encode(&player.counters)
+        .encode(&player.endgame)
+        .encode(&player.abilities)
+        .encode(&player.mulligan);
+    for zone in [
+        &player.deck,
+        &player.hand,
+        &player.graveyard,
+        &player.field,
+        &player.limbo,
+    ] {
+        hasher.unordered(zone.iter().map(|card| hash_card(&card.snapshot())));
+    }
+    hasher.finish()
+}
+
+fn hash_local_player(player: &LocalPlayerState) -> u64 {
+    let mut hasher = Fnv1a::new();
+    hasher
+        .encode(&player.id)
+        .encode(&(player.deck as u64))
+        .encode(&player.stats)
+        .encode(&player.eliminated)
+        .encode(&hash_shards(&player.shards));
+    for zone in [
+        &player.hand,
+        &player.graveyard,
+        &player.field,
+        &player.limbo,
+    ] {
+        hasher.unordered(zone.iter().map(hash_card));
+    }
+    hasher.finish()
+}
+
+impl GameState {
+    /// Returns a hash of the full game state.
+    ///
+    /// Cards within a zone and players are combined without regard to their order.
+    pub fn state_hash(&self) -> u64 {
+        let player_in_turn = self.players.player_in_turn().ok().map(|player| player.id);
+        Fnv1a::new()
+            .encode(&self.turn)
+            .encode(&self.phase)
+            .encode(&self.endgame)
+            .encode(&player_in_turn)
+            .encode(&self.defender)
+            .encode(&self.eliminated)
+            .unordered(self.players.iter().map(hash_player))
+            .finish()
+    }
+
+    /// Returns the hash of the state as seen by `viewer`.
+    ///
+    /// This is the same value as `LocalEnvironment::state_hash` for the viewer's local state.
+    pub fn redacted_hash(&self, viewer: u8) -> u64 {
+        let player_in_turn = self.players.player_in_turn().ok().map(|player| player.id);
+        Fnv1a::new()
+            .encode(&self.turn)
+            .encode(&self.phase)
+            .encode(&self.endgame)
+            .encode(&player_in_turn)
+            .unordered(
+                self.players
+                    .iter()
+                    .map(|player| hash_local_player(&LocalPlayerState::new(player, viewer))),
+            )
+            .finish()
+    }
+}
+
+impl LocalEnvironment {
+    pub fn state_hash(&self) -> u64 {
+        let player_in_turn = self.players.player_in_turn().ok().map(|player| player.id);
+        Fnv1a::new()
+            .encode(&self.turn)
+            .encode(&self.phase)
+            .encode(&self.endgame)
+            .encode(&player_in_turn)
+            .unordered(self.players.iter().map(hash_local_player))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        action::Action,
+        archetype::{CardArchetype, CardAttribute, CardType},
+        catalog::Catalog,
+        env::Environment,
+        player::PlayerConfig,
+        profile::GameProfile,
+        testing::{archetype, catalog, deck},
+        zone::CardZone,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn test_state_hash() {
+        let catalog = Arc::new(Catalog::new(&[]));
+        let profile = GameProfile {
+            players: vec![
+                PlayerConfig {
+                    deck: Default::default(),
+                },
+                PlayerConfig {
+                    deck: Default::default(),
+                },
+            ],
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let env = Environment::new(profile.clone(), catalog.clone());
+        let other = Environment::new(profile, catalog);
+        assert_eq!(env.state.state_hash(), other.state.state_hash());
+    }
+
+    #[test]
+    fn test_diverged_local_view() {
+        let catalog = Arc::new(Catalog::new(&[]));
+        let profile = GameProfile {
+            players: vec![
+                PlayerConfig {
+                    deck: Default::default(),
+                },
+                PlayerConfig {
+                    deck: Default::default(),
+                },
+            ],
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let mut env = Environment::new(profile, catalog);
+        let player = env.state.players.player_in_turn().unwrap().id;
+        env.process(player, None);
+
+        let hash = env.state.redacted_hash(player);
+        let mut local = env.local(player);
+        local.players.get_mut(player).unwrap().stats.life -= 100;
+        assert_ne!(local.state_hash(), hash);
+
+        let mut local = env.local(player);
+        local.turn += 1;
+        assert_ne!(local.state_hash(), hash);
+
+        // The view stays in sync while it follows the server state, and
+        // diverges once the server advances without it.
+        let mut local = env.local(player);
+        local.players.get_mut(player).unwrap().stats.life -= 100;
+        env.state.players.get_mut(player).unwrap().stats.life -= 100;
+        assert_eq!(local.state_hash(), env.state.redacted_hash(player));
+        env.state.players.get_mut(player).unwrap().stats.life -= 100;
+        assert_ne!(local.state_hash(), env.state.redacted_hash(player));
+    }
+
+    #[test]
+    fn test_redacted_hash_matches_local_view() {
+        let creature = CardArchetype {
+            attribute: CardAttribute {
+                card_type: CardType::Creature,
+                power: Some(100),
+                ..Default::default()
+            },
+            ..archetype("crtr")
+        };
+        let catalog = Arc::new(catalog([creature]));
+        let profile = GameProfile {
+            players: vec![
+                PlayerConfig {
+                    deck: deck(&[("crtr", 20)]),
+                },
+                PlayerConfig {
+                    deck: deck(&[("crtr", 20)]),
+                },
+            ],
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let mut env = Environment::new(profile, catalog);
+        let mut hidden = false;
+        while env.state.turn < 6 {
+            for player in env.state.players.iter() {
+                hidden |= !player.hand.is_empty();
+            }
+            for player in env.state.players.iter().map(|player| player.id) {
+                assert_eq!(
+                    env.state.redacted_hash(player),
+                    env.local(player).state_hash()
+                );
+            }
+            let (player, action) = match env.last_available_actions() {
+                Some(actions) => (
+                    actions.player,
+                    match actions.actions.castable_cards().first() {
+                        Some(&card) => Some(Action::CastCard { card }),
+                        None => actions.actions.default_action(&env),
+                    },
+                ),
+                None => (0, None),
+            };
+            assert!(!env.process(player, action).endgame.is_ended());
+        }
+        assert!(hidden);
+    }
+}
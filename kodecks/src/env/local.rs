@@ -2,6 +2,7 @@ use super::{EndgameState, Environment};
 use crate::{
     action::{Action, AvailableActionList, PlayerAvailableActions},
     card::CardSnapshot,
+    clock::PlayerClock,
     env::Report,
     error::ActionError,
     id::ObjectId,
@@ -22,6 +23,9 @@ pub struct LocalEnvironment {
     pub phase: Phase,
     pub stack: Stack<LocalStackItem>,
     pub endgame: EndgameState,
+    /// Remaining time of each player when the game has a time control.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clocks: Vec<PlayerClock>,
 }
 
 impl LocalEnvironment {
@@ -106,6 +110,7 @@ impl Environment {
             stack,
             endgame: self.state.endgame,
             timestamp: self.timestamp,
+            clocks: vec![],
         }
     }
 }
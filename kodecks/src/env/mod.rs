@@ -3,6 +3,7 @@ use crate::{
     archetype::ArchetypeId,
     card::Card,
     catalog::Catalog,
+    command::ActionCommand,
     computed::ComputedSequence,
     continuous::ContinuousEffectList,
     effect::EffectTriggerContext,
@@ -10,13 +11,13 @@ use crate::{
     filter_vec,
     id::{ObjectId, ObjectIdCounter},
     log::GameLog,
-    opcode::OpcodeList,
+    opcode::{Opcode, OpcodeList},
     phase::Phase,
-    player::{Player, PlayerEndgameState, PlayerList, Zone},
+    player::{Player, PlayerEndgameState, PlayerItem, PlayerList, Zone},
     profile::{DebugFlags, GameProfile},
     sequence::CardSequence,
     stack::{Stack, StackItem},
-    zone::{CardZone, ZoneKind},
+    zone::{CardZone, MoveReason, ZoneKind},
 };
 use bincode::{Decode, Encode};
 use rand::rngs::SmallRng;
@@ -28,13 +29,21 @@ use strum::Display;
 use tracing::{error, warn};
 
 mod action;
+mod determinize;
 mod event;
+mod hash;
 mod local;
 mod opcode;
 mod phase;
+mod script;
+mod snapshot;
 mod state;
 
+pub use determinize::CardPrior;
+pub(crate) use hash::Fnv1a;
 pub use local::LocalEnvironment;
+pub use script::*;
+pub use snapshot::*;
 pub use state::*;
 
 #[derive(Clone)]
@@ -45,18 +54,32 @@ pub struct Environment {
     continuous: ContinuousEffectList,
     timestamp: u32,
     last_available_actions: Option<PlayerAvailableActions>,
-    rng: SmallRng,
+    rng: GameRng,
     catalog: Arc<Catalog>,
     obj_counter: ObjectIdCounter,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
+struct GameRng {
+    seed: u64,
+    counter: u64,
+}
+
+impl GameRng {
+    fn new(seed: u64) -> Self {
+        Self { seed, counter: 0 }
+    }
+
+    fn next_rng(&mut self) -> SmallRng {
+        self.counter += 1;
+        SmallRng::seed_from_u64(self.seed ^ self.counter.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+    }
+}
+
 impl Environment {
     pub fn new(profile: GameProfile, catalog: Arc<Catalog>) -> Self {
         let debug = profile.debug.unwrap_or_default();
-        let mut rng: SmallRng = profile
-            .rng_seed
-            .map(SmallRng::seed_from_u64)
-            .unwrap_or_else(SmallRng::from_entropy);
+        let mut rng = GameRng::new(profile.rng_seed.unwrap_or_else(rand::random));
 
         let mut obj_counter = ObjectIdCounter::default();
         let players = profile
@@ -83,7 +106,7 @@ impl Environment {
         let current_player = if debug.no_player_shuffle {
             players.first().as_ref().unwrap().id
         } else {
-            players.choose(&mut rng).unwrap().id
+            players.choose(&mut rng.next_rng()).unwrap().id
         };
 
         Environment {
@@ -94,6 +117,8 @@ impl Environment {
                 phase: Phase::Standby,
                 players: PlayerList::new(current_player, players),
                 endgame: EndgameState::InProgress,
+                defender: None,
+                eliminated: vec![],
             },
             opcodes: VecDeque::new(),
             stack: Stack::new(),
@@ -138,7 +163,7 @@ impl Environment {
         let report = match (&self.last_available_actions, action.clone()) {
             (_, Some(Action::Concede)) => self.process_turn(player, Some(Action::Concede)),
             (None, _) => self.process_turn(player, None),
-            (Some(available), Some(action)) if available.validate(player, &action) => {
+            (Some(_), Some(action)) if self.accepts(player, Some(&action)) => {
                 self.process_turn(player, Some(action))
             }
             _ => {
@@ -155,14 +180,38 @@ impl Environment {
         report
     }
 
+    /// Makes the player lose because they have run out of time.
+    ///
+    /// [`Action::Timeout`] is not accepted from players;
+    /// the host keeping the clock calls this when it expires.
+    pub fn timeout(&mut self, player: u8) -> Report {
+        let report = self.resign(player, EndgameReason::Timeout);
+        self.last_available_actions = report.available_actions.clone();
+        report
+    }
+
+    fn resign(&mut self, player: u8, reason: EndgameReason) -> Report {
+        if let Ok(loser) = self.state.players.get_mut(player) {
+            loser
+                .endgame
+                .get_or_insert(PlayerEndgameState::Lose(reason));
+        }
+        let logs = self.check_game_condition();
+        let available_actions = self
+            .last_available_actions
+            .clone()
+            .filter(|actions| actions.player != player && !self.state.endgame.is_ended());
+        Report {
+            available_actions,
+            logs,
+            endgame: self.state.endgame,
+            timestamp: self.timestamp,
+        }
+    }
+
     fn process_turn(&mut self, player: u8, mut action: Option<Action>) -> Report {
         let action = match action.take() {
-            Some(Action::Concede) => {
-                if let Ok(loser) = self.state.players.get_mut(player) {
-                    loser.endgame = Some(PlayerEndgameState::Lose(EndgameReason::Concede));
-                }
-                Some(Action::Concede)
-            }
+            Some(Action::Concede) => return self.resign(player, EndgameReason::Concede),
             Some(Action::DebugCommand { commands })
                 if self.state.debug.flags.contains(DebugFlags::DEBUG_COMMAND) =>
             {
@@ -196,46 +245,25 @@ impl Environment {
                 _ => None,
             };
 
-            match (item.handler)(&mut ctx, action) {
+            let mut effect = source.effect();
+            let result = effect.resolve(item.id, &item.args, &mut ctx, action);
+            let (continuous, _) = ctx.into_inner();
+            if let Ok(source) = self.state.find_card_mut(item.source) {
+                source.set_effect(effect);
+            }
+            match result {
                 Ok(report) => {
-                    let (continuous, _) = ctx.into_inner();
                     self.continuous.extend(continuous);
 
-                    let mut list = vec![];
-                    for command in report.commands {
-                        match command.into_opcodes(self) {
-                            Ok(codes) => {
-                                list.extend(codes.into_iter().filter(|item| !item.is_empty()))
-                            }
-                            Err(err) => {
-                                error!("Error processing command: {:?}", err);
-                            }
-                        }
-                    }
-
                     let mut logs = filter_vec![targeted,];
-
-                    for item in list {
-                        for opcode in item {
-                            match self.execute(opcode.clone()) {
-                                Ok(log) => logs.extend(log),
-                                Err(err) => {
-                                    error!("Error executing opcode: {:?} {:?}", err, opcode);
-                                }
-                            }
-                        }
-                    }
+                    logs.extend(self.execute_commands(report.commands));
 
                     self.continuous.update();
                     if let Err(err) = self.compute_effects() {
                         error!("Error computing effects: {:?}", err);
                     }
 
-                    if self.check_game_condition() {
-                        if let EndgameState::Finished { winner, reason } = self.state.endgame {
-                            logs.push(GameLog::GameEnded { winner, reason });
-                        }
-                    }
+                    logs.extend(self.check_game_condition());
 
                     if !report
                         .available_actions
@@ -288,11 +316,7 @@ impl Environment {
             error!("Error computing effects: {:?}", err);
         }
 
-        if self.check_game_condition() {
-            if let EndgameState::Finished { winner, reason } = self.state.endgame {
-                logs.push(GameLog::GameEnded { winner, reason });
-            }
-        }
+        logs.extend(self.check_game_condition());
 
         let available_actions = if next_empty {
             self.available_actions()
@@ -308,6 +332,40 @@ impl Environment {
         }
     }
 
+    fn execute_commands(&mut self, commands: Vec<ActionCommand>) -> Vec<GameLog> {
+        let mut list = vec![];
+        for command in commands {
+            match command.into_opcodes(self) {
+                Ok(codes) => list.extend(codes.into_iter().filter(|item| !item.is_empty())),
+                Err(err) => {
+                    error!("Error processing command: {:?}", err);
+                }
+            }
+        }
+
+        let mut logs = vec![];
+        for item in list {
+            for opcode in item {
+                match self.execute(opcode.clone()) {
+                    Ok(log) => logs.extend(log),
+                    Err(err) => {
+                        error!("Error executing opcode: {:?} {:?}", err, opcode);
+                    }
+                }
+            }
+        }
+        logs
+    }
+
+    pub fn accepts(&self, player: u8, action: Option<&Action>) -> bool {
+        match (&self.last_available_actions, action) {
+            (_, Some(Action::Timeout)) => false,
+            (_, Some(Action::Concede)) | (None, _) => true,
+            (Some(available), Some(action)) => available.validate(player, action),
+            (Some(_), None) => false,
+        }
+    }
+
     pub fn last_available_actions(&self) -> Option<&PlayerAvailableActions> {
         self.last_available_actions.as_ref()
     }
@@ -316,9 +374,64 @@ impl Environment {
         self.state.endgame
     }
 
-    pub fn check_game_condition(&mut self) -> bool {
+    /// Puts the field cards of an eliminated player into the graveyard
+    /// and removes the continuous effects of their cards.
+    fn clear_eliminated_player(&mut self, player: u8) -> Vec<GameLog> {
+        let Ok(state) = self.state.players.get(player) else {
+            return vec![];
+        };
+        let sources = [
+            &state.deck,
+            &state.hand,
+            &state.graveyard,
+            &state.field,
+            &state.limbo,
+        ]
+        .into_iter()
+        .flat_map(|zone| zone.iter().map(|card| card.id()))
+        .collect::<Vec<_>>();
+        let field = state
+            .field
+            .iter()
+            .map(|card| (card.id(), card.owner()))
+            .collect::<Vec<_>>();
+
+        let mut logs = vec![];
+        for (card, owner) in field {
+            let opcode = Opcode::MoveCard {
+                card,
+                from: Zone {
+                    player,
+                    kind: ZoneKind::Field,
+                },
+                to: Zone {
+                    player: owner,
+                    kind: ZoneKind::Graveyard,
+                },
+                reason: MoveReason::Move,
+            };
+            match self.execute(opcode) {
+                Ok(log) => logs.extend(log),
+                Err(err) => {
+                    error!("Error removing eliminated player's card: {:?}", err);
+                }
+            }
+        }
+
+        self.continuous.remove_sources(&sources);
+        self.continuous.update();
+        if let Err(err) = self.compute_effects() {
+            error!("Error computing effects: {:?}", err);
+        }
+        logs
+    }
+
+    /// Updates the endgame state and returns the logs of newly eliminated players and the game result.
+    ///
+    /// The game continues as long as two or more players remain.
+    pub fn check_game_condition(&mut self) -> Vec<GameLog> {
         if self.state.endgame.is_ended() {
-            return false;
+            return vec![];
         }
 
         for player in self
@@ -332,6 +445,26 @@ impl Environment {
             }
         }
 
+        let mut logs = vec![];
+        let mut last_reason = None;
+        let mut newly_eliminated = vec![];
+        for player in self.state.players.iter() {
+            if let Some(PlayerEndgameState::Lose(reason)) = player.endgame {
+                if !self.state.eliminated.contains(&player.id) {
+                    self.state.eliminated.push(player.id);
+                    newly_eliminated.push(player.id);
+                    logs.push(GameLog::PlayerEliminated {
+                        player: player.id,
+                        reason,
+                    });
+                    last_reason = Some(reason);
+                }
+            }
+        }
+        for player in newly_eliminated {
+            logs.extend(self.clear_eliminated_player(player));
+        }
+
         let won_players = self
             .state
             .players
@@ -342,40 +475,42 @@ impl Environment {
             })
             .collect::<Vec<_>>();
 
-        let lost_players = self
+        let remaining_players = self
             .state
             .players
             .iter()
-            .filter_map(|player| match player.endgame {
-                Some(PlayerEndgameState::Lose(reason)) => Some((player, reason)),
-                _ => None,
-            })
+            .filter(|player| !player.is_eliminated())
             .collect::<Vec<_>>();
 
-        let new_condition = if won_players.is_empty() && lost_players.is_empty() {
-            EndgameState::InProgress
-        } else if let [(won, reason)] = won_players.as_slice() {
+        let new_condition = if let [(won, reason)] = won_players.as_slice() {
             EndgameState::Finished {
                 winner: Some(won.id),
                 reason: *reason,
             }
-        } else if let [(lost, reason)] = lost_players.as_slice() {
+        } else if !won_players.is_empty() {
             EndgameState::Finished {
-                winner: self.state.players.next_id(lost.id).ok(),
-                reason: *reason,
+                winner: None,
+                reason: EndgameReason::SimultaneousEnd,
             }
-        } else {
+        } else if let (Some(reason), [remaining]) = (last_reason, remaining_players.as_slice()) {
+            EndgameState::Finished {
+                winner: Some(remaining.id),
+                reason,
+            }
+        } else if remaining_players.is_empty() {
             EndgameState::Finished {
                 winner: None,
                 reason: EndgameReason::SimultaneousEnd,
             }
+        } else {
+            EndgameState::InProgress
         };
-        if self.state.endgame != new_condition {
+
+        if let EndgameState::Finished { winner, reason } = new_condition {
             self.state.endgame = new_condition;
-            true
-        } else {
-            false
+            logs.push(GameLog::GameEnded { winner, reason });
         }
+        logs
     }
 
     pub fn generate_card_token(&self, player: u8, token: ObjectId, archetype: ArchetypeId) -> Card {
@@ -424,6 +559,7 @@ pub enum EndgameReason {
     LifeZero,
     DeckOut,
     SimultaneousEnd,
+    Timeout,
 }
 
 #[derive(Debug, Clone)]
@@ -433,3 +569,164 @@ pub struct Report {
     pub endgame: EndgameState,
     pub timestamp: u32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        archetype::{CardArchetype, CardAttribute, CardType},
+        card::CardEntry,
+        continuous::ContinuousItem,
+        deck::{DeckItem, DeckList},
+        effect::EffectId,
+        player::PlayerConfig,
+        target::Target,
+    };
+    use std::sync::LazyLock;
+
+    #[test]
+    fn test_elimination() {
+        let catalog = Arc::new(Catalog::new(&[]));
+        let profile = GameProfile {
+            players: (0..3)
+                .map(|_| PlayerConfig {
+                    deck: Default::default(),
+                })
+                .collect(),
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let mut env = Environment::new(profile, catalog);
+
+        let report = env.process(1, Some(Action::Concede));
+        assert_eq!(report.endgame, EndgameState::InProgress);
+        assert!(matches!(
+            report.logs.as_slice(),
+            [GameLog::PlayerEliminated {
+                player: 1,
+                reason: EndgameReason::Concede
+            }]
+        ));
+        assert_eq!(env.state.players.next_id(0).unwrap(), 2);
+        assert_eq!(env.state.players.next_id(2).unwrap(), 0);
+
+        let report = env.process(0, Some(Action::Concede));
+        assert_eq!(
+            report.endgame,
+            EndgameState::Finished {
+                winner: Some(2),
+                reason: EndgameReason::Concede
+            }
+        );
+        assert_eq!(env.state.eliminated, vec![1, 0]);
+    }
+
+    #[test]
+    fn test_timeout() {
+        let catalog = Arc::new(Catalog::new(&[]));
+        let profile = GameProfile {
+            players: (0..3)
+                .map(|_| PlayerConfig {
+                    deck: Default::default(),
+                })
+                .collect(),
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let mut env = Environment::new(profile, catalog);
+
+        assert!(!env.accepts(1, Some(&Action::Timeout)));
+        env.process(1, Some(Action::Timeout));
+        assert!(env.state.eliminated.is_empty());
+
+        let report = env.timeout(1);
+        assert_eq!(report.endgame, EndgameState::InProgress);
+        assert!(matches!(
+            report.logs.as_slice(),
+            [GameLog::PlayerEliminated {
+                player: 1,
+                reason: EndgameReason::Timeout
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_eliminated_player_cards() {
+        static CREATURE: LazyLock<CardArchetype> = LazyLock::new(|| CardArchetype {
+            id: ArchetypeId::new("test"),
+            name: "Test".to_string(),
+            safe_name: "test".to_string(),
+            attribute: CardAttribute {
+                card_type: CardType::Creature,
+                power: Some(100),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let catalog = Arc::new(Catalog::new(&[|| &CREATURE]));
+        let deck = DeckList {
+            cards: std::iter::repeat_n(
+                DeckItem {
+                    card: CardEntry {
+                        archetype_id: CREATURE.id,
+                        style: 0,
+                    },
+                    base_id: None,
+                },
+                20,
+            )
+            .collect(),
+            ..Default::default()
+        };
+        let profile = GameProfile {
+            players: (0..3)
+                .map(|_| PlayerConfig { deck: deck.clone() })
+                .collect(),
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let mut env = Environment::new(profile, catalog);
+
+        let card = env
+            .state
+            .players
+            .get(1)
+            .unwrap()
+            .deck
+            .iter()
+            .next()
+            .unwrap()
+            .id();
+        env.execute(Opcode::MoveCard {
+            card,
+            from: Zone {
+                player: 1,
+                kind: ZoneKind::Deck,
+            },
+            to: Zone {
+                player: 1,
+                kind: ZoneKind::Field,
+            },
+            reason: MoveReason::Move,
+        })
+        .unwrap();
+        let source = env.state.find_card(card).unwrap();
+        env.continuous.add(ContinuousItem::new(
+            source,
+            EffectId::new("test"),
+            vec![],
+            Target::Player(0),
+        ));
+
+        let report = env.process(1, Some(Action::Concede));
+        assert_eq!(report.endgame, EndgameState::InProgress);
+        assert!(report.logs.iter().any(|log| matches!(
+            log,
+            GameLog::CardMoved { card: moved, .. } if moved.id == card
+        )));
+        let player = env.state.players.get(1).unwrap();
+        assert_eq!(player.field.len(), 0);
+        assert!(player.graveyard.get(card).is_some());
+        assert_eq!(env.continuous.iter().count(), 0);
+    }
+}
@@ -184,9 +184,36 @@ impl Environment {
             }
             Opcode::ShuffleDeck { player } => {
                 let player = self.state.players.get_mut(player)?;
-                player.deck.shuffle(&mut self.obj_counter, &mut self.rng);
+                player
+                    .deck
+                    .shuffle(&mut self.obj_counter, &mut self.rng.next_rng());
                 Ok(vec![GameLog::DeckShuffled { player: player.id }])
             }
+            Opcode::Mulligan { player, cards } => {
+                let player = self.state.players.get_mut(player)?;
+                let mut amount = 0;
+                for card in cards {
+                    if let Some(mut card) = player.hand.remove(card) {
+                        card.set_zone(Zone::new(player.id, ZoneKind::Deck));
+                        card.renew_id(&mut self.obj_counter);
+                        player.deck.add_bottom(card);
+                        amount += 1;
+                    }
+                }
+                player.mulligan.count += 1;
+                Ok(vec![GameLog::MulliganTaken {
+                    player: player.id,
+                    amount,
+                }])
+            }
+            Opcode::KeepHand { player } => {
+                let player = self.state.players.get_mut(player)?;
+                player.mulligan.decided = true;
+                Ok(vec![GameLog::HandKept {
+                    player: player.id,
+                    mulligans: player.mulligan.count,
+                }])
+            }
             Opcode::TriggerEvent {
                 source,
                 target,
@@ -243,7 +270,12 @@ impl Environment {
                 }
                 Ok(logs)
             }
+            Opcode::SetDefender { player } => {
+                self.state.defender = Some(self.state.players.get(player)?.id);
+                Ok(vec![])
+            }
             Opcode::ResetBattleState => {
+                self.state.defender = None;
                 for player in self.state.players.iter_mut() {
                     for card in player.field.iter_mut() {
                         if card.battle_state().is_none() {
@@ -10,8 +10,9 @@ use crate::{
     id::TimedCardId,
     opcode::{Opcode, OpcodeList},
     phase::Phase,
-    player::Zone,
+    player::{PlayerItem, Zone},
     profile::DebugFlags,
+    regulation::MulliganPolicy,
     target::Target,
     zone::{CardZone, MoveReason, ZoneKind},
 };
@@ -49,12 +50,78 @@ impl Environment {
                 .take(self.state.regulation.initial_hand_size as usize);
                 filter_vec![opcodes,]
             }),
-            Some(OpcodeList::new(vec![Opcode::ChangeTurn {
+            Some(OpcodeList::new(vec![
+                if self.state.regulation.mulligan == MulliganPolicy::Disabled {
+                    Opcode::ChangeTurn {
+                        turn: 1,
+                        player: self.state.players.player_in_turn()?.id,
+                        phase: Phase::Standby,
+                    }
+                } else {
+                    Opcode::ChangePhase {
+                        phase: Phase::Mulligan,
+                    }
+                }
+            ],)),
+        ])
+    }
+
+    fn process_mulligan(&self, action: Option<Action>) -> Result<Vec<OpcodeList>, ActionError> {
+        let player = if let Some(player) = self.state.mulligan_player() {
+            player
+        } else {
+            return Ok(vec![OpcodeList::new(vec![Opcode::ChangeTurn {
                 turn: 1,
                 player: self.state.players.player_in_turn()?.id,
-                phase: self.state.phase,
-            },],)),
-        ])
+                phase: Phase::Standby,
+            }])]);
+        };
+
+        let draw = |amount: usize| {
+            iter::repeat_n(
+                OpcodeList::new(vec![Opcode::DrawCard { player: player.id }]),
+                amount,
+            )
+        };
+        let keep = OpcodeList::new(vec![Opcode::KeepHand { player: player.id }]);
+        match (action, self.state.regulation.mulligan) {
+            (Some(Action::Mulligan { .. }), MulliganPolicy::Redraw) => {
+                let amount = self
+                    .state
+                    .regulation
+                    .initial_hand_size
+                    .saturating_sub(player.mulligan.count + 1);
+                Ok(filter_vec![
+                    Some(OpcodeList::new(vec![
+                        Opcode::Mulligan {
+                            player: player.id,
+                            cards: player.hand.iter().map(|card| card.id()).collect(),
+                        },
+                        Opcode::ShuffleDeck { player: player.id },
+                    ])),
+                    draw(amount as usize),
+                ])
+            }
+            (Some(Action::Mulligan { cards }), MulliganPolicy::Bottom) => {
+                let cards = cards
+                    .iter()
+                    .filter_map(|card| player.hand.get(*card))
+                    .map(|card| card.id())
+                    .collect::<Vec<_>>();
+                let amount = cards.len();
+                Ok(filter_vec![
+                    Some(OpcodeList::new(vec![Opcode::Mulligan {
+                        player: player.id,
+                        cards,
+                    }])),
+                    draw(amount),
+                    Some(keep),
+                ])
+            }
+            (Some(Action::Mulligan { .. }), MulliganPolicy::Disabled)
+            | (Some(Action::Continue), _) => Ok(vec![keep]),
+            _ => Ok(vec![]),
+        }
     }
 
     pub fn process_player_phase(
@@ -62,6 +129,9 @@ impl Environment {
         action: Option<Action>,
     ) -> Result<Vec<OpcodeList>, ActionError> {
         if self.state.turn == 0 {
+            if matches!(self.state.phase, Phase::Mulligan) {
+                return self.process_mulligan(action);
+            }
             return self.initialize();
         }
 
@@ -101,7 +171,19 @@ impl Environment {
             .state
             .players
             .get(self.state.players.player_in_turn()?.id)?;
+        if player_in_turn.is_eliminated() {
+            return Ok(vec![OpcodeList::new(vec![
+                Opcode::ResetBattleState,
+                Opcode::ChangeTurn {
+                    turn: self.state.turn + 1,
+                    player: self.state.players.next_id(player_in_turn.id)?,
+                    phase: Phase::Standby,
+                },
+            ])]);
+        }
+
         match self.state.phase {
+            Phase::Mulligan => self.process_mulligan(action),
             Phase::Standby => Ok(vec![OpcodeList::new(vec![Opcode::ChangePhase {
                 phase: Phase::Draw,
             }])]),
@@ -202,19 +284,30 @@ impl Environment {
                                 .flatten(),
                         ]
                     }
-                    Some(Action::Attack { attackers }) => {
+                    Some(Action::Attack { attackers, target }) => {
+                        let defender = match target {
+                            Some(Target::Player(player)) => player,
+                            Some(target) => {
+                                return Err(ActionError::InvalidAttackTarget { target })
+                            }
+                            None => self.state.players.next_id(player_in_turn.id)?,
+                        };
+                        if defender == player_in_turn.id
+                            || self.state.players.get(defender)?.is_eliminated()
+                        {
+                            return Err(ActionError::InvalidAttackTarget {
+                                target: Target::Player(defender),
+                            });
+                        }
                         let attackers = attackers
                             .iter()
                             .map(|id| player_in_turn.field.get(*id).unwrap())
                             .collect::<Vec<_>>();
-                        let opcodes = attackers
-                            .iter()
-                            .flat_map(|card| {
-                                vec![Opcode::SetBattleState {
-                                    card: card.id(),
-                                    state: Some(FieldBattleState::Attacking),
-                                }]
-                            })
+                        let opcodes = iter::once(Opcode::SetDefender { player: defender })
+                            .chain(attackers.iter().map(|card| Opcode::SetBattleState {
+                                card: card.id(),
+                                state: Some(FieldBattleState::Attacking),
+                            }))
                             .collect::<Vec<_>>();
                         vec![
                             OpcodeList::new(opcodes),
@@ -234,12 +327,11 @@ impl Environment {
                 Ok(logs)
             }
             Phase::Block => {
-                let active_player = &self
-                    .state
-                    .players
-                    .next_player(self.state.players.player_in_turn()?.id)?;
+                let active_player = self.state.defender()?;
 
-                if player_in_turn.field.attacking_cards().next().is_none() {
+                if player_in_turn.field.attacking_cards().next().is_none()
+                    || active_player.is_eliminated()
+                {
                     Ok(vec![OpcodeList::new(filter_vec![Some(
                         Opcode::ChangePhase {
                             phase: Phase::Battle,
@@ -315,10 +407,13 @@ impl Environment {
                 }
             }
             Phase::Battle => {
-                let target = &self
-                    .state
-                    .players
-                    .next_player(self.state.players.player_in_turn()?.id)?;
+                let target = self.state.defender()?;
+                if target.is_eliminated() {
+                    return Ok(vec![OpcodeList::new(vec![
+                        Opcode::ResetBattleState,
+                        Opcode::ChangePhase { phase: Phase::End },
+                    ])]);
+                }
                 let attacker = player_in_turn.field.attacking_cards().min_by_key(|card| {
                     (
                         if target.field.find_blocker(card.id()).is_some() {
@@ -0,0 +1,169 @@
+use super::Environment;
+use crate::{
+    command::ActionCommand,
+    dsl::script::{
+        error::{Error, EvalError},
+        exp::{Exp, ExpContext, ExpExt, ExpParams},
+        value::Value,
+    },
+    effect::{EffectReport, EffectTriggerContext},
+    id::ObjectId,
+    log::GameLog,
+};
+use std::str::FromStr;
+use tracing::error;
+
+/// The result of evaluating a script expression outside of a card effect.
+#[derive(Debug, Default)]
+pub struct ScriptOutput {
+    pub values: Vec<Value>,
+    /// Commands and actions found in `values`, in the order they were returned.
+    pub report: EffectReport,
+}
+
+impl Environment {
+    /// Evaluates a script expression as if it were resolved by an effect of `source`.
+    ///
+    /// Values describing commands or an `EffectReport` are collected into the output,
+    /// but nothing is applied to the game.
+    /// Stack and continuous effects pushed by the expression are discarded.
+    pub fn eval_script(
+        &mut self,
+        source: ObjectId,
+        script: &str,
+    ) -> Result<ScriptOutput, EvalError> {
+        let mut params = ExpParams::default();
+        self.eval_script_with(source, script, &mut params)
+            .map_err(|error| EvalError {
+                error,
+                trace: params.trace,
+                execution_limit: params.execution_limit,
+                script: script.into(),
+            })
+    }
+
+    fn eval_script_with(
+        &mut self,
+        source: ObjectId,
+        script: &str,
+        params: &mut ExpParams,
+    ) -> Result<ScriptOutput, Error> {
+        let exp = Exp::from_str(script)?;
+        let source = self
+            .state
+            .find_card(source)
+            .map_err(|err| Error::Custom(err.to_string()))?;
+        let mut ctx = EffectTriggerContext::new(&self.state, &mut self.obj_counter, source);
+        let input = Value::default();
+        let values = exp.eval(&mut ExpContext::new(&mut ctx, &input, params))?;
+
+        let mut report = EffectReport::default();
+        for value in &values {
+            let Ok(value) = serde_json::Value::try_from(value.clone()) else {
+                continue;
+            };
+            if let Ok(command) = serde_json::from_value::<ActionCommand>(value.clone()) {
+                report.commands.push(command);
+            } else if let Ok(commands) = serde_json::from_value::<Vec<_>>(value.clone()) {
+                report.commands.extend(commands);
+            } else if let Ok(other) = serde_json::from_value::<EffectReport>(value) {
+                report.commands.extend(other.commands);
+                if other.available_actions.is_some() {
+                    report.available_actions = other.available_actions;
+                }
+            }
+        }
+        Ok(ScriptOutput { values, report })
+    }
+
+    /// Applies commands as if they were returned by a resolved effect.
+    pub fn apply_commands(&mut self, commands: Vec<ActionCommand>) -> Vec<GameLog> {
+        let mut logs = self.execute_commands(commands);
+        self.continuous.update();
+        if let Err(err) = self.compute_effects() {
+            error!("Error computing effects: {:?}", err);
+        }
+        logs.extend(self.check_game_condition());
+        logs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        archetype::{ArchetypeId, CardArchetype},
+        card::CardEntry,
+        catalog::Catalog,
+        deck::{DeckItem, DeckList},
+        player::PlayerConfig,
+        profile::GameProfile,
+        zone::CardZone,
+    };
+    use std::sync::{Arc, LazyLock};
+
+    fn archetype() -> &'static CardArchetype {
+        static ARCHETYPE: LazyLock<CardArchetype> = LazyLock::new(|| CardArchetype {
+            id: ArchetypeId::new("test"),
+            name: "Test".to_string(),
+            safe_name: "test".to_string(),
+            ..Default::default()
+        });
+        &ARCHETYPE
+    }
+
+    #[test]
+    fn test_eval_script() {
+        let deck = DeckList {
+            cards: vec![DeckItem {
+                card: CardEntry {
+                    archetype_id: ArchetypeId::new("test"),
+                    style: 0,
+                },
+                base_id: None,
+            }],
+            ..Default::default()
+        };
+        let profile = GameProfile {
+            players: vec![PlayerConfig { deck: deck.clone() }, PlayerConfig { deck }],
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let mut env = Environment::new(profile, Arc::new(Catalog::new(&[archetype])));
+        let source = env
+            .state
+            .players
+            .get(0)
+            .unwrap()
+            .deck
+            .iter()
+            .next()
+            .unwrap()
+            .id();
+        let life = env.state.players.get(1).unwrap().stats.life;
+
+        let output = env
+            .eval_script(
+                source,
+                "$source | controller | opponent | inflict_damage(.; 100)",
+            )
+            .unwrap();
+        assert_eq!(output.values.len(), 1);
+        assert_eq!(output.report.commands.len(), 1);
+        assert_eq!(env.state.players.get(1).unwrap().stats.life, life);
+
+        let logs = env.apply_commands(output.report.commands);
+        assert!(logs.iter().any(|log| matches!(
+            log,
+            GameLog::DamageTaken {
+                player: 1,
+                amount: 100
+            }
+        )));
+        assert_eq!(env.state.players.get(1).unwrap().stats.life, life - 100);
+
+        let err = env.eval_script(source, "1 | $missing").unwrap_err();
+        assert_eq!(err.error, Error::UndefinedVariable);
+        assert!(err.trace.span.is_some());
+    }
+}
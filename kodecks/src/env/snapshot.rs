@@ -0,0 +1,418 @@
+use super::{Environment, GameRng, GameState};
+use crate::{
+    action::PlayerAvailableActions,
+    archetype::ArchetypeId,
+    card::Card,
+    catalog::Catalog,
+    continuous::ContinuousEffectList,
+    id::ObjectIdCounter,
+    opcode::{Opcode, OpcodeList},
+    stack::{Stack, StackItem},
+    zone::CardZone,
+};
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, sync::Arc};
+use thiserror::Error;
+
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+#[derive(Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct EnvironmentSnapshot {
+    pub version: u16,
+    pub catalog_version: u64,
+    state: GameState,
+    opcodes: VecDeque<OpcodeList>,
+    stack: Stack<StackItem>,
+    continuous: ContinuousEffectList,
+    timestamp: u32,
+    last_available_actions: Option<PlayerAvailableActions>,
+    rng: GameRng,
+    obj_counter: ObjectIdCounter,
+}
+
+impl EnvironmentSnapshot {
+    pub fn encode(&self) -> Vec<u8> {
+        bincode::encode_to_vec(self, bincode::config::standard()).unwrap()
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, SnapshotError> {
+        let (snapshot, _): (Self, _) =
+            bincode::decode_from_slice(data, bincode::config::standard())
+                .map_err(|err| SnapshotError::InvalidFormat(err.to_string()))?;
+        Ok(snapshot)
+    }
+}
+
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    #[error("Invalid snapshot format: {0}")]
+    InvalidFormat(String),
+    #[error("Unsupported snapshot version: {version}")]
+    UnsupportedVersion { version: u16 },
+    #[error("Catalog version mismatch: snapshot: {expected:016x} catalog: {actual:016x}")]
+    CatalogMismatch { expected: u64, actual: u64 },
+    #[error("Archetype not found: {id}")]
+    ArchetypeNotFound { id: ArchetypeId },
+}
+
+impl Environment {
+    pub fn snapshot(&self) -> EnvironmentSnapshot {
+        EnvironmentSnapshot {
+            version: SNAPSHOT_VERSION,
+            catalog_version: self.catalog.version(),
+            state: self.state.clone(),
+            opcodes: self.opcodes.clone(),
+            stack: self.stack.clone(),
+            continuous: self.continuous.clone(),
+            timestamp: self.timestamp,
+            last_available_actions: self.last_available_actions.clone(),
+            rng: self.rng,
+            obj_counter: self.obj_counter,
+        }
+    }
+
+    pub fn restore(
+        snapshot: EnvironmentSnapshot,
+        catalog: Arc<Catalog>,
+    ) -> Result<Self, SnapshotError> {
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion {
+                version: snapshot.version,
+            });
+        }
+        let actual = catalog.version();
+        if snapshot.catalog_version != actual {
+            return Err(SnapshotError::CatalogMismatch {
+                expected: snapshot.catalog_version,
+                actual,
+            });
+        }
+
+        let archetypes = snapshot
+            .stack
+            .iter()
+            .map(|item| item.archetype)
+            .chain(snapshot.continuous.iter().map(|item| item.archetype()));
+        for id in archetypes {
+            if !catalog.contains(id) {
+                return Err(SnapshotError::ArchetypeNotFound { id });
+            }
+        }
+
+        let mut state = snapshot.state;
+        for player in state.players.iter_mut() {
+            for zone in [
+                &mut player.deck,
+                &mut player.hand,
+                &mut player.graveyard,
+                &mut player.field,
+                &mut player.limbo,
+            ] {
+                for card in zone.iter_mut() {
+                    relink(card, &catalog)?;
+                }
+            }
+        }
+
+        let mut opcodes = snapshot.opcodes;
+        for opcode in opcodes.iter_mut().flat_map(|list| list.iter_mut()) {
+            if let Opcode::GenerateCardToken { card } = opcode {
+                relink(card, &catalog)?;
+            }
+        }
+
+        Ok(Environment {
+            state,
+            opcodes,
+            stack: snapshot.stack,
+            continuous: snapshot.continuous,
+            timestamp: snapshot.timestamp,
+            last_available_actions: snapshot.last_available_actions,
+            rng: snapshot.rng,
+            catalog,
+            obj_counter: snapshot.obj_counter,
+        })
+    }
+}
+
+fn relink(card: &mut Card, catalog: &Catalog) -> Result<(), SnapshotError> {
+    let id = card.archetype().id;
+    if !catalog.contains(id) {
+        return Err(SnapshotError::ArchetypeNotFound { id });
+    }
+    card.relink(catalog);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        action::{Action, AvailableAction},
+        archetype::{CardArchetype, CardAttribute, CardType, EffectFactory},
+        card::CardEntry,
+        deck::{DeckItem, DeckList},
+        dsl::script::value::Value,
+        effect::{
+            ContinuousCardEffectContext, Effect, EffectActivateContext, EffectId, EffectReport,
+            EffectTriggerContext,
+        },
+        env::Report,
+        event::{CardEvent, EventFilter},
+        id::TimedCardId,
+        player::PlayerConfig,
+        profile::GameProfile,
+        target::Target,
+    };
+    use std::sync::LazyLock;
+
+    /// Asks its controller to select a card when cast,
+    /// then powers up the selected card by the amount stored in the stack item.
+    #[derive(Clone)]
+    struct Booster;
+
+    impl Effect for Booster {
+        fn event_filter(&self) -> EventFilter {
+            EventFilter::CASTED
+        }
+
+        fn activate(
+            &mut self,
+            _event: CardEvent,
+            ctx: &mut EffectActivateContext,
+        ) -> anyhow::Result<()> {
+            ctx.trigger_stack("main");
+            Ok(())
+        }
+
+        fn trigger(&mut self, id: EffectId, ctx: &mut EffectTriggerContext) -> anyhow::Result<()> {
+            ctx.push_stack(id, vec![Value::from(serde_json::json!(200))]);
+            Ok(())
+        }
+
+        fn resolve(
+            &mut self,
+            _id: EffectId,
+            args: &[Value],
+            ctx: &mut EffectTriggerContext,
+            action: Option<Action>,
+        ) -> anyhow::Result<EffectReport> {
+            if let Some(Action::SelectCard { card }) = action {
+                ctx.push_continuous("powerup", args.to_vec(), Target::Card(card.id));
+                return Ok(EffectReport::default());
+            }
+            Ok(
+                EffectReport::default().with_available_actions(PlayerAvailableActions {
+                    player: ctx.source().controller(),
+                    actions: vec![AvailableAction::SelectCard {
+                        cards: vec![ctx.source().timed_id()],
+                    }]
+                    .into_iter()
+                    .collect(),
+                    instructions: None,
+                    message_dialog: None,
+                }),
+            )
+        }
+
+        fn apply_card(
+            &self,
+            _id: EffectId,
+            args: &[Value],
+            ctx: &mut ContinuousCardEffectContext,
+        ) -> anyhow::Result<bool> {
+            let amount = match args.first() {
+                Some(Value::Constant(amount)) => amount.as_u64(),
+                _ => None,
+            };
+            if ctx.target.id() == ctx.source.id() {
+                if let (Some(power), Some(amount)) = (&mut ctx.computed.power, amount) {
+                    power.add(amount as u32);
+                }
+            }
+            Ok(true)
+        }
+    }
+
+    static BOOSTER: LazyLock<CardArchetype> = LazyLock::new(|| CardArchetype {
+        id: ArchetypeId::new("bstr"),
+        name: "Booster".to_string(),
+        safe_name: "booster".to_string(),
+        attribute: CardAttribute {
+            card_type: CardType::Creature,
+            power: Some(100),
+            ..Default::default()
+        },
+        effect: EffectFactory::Native(|| Box::new(Booster)),
+    });
+
+    static CREATURE: LazyLock<CardArchetype> = LazyLock::new(|| CardArchetype {
+        id: ArchetypeId::new("crtr"),
+        name: "Creature".to_string(),
+        safe_name: "creature".to_string(),
+        attribute: CardAttribute {
+            card_type: CardType::Creature,
+            power: Some(300),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    fn deck_of(archetype: &CardArchetype) -> DeckList {
+        DeckList {
+            cards: std::iter::repeat_n(
+                DeckItem {
+                    card: CardEntry {
+                        archetype_id: archetype.id,
+                        style: 0,
+                    },
+                    base_id: None,
+                },
+                20,
+            )
+            .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn restored(env: &Environment) -> Environment {
+        let data = env.snapshot().encode();
+        let snapshot = EnvironmentSnapshot::decode(&data).unwrap();
+        Environment::restore(snapshot, env.catalog.clone()).unwrap()
+    }
+
+    fn powers(env: &Environment) -> Vec<u32> {
+        env.state
+            .players
+            .iter()
+            .flat_map(|player| player.field.iter())
+            .filter_map(|card| card.computed().power.map(|power| power.value()))
+            .collect()
+    }
+
+    /// Takes the first castable card or the default action of the pending decision.
+    fn play(env: &mut Environment) -> Report {
+        let (player, action) = match env.last_available_actions() {
+            Some(actions) => (
+                actions.player,
+                match actions.actions.castable_cards().first() {
+                    Some(&card) => Some(Action::CastCard { card }),
+                    None => actions.actions.default_action(env),
+                },
+            ),
+            None => (0, None),
+        };
+        env.process(player, action)
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let catalog = Arc::new(Catalog::new(&[|| &CREATURE]));
+        let profile = GameProfile {
+            players: vec![
+                PlayerConfig {
+                    deck: deck_of(&CREATURE),
+                },
+                PlayerConfig {
+                    deck: deck_of(&CREATURE),
+                },
+            ],
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let mut env = Environment::new(profile, catalog.clone());
+
+        // Stop in the middle of a later turn, with creatures on the field and a decision pending.
+        while env.state.turn < 4
+            || env.last_available_actions().is_none()
+            || powers(&env).is_empty()
+        {
+            assert!(!play(&mut env).endgame.is_ended());
+        }
+
+        let mut restored = restored(&env);
+        assert_eq!(restored.state.state_hash(), env.state.state_hash());
+        assert_eq!(
+            restored
+                .last_available_actions()
+                .map(|actions| actions.player),
+            env.last_available_actions().map(|actions| actions.player)
+        );
+        for _ in 0..50 {
+            let expected = play(&mut env);
+            let actual = play(&mut restored);
+            assert_eq!(expected.timestamp, actual.timestamp);
+            assert_eq!(expected.logs.len(), actual.logs.len());
+            assert_eq!(expected.endgame, actual.endgame);
+            assert_eq!(restored.state.state_hash(), env.state.state_hash());
+        }
+
+        let mut snapshot = env.snapshot();
+        snapshot.catalog_version ^= 1;
+        assert!(matches!(
+            Environment::restore(snapshot, catalog),
+            Err(SnapshotError::CatalogMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_snapshot_restore_effects() {
+        let catalog = Arc::new(Catalog::new(&[|| &BOOSTER]));
+        let profile = GameProfile {
+            players: vec![
+                PlayerConfig {
+                    deck: deck_of(&BOOSTER),
+                },
+                PlayerConfig {
+                    deck: deck_of(&BOOSTER),
+                },
+            ],
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let mut env = Environment::new(profile, catalog);
+        let mut player = env.state.players.player_in_turn().unwrap().id;
+        let mut action = None;
+
+        // Cast a card and stop while its stack item waits for the selection.
+        let selection = loop {
+            let report = env.process(player, action.take());
+            assert!(!report.endgame.is_ended());
+            let Some(actions) = report.available_actions else {
+                continue;
+            };
+            player = actions.player;
+            let selectable = actions.actions.selectable_cards();
+            if !selectable.is_empty() && !env.stack.is_empty() {
+                break selectable[0];
+            }
+            action = match actions.actions.castable_cards().first() {
+                Some(&card) => Some(Action::CastCard { card }),
+                None => actions.actions.default_action(&env),
+            };
+        };
+        assert_eq!(env.stack.iter().count(), 1);
+
+        let mut restored_env = restored(&env);
+        assert_eq!(restored_env.stack.iter().count(), 1);
+        let action = Action::SelectCard { card: selection };
+        let expected = env.process(player, Some(action.clone()));
+        let actual = restored_env.process(player, Some(action));
+        assert_eq!(expected.logs.len(), actual.logs.len());
+        assert_eq!(env.continuous.iter().count(), 1);
+        assert_eq!(restored_env.continuous.iter().count(), 1);
+        assert!(powers(&env).contains(&300));
+        assert_eq!(powers(&restored_env), powers(&env));
+
+        // The continuous effect keeps applying after a restore.
+        let mut restored_env = restored(&env);
+        for _ in 0..5 {
+            let expected = env.process(player, None);
+            let actual = restored_env.process(player, None);
+            assert_eq!(expected.timestamp, actual.timestamp);
+            assert_eq!(powers(&restored_env), powers(&env));
+        }
+        assert!(powers(&restored_env).contains(&300));
+    }
+}
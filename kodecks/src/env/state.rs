@@ -5,24 +5,28 @@ use tracing::debug;
 use crate::{
     action::PlayerAvailableActions,
     card::Card,
-    dsl::script::{
-        error::Error,
-        exp::ExpParams,
-        value::{Constant, Value},
+    dsl::{
+        script::{
+            error::Error,
+            exp::{ExpEnv, ExpParams},
+            value::{Constant, CustomType, Value},
+        },
+        SmallStr,
     },
     error::ActionError,
-    id::{CardId, ObjectId},
+    id::{CardId, ObjectId, TimedCardId},
     log::GameLog,
     phase::Phase,
-    player::{Player, PlayerList, PlayerScore, Zone},
+    player::{Player, PlayerItem, PlayerList, PlayerScore, Zone},
     profile::DebugConfig,
     regulation::Regulation,
     score::Score,
+    zone::CardZone,
 };
 
 use super::{EndgameState, LocalEnvironment};
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct GameState {
     pub regulation: Regulation,
     pub debug: DebugConfig,
@@ -30,6 +34,12 @@ pub struct GameState {
     pub phase: Phase,
     pub players: PlayerList<Player>,
     pub endgame: EndgameState,
+    /// The player being attacked in the current battle.
+    #[serde(default)]
+    pub defender: Option<u8>,
+    /// Players that have lost the game, in the order of elimination.
+    #[serde(default)]
+    pub eliminated: Vec<u8>,
 }
 
 impl GameState {
@@ -68,6 +78,23 @@ impl GameState {
         &self.players
     }
 
+    /// Returns the next player to decide on a mulligan, in turn order.
+    pub fn mulligan_player(&self) -> Option<&Player> {
+        self.players
+            .iter()
+            .find(|player| !player.mulligan.decided && !player.is_eliminated())
+    }
+
+    /// Returns the player being attacked, or the next player if no attack has been declared.
+    pub fn defender(&self) -> Result<&Player, ActionError> {
+        let player_in_turn = self.players.player_in_turn()?.id;
+        let defender = match self.defender {
+            Some(defender) => defender,
+            None => self.players.next_id(player_in_turn)?,
+        };
+        self.players.get(defender)
+    }
+
     pub fn get_var(&self, name: &str) -> Option<Value> {
         match name {
             "$turn" => Some(self.turn.into()),
@@ -97,11 +124,96 @@ impl GameState {
                 debug!("{args}");
                 Ok(vec![input.clone()])
             }
+            "deck" | "hand" | "graveyard" | "field" => {
+                let players = match args.as_slice() {
+                    [] => self.players.iter().collect::<Vec<_>>(),
+                    [player] => {
+                        let player = player.as_player().ok_or(Error::InvalidConversion)?;
+                        vec![self
+                            .players
+                            .get(player)
+                            .map_err(|_| Error::InvalidConversion)?]
+                    }
+                    _ => return Err(Error::InvalidArgumentCount),
+                };
+                let cards = players
+                    .into_iter()
+                    .flat_map(|player| match name {
+                        "deck" => player.deck.iter().collect::<Vec<_>>(),
+                        "hand" => player.hand.iter().collect(),
+                        "graveyard" => player.graveyard.iter().collect(),
+                        _ => player.field.iter().collect(),
+                    })
+                    .map(|card| card.timed_id().into())
+                    .collect();
+                Ok(vec![Value::Array(cards)])
+            }
+            "opponent" => {
+                let target = match args.as_slice() {
+                    [] => input,
+                    [target] => target,
+                    _ => return Err(Error::InvalidArgumentCount),
+                };
+                let player = if let Some(card) = target.as_card() {
+                    self.find_card(card)
+                        .map_err(|_| Error::InvalidConversion)?
+                        .controller()
+                } else {
+                    target.as_player().ok_or(Error::InvalidConversion)?
+                };
+                // Every opponent still in the game, in turn order.
+                Ok(self
+                    .players
+                    .opponents(player)
+                    .map(|opponent| Value::Custom(CustomType::Player(opponent.id)))
+                    .collect())
+            }
+            "name" | "zone" | "controller" | "owner" | "color" | "card_type" | "creature_type"
+            | "cost" | "power" | "shards" | "abilities" | "anon_abilities" | "is_token"
+            | "life" => {
+                let target = match args.as_slice() {
+                    [] => input,
+                    [target] => target,
+                    _ => return Err(Error::InvalidArgumentCount),
+                };
+                Ok(vec![target.index_str(
+                    &SmallStr::from_bytes_lossy(name.as_bytes()),
+                    self,
+                )?])
+            }
             _ => Err(Error::UndefinedFilter),
         }
     }
 }
 
+/// A read-only script environment without a source card.
+impl ExpEnv for GameState {
+    fn get_var(&self, name: &str) -> Option<Value> {
+        GameState::get_var(self, name)
+    }
+
+    fn get_card<T>(&self, id: T) -> Option<&Card>
+    where
+        T: CardId + Copy,
+    {
+        self.find_card(id).ok()
+    }
+
+    fn get_players(&self) -> Option<&PlayerList<Player>> {
+        Some(&self.players)
+    }
+
+    fn invoke(
+        &mut self,
+        name: &str,
+        args: Vec<Value>,
+        params: &ExpParams,
+        input: &Value,
+    ) -> Result<Vec<Value>, Error> {
+        GameState::invoke(self, name, args, params, input)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct LocalGameState {
     pub env: LocalEnvironment,
@@ -162,6 +274,7 @@ mod tests {
                         limbo: vec![],
                         shards,
                         stats: Default::default(),
+                        eliminated: false,
                     }],
                 ),
                 phase: Phase::Main,
@@ -172,6 +285,7 @@ mod tests {
                 .into_iter()
                 .collect(),
                 endgame: EndgameState::InProgress,
+                clocks: vec![],
             },
             logs: vec![GameLog::LifeChanged { player, life: 100 }],
             available_actions: Some(PlayerAvailableActions::new(player)),
@@ -184,4 +298,84 @@ mod tests {
         let serialized = bincode::encode_to_vec(&game_state, bincode::config::standard()).unwrap();
         bincode::decode_from_slice::<LocalGameState, _>(&serialized, config).unwrap();
     }
+
+    #[test]
+    fn test_script_builtins() {
+        use crate::{
+            catalog::Catalog,
+            dsl::script::exp::{Exp, ExpContext, ExpExt},
+            env::Environment,
+            player::PlayerConfig,
+            profile::GameProfile,
+        };
+        use std::{str::FromStr, sync::Arc};
+
+        let profile = GameProfile {
+            players: vec![
+                PlayerConfig {
+                    deck: Default::default(),
+                },
+                PlayerConfig {
+                    deck: Default::default(),
+                },
+            ],
+            ..Default::default()
+        };
+        let mut env = Environment::new(profile, Arc::new(Catalog::new(&[])));
+        let input = Value::default();
+        let mut params = ExpParams::default();
+        let mut ctx = ExpContext::new(&mut env.state, &input, &mut params);
+
+        let exp = Exp::from_str("opponent(0)").unwrap();
+        assert_eq!(
+            exp.eval(&mut ctx),
+            Ok(vec![Value::Custom(CustomType::Player(1))])
+        );
+
+        let exp = Exp::from_str("[field, hand(1)] | map(length)").unwrap();
+        assert_eq!(
+            exp.eval(&mut ctx),
+            Ok(vec![Value::Array(vec![0.into(), 0.into()])])
+        );
+
+        let exp = Exp::from_str("hand(1; 2)").unwrap();
+        assert_eq!(exp.eval(&mut ctx), Err(Error::InvalidArgumentCount));
+    }
+
+    #[test]
+    fn test_script_opponents() {
+        use crate::{
+            action::Action,
+            catalog::Catalog,
+            dsl::script::exp::{Exp, ExpContext, ExpExt},
+            env::Environment,
+            player::PlayerConfig,
+            profile::GameProfile,
+        };
+        use std::{str::FromStr, sync::Arc};
+
+        let profile = GameProfile {
+            players: (0..4)
+                .map(|_| PlayerConfig {
+                    deck: Default::default(),
+                })
+                .collect(),
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let mut env = Environment::new(profile, Arc::new(Catalog::new(&[])));
+        env.process(2, Some(Action::Concede));
+        let input = Value::default();
+        let mut params = ExpParams::default();
+        let mut ctx = ExpContext::new(&mut env.state, &input, &mut params);
+
+        let exp = Exp::from_str("[opponent(1)]").unwrap();
+        assert_eq!(
+            exp.eval(&mut ctx),
+            Ok(vec![Value::Array(vec![
+                Value::Custom(CustomType::Player(3)),
+                Value::Custom(CustomType::Player(0)),
+            ])])
+        );
+    }
 }
@@ -1,14 +1,19 @@
 use crate::{
+    archetype::ArchetypeId,
     color::Color,
     id::{ObjectId, TimedObjectId},
+    target::Target,
 };
 use bincode::{Decode, Encode};
 use fluent_bundle::FluentArgs;
 use fluent_content::Request;
 use serde::{Deserialize, Serialize};
+use strum::{EnumDiscriminants, EnumIter};
 use thiserror::Error;
 
-#[derive(Debug, Error, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash)]
+#[derive(
+    Debug, Error, Clone, PartialEq, Eq, EnumIter, Serialize, Deserialize, Encode, Decode, Hash,
+)]
 #[serde(tag = "name", rename_all = "snake_case")]
 pub enum Error {
     #[error("Failed to connect the server")]
@@ -25,8 +30,12 @@ pub enum Error {
         client: String,
         requirement: String,
     },
-    #[error("Invalid deck")]
-    InvalidDeck,
+    #[error("Invalid deck: {violations:?}")]
+    InvalidDeck { violations: Vec<DeckViolation> },
+    #[error("Invalid number of players: {players}")]
+    InvalidPlayerCount { players: u8 },
+    #[error("A player left the room before the game started")]
+    PlayerUnavailable,
 }
 
 impl<'a> From<Error> for Request<'a, FluentArgs<'a>> {
@@ -36,7 +45,9 @@ impl<'a> From<Error> for Request<'a, FluentArgs<'a>> {
             Error::FailedToConnectServer => "error-failed-to-connect-server",
             Error::ClientVersionOutdated { .. } => "error-client-version-outdated",
             Error::ServerVersionOutdated { .. } => "error-server-version-outdated",
-            Error::InvalidDeck => "error-invalid-deck",
+            Error::InvalidDeck { .. } => "error-invalid-deck",
+            Error::InvalidPlayerCount { .. } => "error-invalid-player-count",
+            Error::PlayerUnavailable => "error-player-unavailable",
         };
         match error {
             Error::ClientVersionOutdated {
@@ -57,6 +68,9 @@ impl<'a> From<Error> for Request<'a, FluentArgs<'a>> {
                 args.set("client", client.to_string());
                 args.set("requirement", requirement.to_string());
             }
+            Error::InvalidPlayerCount { players } => {
+                args.set("players", players);
+            }
             _ => {}
         }
         Request {
@@ -67,6 +81,101 @@ impl<'a> From<Error> for Request<'a, FluentArgs<'a>> {
     }
 }
 
+/// A reason why a deck is not allowed by a regulation or a card pool.
+#[derive(
+    Debug,
+    Error,
+    Clone,
+    PartialEq,
+    Eq,
+    EnumDiscriminants,
+    Serialize,
+    Deserialize,
+    Encode,
+    Decode,
+    Hash,
+)]
+#[strum_discriminants(name(DeckViolationKind), derive(EnumIter))]
+#[serde(tag = "name", rename_all = "snake_case")]
+pub enum DeckViolation {
+    #[error("Too few cards: {size} < {min}")]
+    TooFewCards { size: u32, min: u8 },
+    #[error("Too many cards: {size} > {max}")]
+    TooManyCards { size: u32, max: u8 },
+    #[error("Too many copies of {card}: {count} > {max}")]
+    TooManyCopies {
+        card: ArchetypeId,
+        count: u32,
+        max: u8,
+    },
+    #[error("Card not in catalog: {card}")]
+    UnknownCard { card: ArchetypeId },
+    #[error("Card banned: {card}")]
+    CardBanned { card: ArchetypeId },
+    #[error("Card limited: {card}: {count} > {max}")]
+    CardLimited {
+        card: ArchetypeId,
+        count: u32,
+        max: u8,
+    },
+    #[error("Token in deck: {card}")]
+    TokenInDeck { card: ArchetypeId },
+}
+
+impl DeckViolation {
+    /// Returns the card concerned by the violation, if any.
+    pub fn card(&self) -> Option<ArchetypeId> {
+        match self {
+            DeckViolation::TooFewCards { .. } | DeckViolation::TooManyCards { .. } => None,
+            DeckViolation::TooManyCopies { card, .. }
+            | DeckViolation::UnknownCard { card }
+            | DeckViolation::CardBanned { card }
+            | DeckViolation::CardLimited { card, .. }
+            | DeckViolation::TokenInDeck { card } => Some(*card),
+        }
+    }
+}
+
+/// The `card` argument is the archetype id. Clients may replace it with the localized name.
+impl<'a> From<DeckViolation> for Request<'a, FluentArgs<'a>> {
+    fn from(violation: DeckViolation) -> Request<'a, FluentArgs<'a>> {
+        let mut args = FluentArgs::new();
+        if let Some(card) = violation.card() {
+            args.set("card", card.to_string());
+        }
+        let id = match violation {
+            DeckViolation::TooFewCards { size, min } => {
+                args.set("size", size);
+                args.set("min", min);
+                "error-deck-too-few-cards"
+            }
+            DeckViolation::TooManyCards { size, max } => {
+                args.set("size", size);
+                args.set("max", max);
+                "error-deck-too-many-cards"
+            }
+            DeckViolation::TooManyCopies { count, max, .. } => {
+                args.set("count", count);
+                args.set("max", max);
+                "error-deck-too-many-copies"
+            }
+            DeckViolation::UnknownCard { .. } => "error-deck-unknown-card",
+            DeckViolation::CardBanned { .. } => "error-deck-card-banned",
+            DeckViolation::CardLimited { count, max, .. } => {
+                args.set("count", count);
+                args.set("max", max);
+                "error-deck-card-limited"
+            }
+            DeckViolation::TokenInDeck { .. } => "error-deck-token-in-deck",
+        };
+        Request {
+            id,
+            attr: None,
+            args: Some(args),
+        }
+    }
+}
+
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum ActionError {
     #[error("Insufficient shards: {color} {amount}")]
@@ -85,4 +194,6 @@ pub enum ActionError {
     InvalidObjectId,
     #[error("Target lost: {target}")]
     TargetLost { target: TimedObjectId },
+    #[error("Invalid attack target: {target:?}")]
+    InvalidAttackTarget { target: Target },
 }
@@ -1,7 +1,7 @@
 use crate::error::ActionError;
 use bincode::{Decode, Encode};
 use core::fmt;
-use serde::{de, ser, Serialize};
+use serde::{de, ser, Deserialize, Serialize};
 use std::num::NonZeroU32;
 
 const MAX_RESERVED_ID: u32 = 100;
@@ -59,7 +59,8 @@ impl<'de> de::Deserialize<'de> for ObjectId {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
+#[serde(transparent)]
 pub struct ObjectIdCounter(u32);
 
 impl Default for ObjectIdCounter {
@@ -3,8 +3,10 @@
 pub mod ability;
 pub mod action;
 pub mod archetype;
+pub mod booster;
 pub mod card;
 pub mod catalog;
+pub mod clock;
 pub mod color;
 pub mod command;
 pub mod computed;
@@ -29,12 +31,15 @@ pub mod pool;
 pub mod prelude;
 pub mod profile;
 pub mod regulation;
+pub mod replay;
 pub mod scenario;
 pub mod score;
 pub mod sequence;
 pub mod shard;
 pub mod stack;
 pub mod target;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
 pub mod text;
 pub mod variable;
 pub mod zone;
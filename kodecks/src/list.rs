@@ -4,10 +4,13 @@ use crate::{
     sequence::CardSequence,
     zone::CardZone,
 };
+use bincode::{Decode, Encode};
 use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
-pub struct CardList<T> {
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+#[serde(transparent)]
+pub struct CardList<T: 'static> {
     cards: Vec<T>,
 }
 
@@ -69,6 +72,10 @@ where
     fn add_top(&mut self, card: Card) {
         self.cards.push(card.into());
     }
+
+    fn add_bottom(&mut self, card: Card) {
+        self.cards.insert(0, card.into());
+    }
 }
 
 impl CardList<Card> {
@@ -5,6 +5,45 @@ use crate::{
 use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
+/// Ids of the messages used by clients to display game logs, with the names of their variables.
+pub const LOG_MESSAGES: &[(&str, &[&str])] = &[
+    ("log-game-started", &[]),
+    ("log-game-ended", &["winner"]),
+    ("log-game-draw", &[]),
+    ("log-player-eliminated", &["player"]),
+    ("log-mulligan-taken", &["player", "amount"]),
+    ("log-hand-kept", &["player"]),
+    ("log-turn-changed", &["player", "turn"]),
+    ("log-phase-changed", &["phase"]),
+    ("log-life-changed", &["player", "life"]),
+    ("log-damage-taken", &["player", "amount"]),
+    ("log-deck-shuffled", &["player"]),
+    ("log-effect-activated", &["source"]),
+    ("log-card-drawn", &["player", "card"]),
+    ("log-card-played", &["player", "card"]),
+    ("log-card-destroyed-to-graveyard", &["player", "card"]),
+    ("log-card-discarded", &["player", "card"]),
+    (
+        "log-card-moved",
+        &[
+            "player",
+            "card",
+            "from-player",
+            "from-zone",
+            "to-player",
+            "to-zone",
+        ],
+    ),
+    ("log-card-targeted", &["source", "target"]),
+    ("log-shards-earned", &["player", "color", "amount"]),
+    ("log-shards-spent", &["player", "color", "amount"]),
+    ("log-card-token-generated", &["card"]),
+    ("log-card-token-destroyed", &["card"]),
+    ("log-attack-declared", &["attacker"]),
+    ("log-creature-attacked-creature", &["attacker", "blocker"]),
+    ("log-creature-attacked-player", &["attacker", "player"]),
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 #[serde(rename_all = "snake_case")]
 pub enum GameLog {
@@ -13,6 +52,10 @@ pub enum GameLog {
         winner: Option<u8>,
         reason: EndgameReason,
     },
+    PlayerEliminated {
+        player: u8,
+        reason: EndgameReason,
+    },
     TurnChanged {
         turn: u16,
         player: u8,
@@ -67,6 +110,14 @@ pub enum GameLog {
     DeckShuffled {
         player: u8,
     },
+    MulliganTaken {
+        player: u8,
+        amount: u8,
+    },
+    HandKept {
+        player: u8,
+        mulligans: u8,
+    },
     EffectActivated {
         source: CardSnapshot,
         id: EffectId,
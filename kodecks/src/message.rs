@@ -16,6 +16,13 @@ pub struct Message {
     pub variables: VariableList,
 }
 
+/// Ids of the messages sent by the engine, with the names of their variables.
+pub const ENGINE_MESSAGES: &[(&str, &[&str])] = &[
+    ("message-mulligan", &[]),
+    ("message-discard", &[]),
+    ("message-discard-excess-cards", &["maxHandSize"]),
+];
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Encode, Decode)]
 pub struct MessageBox {
     pub message: Message,
@@ -9,9 +9,12 @@ use crate::{
     target::Target,
     zone::MoveReason,
 };
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+#[serde(tag = "name", rename_all = "snake_case")]
 pub enum Opcode {
     StartGame,
     ChangeTurn {
@@ -58,6 +61,13 @@ pub enum Opcode {
     ShuffleDeck {
         player: u8,
     },
+    Mulligan {
+        player: u8,
+        cards: Vec<ObjectId>,
+    },
+    KeepHand {
+        player: u8,
+    },
     TriggerEvent {
         source: ObjectId,
         target: ObjectId,
@@ -75,6 +85,9 @@ pub enum Opcode {
         card: ObjectId,
         state: Option<FieldBattleState>,
     },
+    SetDefender {
+        player: u8,
+    },
     ResetBattleState,
     InflictDamage {
         player: u8,
@@ -82,7 +95,8 @@ pub enum Opcode {
     },
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+#[serde(transparent)]
 pub struct OpcodeList(Vec<Opcode>);
 
 impl OpcodeList {
@@ -100,6 +114,10 @@ impl OpcodeList {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Opcode> {
+        self.0.iter_mut()
+    }
 }
 
 impl IntoIterator for OpcodeList {
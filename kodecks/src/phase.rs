@@ -3,10 +3,10 @@ use bincode::{Decode, Encode};
 use fluent_bundle::FluentArgs;
 use fluent_content::Request;
 use serde::{Deserialize, Serialize};
-use strum::Display;
+use strum::{Display, EnumIter};
 use tinystr::tinystr;
 
-#[derive(Debug, Clone, Copy, Display, Serialize, Deserialize, Encode, Decode)]
+#[derive(Debug, Clone, Copy, Display, EnumIter, Serialize, Deserialize, Encode, Decode)]
 #[serde(rename_all = "snake_case")]
 pub enum Phase {
     Standby,
@@ -16,6 +16,7 @@ pub enum Phase {
     Block,
     Battle,
     End,
+    Mulligan,
 }
 
 impl<'a> From<Phase> for Request<'a, FluentArgs<'a>> {
@@ -28,6 +29,7 @@ impl<'a> From<Phase> for Request<'a, FluentArgs<'a>> {
             Phase::Block => "phase-block",
             Phase::Battle => "phase-battle",
             Phase::End => "phase-end",
+            Phase::Mulligan => "phase-mulligan",
         };
         Request {
             id,
@@ -47,6 +49,7 @@ impl From<Phase> for SmallStr {
             Phase::Block => tinystr!(32, "block"),
             Phase::Battle => tinystr!(32, "battle"),
             Phase::End => tinystr!(32, "end"),
+            Phase::Mulligan => tinystr!(32, "mulligan"),
         }
     }
 }
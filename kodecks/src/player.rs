@@ -124,6 +124,7 @@ where
         PlayerListMutIter::new(self.player_in_turn, &mut self.players)
     }
 
+    /// Returns the next player in turn order, skipping eliminated players.
     pub fn next_id(&self, id: u8) -> Result<u8, ActionError> {
         if self.players.is_empty() {
             return Err(ActionError::PlayerNotFound { player: id });
@@ -133,8 +134,12 @@ where
             .iter()
             .position(|player| player.id() == id)
             .unwrap_or(0);
-        let next = (pos + 1) % self.players.len();
-        Ok(self.players[next].id())
+        let len = self.players.len();
+        let next = (1..len)
+            .map(|offset| &self.players[(pos + offset) % len])
+            .find(|player| !player.is_eliminated())
+            .unwrap_or(&self.players[(pos + 1) % len]);
+        Ok(next.id())
     }
 
     pub fn next_player(&self, id: u8) -> Result<&T, ActionError> {
@@ -142,6 +147,12 @@ where
         self.get(next)
     }
 
+    /// Returns the players that have not been eliminated except `id`, in turn order.
+    pub fn opponents(&self, id: u8) -> impl Iterator<Item = &T> {
+        PlayerListIter::new(id, &self.players)
+            .filter(move |player| player.id() != id && !player.is_eliminated())
+    }
+
     pub fn push(&mut self, player: T) {
         self.players.push(player);
     }
@@ -153,9 +164,13 @@ where
 
 pub trait PlayerItem {
     fn id(&self) -> u8;
+
+    fn is_eliminated(&self) -> bool {
+        false
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct Player {
     pub id: u8,
     pub deck: CardList<Card>,
@@ -168,12 +183,18 @@ pub struct Player {
     pub counters: PlayerCounters,
     pub endgame: Option<PlayerEndgameState>,
     pub abilities: AbilityList<PlayerAbility>,
+    #[serde(default)]
+    pub mulligan: MulliganState,
 }
 
 impl PlayerItem for Player {
     fn id(&self) -> u8 {
         self.id
     }
+
+    fn is_eliminated(&self) -> bool {
+        matches!(self.endgame, Some(PlayerEndgameState::Lose(_)))
+    }
 }
 
 impl Player {
@@ -190,6 +211,7 @@ impl Player {
             counters: PlayerCounters::default(),
             endgame: None,
             abilities: AbilityList::default(),
+            mulligan: MulliganState::default(),
         }
     }
 
@@ -303,12 +325,18 @@ pub enum PlayerEndgameState {
     Lose(EndgameReason),
 }
 
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
 pub struct PlayerCounters {
     pub draw: u16,
     pub free_casted: u16,
 }
 
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
+pub struct MulliganState {
+    pub count: u8,
+    pub decided: bool,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
 pub struct Zone {
     pub player: u8,
@@ -331,12 +359,18 @@ pub struct LocalPlayerState {
     pub limbo: Vec<CardSnapshot>,
     pub shards: ShardList,
     pub stats: PlayerStats,
+    #[serde(default)]
+    pub eliminated: bool,
 }
 
 impl PlayerItem for LocalPlayerState {
     fn id(&self) -> u8 {
         self.id
     }
+
+    fn is_eliminated(&self) -> bool {
+        self.eliminated
+    }
 }
 
 impl LocalPlayerState {
@@ -366,6 +400,7 @@ impl LocalPlayerState {
                 .collect(),
             shards: state.shards.clone(),
             stats: state.stats,
+            eliminated: state.is_eliminated(),
         }
     }
 
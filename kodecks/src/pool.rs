@@ -1,4 +1,4 @@
-use crate::{archetype::ArchetypeId, catalog::Catalog, deck::DeckList};
+use crate::{archetype::ArchetypeId, catalog::Catalog, deck::DeckList, error::DeckViolation};
 use bincode::{
     de::{BorrowDecoder, Decoder},
     enc::Encoder,
@@ -6,7 +6,7 @@ use bincode::{
     BorrowDecode, Decode, Encode,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::iter;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash)]
 pub struct CardPool(Vec<(CardPoolEntry, PoolStatus)>);
@@ -16,38 +16,83 @@ impl CardPool {
         Self::default()
     }
 
-    pub fn verify(&self, deck: &DeckList, catalog: &Catalog) -> bool {
-        let mut count = HashMap::new();
-        for item in &deck.cards {
-            let entry = count.entry(item.card.archetype_id).or_insert(0);
-            *entry += 1;
+    /// Returns a pool which allows each card as many times as it appears in `cards`.
+    pub fn from_cards<I>(cards: I) -> Self
+    where
+        I: IntoIterator<Item = ArchetypeId>,
+    {
+        let mut counts: Vec<(ArchetypeId, u8)> = vec![];
+        for card in cards {
+            match counts.iter_mut().find(|(id, _)| *id == card) {
+                Some((_, count)) => *count = count.saturating_add(1),
+                None => counts.push((card, 1)),
+            }
         }
-        for (entry, status) in &self.0 {
-            let status: u8 = (*status).into();
-            match entry {
-                CardPoolEntry::CoreSet => {
-                    for (id, value) in count.clone() {
-                        if catalog.contains(id) {
-                            if value > status {
-                                return false;
-                            } else {
-                                count.remove(&id);
-                            }
-                        }
-                    }
+        counts
+            .into_iter()
+            .map(|(card, count)| {
+                // 255 is reserved for `PoolStatus::Legal` in the serialized form.
+                let status = PoolStatus::Limited(count.min(254));
+                (CardPoolEntry::Card(card), status)
+            })
+            .collect()
+    }
+
+    /// Returns a pool for a rotating format, where the core set and the latest `sets` sets are legal.
+    pub fn rotation(catalog: &Catalog, sets: usize) -> Self {
+        let latest = catalog
+            .sets
+            .iter()
+            .skip(1)
+            .rev()
+            .take(sets)
+            .map(|set| CardPoolEntry::Set(set.code().to_string()));
+        iter::once(CardPoolEntry::CoreSet)
+            .chain(latest)
+            .map(|entry| (entry, PoolStatus::Legal))
+            .collect()
+    }
+
+    /// Returns the reasons why the deck is not allowed. The deck is valid if the list is empty.
+    ///
+    /// The first entry matching a card decides its status.
+    pub fn verify(&self, deck: &DeckList, catalog: &Catalog) -> Vec<DeckViolation> {
+        let mut violations = vec![];
+        for (card, count) in deck.count_cards() {
+            let status = self.0.iter().find_map(|(entry, status)| match entry {
+                CardPoolEntry::CoreSet
+                    if catalog.sets.first().is_some_and(|set| set.contains(card)) =>
+                {
+                    Some(*status)
+                }
+                CardPoolEntry::Set(code)
+                    if catalog.set(code).is_some_and(|set| set.contains(card)) =>
+                {
+                    Some(*status)
+                }
+                CardPoolEntry::Card(id) if *id == card => Some(*status),
+                _ => None,
+            });
+            match status {
+                Some(PoolStatus::Legal) => {}
+                Some(PoolStatus::Limited(max)) if count <= max as u32 => {}
+                Some(PoolStatus::Limited(max)) => {
+                    violations.push(DeckViolation::CardLimited { card, count, max })
                 }
-                CardPoolEntry::Card(id) => {
-                    if let Some(&value) = count.get(id) {
-                        if value > status {
-                            return false;
-                        } else {
-                            count.remove(id);
-                        }
-                    }
+                Some(PoolStatus::Illegal) => violations.push(DeckViolation::CardBanned { card }),
+                None if catalog.contains(card) => {
+                    violations.push(DeckViolation::CardBanned { card })
                 }
+                None => violations.push(DeckViolation::UnknownCard { card }),
             }
         }
-        count.is_empty()
+        violations
+    }
+}
+
+impl FromIterator<(CardPoolEntry, PoolStatus)> for CardPool {
+    fn from_iter<I: IntoIterator<Item = (CardPoolEntry, PoolStatus)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
     }
 }
 
@@ -57,9 +102,12 @@ impl Default for CardPool {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode, Hash)]
 pub enum CardPoolEntry {
+    /// The cards of the first set of the catalog.
     CoreSet,
+    /// The cards of the set with the code.
+    Set(String),
     Card(ArchetypeId),
 }
 
@@ -129,3 +177,74 @@ impl<'de> BorrowDecode<'de> for PoolStatus {
         Ok(PoolStatus::from(value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        archetype::CardArchetype,
+        card::CardEntry,
+        catalog::{CardSet, SetInfo},
+        deck::DeckItem,
+    };
+    use std::sync::LazyLock;
+
+    #[test]
+    fn test_rotation() {
+        static CARDS: LazyLock<Vec<CardArchetype>> = LazyLock::new(|| {
+            ["core", "old", "new"]
+                .iter()
+                .map(|name| CardArchetype {
+                    id: ArchetypeId::new(name),
+                    name: name.to_string(),
+                    safe_name: name.to_string(),
+                    ..Default::default()
+                })
+                .collect()
+        });
+        let set = |code: &str, release, card: fn() -> &'static CardArchetype| {
+            CardSet::new(&[card]).with_info(SetInfo {
+                code: code.to_string(),
+                name: code.to_uppercase(),
+                release,
+            })
+        };
+        let catalog = Catalog::from_sets([
+            set("new", 2, || &CARDS[2]),
+            set("core", 0, || &CARDS[0]),
+            set("old", 1, || &CARDS[1]),
+        ])
+        .unwrap();
+        let deck = DeckList {
+            cards: ["core", "old", "new"]
+                .iter()
+                .map(|id| DeckItem {
+                    card: CardEntry {
+                        archetype_id: ArchetypeId::new(id),
+                        style: 0,
+                    },
+                    base_id: None,
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        let pool = CardPool::rotation(&catalog, 1);
+        assert_eq!(
+            pool,
+            CardPool::from_iter([
+                (CardPoolEntry::CoreSet, PoolStatus::Legal),
+                (CardPoolEntry::Set("new".to_string()), PoolStatus::Legal),
+            ])
+        );
+        assert_eq!(
+            pool.verify(&deck, &catalog),
+            vec![DeckViolation::CardBanned {
+                card: ArchetypeId::new("old")
+            }]
+        );
+        assert!(CardPool::rotation(&catalog, 2)
+            .verify(&deck, &catalog)
+            .is_empty());
+    }
+}
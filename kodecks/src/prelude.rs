@@ -1,5 +1,5 @@
 pub use crate::{
     ability::*, action::*, anyhow::Result, archetype::*, card::*, catalog::*, color::*, command::*,
-    computed::*, continuous::*, effect::*, env::*, event::*, id::*, message::*, variable::*,
-    zone::*,
+    computed::*, continuous::*, dsl::script::value::Value, effect::*, env::*, event::*, id::*,
+    message::*, variable::*, zone::*,
 };
@@ -1,4 +1,7 @@
-use crate::{player::PlayerConfig, pool::CardPool, regulation::Regulation};
+use crate::{
+    booster::SealedConfig, catalog::Catalog, error::DeckViolation, player::PlayerConfig,
+    pool::CardPool, regulation::Regulation,
+};
 use bincode::{
     de::{BorrowDecoder, Decoder},
     enc::Encoder,
@@ -7,6 +10,7 @@ use bincode::{
 };
 use bitflags::bitflags;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct GameProfile {
@@ -17,6 +21,30 @@ pub struct GameProfile {
     pub bots: Vec<BotConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rng_seed: Option<u64>,
+    /// Replaces `card_pool` with the cards each player opened.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sealed: Option<SealedConfig>,
+}
+
+impl GameProfile {
+    /// Returns the players whose decks are not allowed, with the reasons.
+    pub fn verify_decks(&self, catalog: &Catalog) -> Vec<(u8, Vec<DeckViolation>)> {
+        let pools = match &self.sealed {
+            Some(sealed) => sealed.card_pools(catalog, self.players.len()),
+            None => vec![self.card_pool.clone(); self.players.len()],
+        };
+        self.players
+            .iter()
+            .zip(pools)
+            .enumerate()
+            .filter_map(|(player, (config, pool))| {
+                let violations = self
+                    .regulation
+                    .verify_with_pool(&config.deck, &pool, catalog);
+                (!violations.is_empty()).then_some((player as u8, violations))
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, Encode, Decode)]
@@ -53,7 +81,80 @@ impl<'de> BorrowDecode<'de> for DebugFlags {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct BotConfig {
     pub player: u8,
+    #[serde(default)]
+    pub kind: BotKind,
+    #[serde(default)]
+    pub difficulty: BotDifficulty,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub enum BotKind {
+    /// Plays the best looking card or attack without looking ahead.
+    Simple,
+    /// Scores each action by simulating the rest of the turn.
+    Default {
+        #[serde(default)]
+        style: BotStyle,
+    },
+    /// Searches the game tree with Monte Carlo tree search.
+    #[default]
+    Mcts,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Encode, Decode)]
+pub struct BotDifficulty {
+    /// The maximum number of simulations for each decision of a search bot.
+    pub iterations: u32,
+    /// The maximum time to search for each decision, if any.
+    ///
+    /// A time limit makes the search depend on the speed of the machine,
+    /// so a seeded game no longer replays the same.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<Duration>,
+    /// The chance of playing a random action instead of the best one, from 0.0 to 1.0.
+    pub blunder_rate: f32,
+    /// How much a search bot tries less visited actions over the ones that look best.
+    pub exploration: f32,
+}
+
+impl BotDifficulty {
+    pub const EASY: Self = Self {
+        iterations: 50,
+        time: None,
+        blunder_rate: 0.15,
+        exploration: 2.0,
+    };
+
+    pub const NORMAL: Self = Self {
+        iterations: 200,
+        time: None,
+        blunder_rate: 0.0,
+        exploration: 1.4,
+    };
+
+    pub const HARD: Self = Self {
+        iterations: 800,
+        time: None,
+        blunder_rate: 0.0,
+        exploration: 1.4,
+    };
+}
+
+impl Default for BotDifficulty {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+/// Biases the battle decisions of the `Default` bot.
+///
+/// Both values are added to the score of an attack or a block for each creature in it,
+/// so positive values make the bot attack or block more readily and negative ones less.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct BotStyle {
+    pub aggression: i32,
+    pub blocking: i32,
 }
@@ -1,7 +1,9 @@
-use crate::{catalog::Catalog, deck::DeckList};
+use crate::{
+    catalog::Catalog, clock::TimeControl, deck::DeckList, error::DeckViolation, pool::CardPool,
+};
 use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, time::Duration};
+use std::time::Duration;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, Encode, Decode)]
 pub struct Regulation {
@@ -14,6 +16,27 @@ pub struct Regulation {
     pub action_timeout: Duration,
     pub phase_timeout: Duration,
     pub max_consecutive_timeouts: u8,
+    #[serde(default)]
+    pub mulligan: MulliganPolicy,
+    /// Replaces `action_timeout` and `phase_timeout` with per-player time banks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_control: Option<TimeControl>,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Encode, Decode,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum MulliganPolicy {
+    /// The game starts without a mulligan step.
+    #[default]
+    Disabled,
+    /// The whole hand is shuffled into the deck and the player draws one fewer card.
+    /// This can be repeated as long as the new hand has at least one card.
+    Redraw,
+    /// The chosen cards are put on the bottom of the deck and the player draws the same number.
+    /// This can be done only once.
+    Bottom,
 }
 
 impl Default for Regulation {
@@ -33,21 +56,161 @@ impl Regulation {
         action_timeout: Duration::from_secs(30),
         phase_timeout: Duration::from_secs(60),
         max_consecutive_timeouts: 2,
+        mulligan: MulliganPolicy::Disabled,
+        time_control: None,
     };
 
-    pub fn verify(&self, deck: &DeckList, catalog: &Catalog) -> bool {
-        let mut count = HashMap::new();
-        for item in &deck.cards {
-            if !catalog.contains(item.card.archetype_id.as_str()) {
-                return false;
+    /// For sealed games, where decks are built from the cards of a few booster packs.
+    pub const SEALED: Self = Self {
+        min_deck_size: 15,
+        ..Self::STANDARD
+    };
+
+    /// Returns the reasons why the deck is not allowed. The deck is valid if the list is empty.
+    pub fn verify(&self, deck: &DeckList, catalog: &Catalog) -> Vec<DeckViolation> {
+        let mut violations = vec![];
+        let size = deck.cards.len() as u32;
+        if size < self.min_deck_size as u32 {
+            violations.push(DeckViolation::TooFewCards {
+                size,
+                min: self.min_deck_size,
+            });
+        }
+        if size > self.max_deck_size as u32 {
+            violations.push(DeckViolation::TooManyCards {
+                size,
+                max: self.max_deck_size,
+            });
+        }
+        for (card, count) in deck.count_cards() {
+            match catalog.get(card) {
+                None => violations.push(DeckViolation::UnknownCard { card }),
+                Some(archetype) if archetype.attribute.is_token => {
+                    violations.push(DeckViolation::TokenInDeck { card })
+                }
+                _ => {}
             }
-            let entry = count.entry(item.card.archetype_id).or_insert(0);
-            *entry += 1;
-            if *entry > self.max_same_cards {
-                return false;
+            if count > self.max_same_cards as u32 {
+                violations.push(DeckViolation::TooManyCopies {
+                    card,
+                    count,
+                    max: self.max_same_cards,
+                });
             }
         }
-        let size = deck.cards.len() as u8;
-        size >= self.min_deck_size && size <= self.max_deck_size
+        violations
+    }
+
+    /// Returns the reasons why the deck is not allowed by the regulation or the card pool,
+    /// without the violations reported by both.
+    pub fn verify_with_pool(
+        &self,
+        deck: &DeckList,
+        pool: &CardPool,
+        catalog: &Catalog,
+    ) -> Vec<DeckViolation> {
+        let mut violations = self.verify(deck, catalog);
+        for violation in pool.verify(deck, catalog) {
+            if !violations.contains(&violation) {
+                violations.push(violation);
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        archetype::{ArchetypeId, CardArchetype, CardAttribute},
+        pool::{CardPoolEntry, PoolStatus},
+        testing::{archetype, catalog, deck},
+    };
+
+    #[test]
+    fn test_verify_deck() {
+        let catalog = catalog([
+            archetype("card"),
+            archetype("lmtd"),
+            CardArchetype {
+                attribute: CardAttribute {
+                    is_token: true,
+                    ..Default::default()
+                },
+                ..archetype("tokn")
+            },
+        ]);
+        let regulation = Regulation {
+            min_deck_size: 4,
+            max_deck_size: 6,
+            max_same_cards: 3,
+            ..Regulation::STANDARD
+        };
+        let pool = CardPool::from_iter([
+            (
+                CardPoolEntry::Card(ArchetypeId::new("lmtd")),
+                PoolStatus::Limited(1),
+            ),
+            (CardPoolEntry::CoreSet, PoolStatus::Legal),
+        ]);
+
+        let valid = deck(&[("card", 3), ("lmtd", 1)]);
+        assert!(regulation.verify(&valid, &catalog).is_empty());
+        assert!(pool.verify(&valid, &catalog).is_empty());
+
+        let invalid = deck(&[("card", 4), ("lmtd", 2), ("tokn", 1), ("none", 1)]);
+        assert_eq!(
+            regulation.verify(&invalid, &catalog),
+            vec![
+                DeckViolation::TooManyCards { size: 8, max: 6 },
+                DeckViolation::TooManyCopies {
+                    card: ArchetypeId::new("card"),
+                    count: 4,
+                    max: 3,
+                },
+                DeckViolation::TokenInDeck {
+                    card: ArchetypeId::new("tokn"),
+                },
+                DeckViolation::UnknownCard {
+                    card: ArchetypeId::new("none"),
+                },
+            ]
+        );
+        assert_eq!(
+            pool.verify(&invalid, &catalog),
+            vec![
+                DeckViolation::CardLimited {
+                    card: ArchetypeId::new("lmtd"),
+                    count: 2,
+                    max: 1,
+                },
+                DeckViolation::UnknownCard {
+                    card: ArchetypeId::new("none"),
+                },
+            ]
+        );
+        assert_eq!(
+            regulation.verify_with_pool(&invalid, &pool, &catalog),
+            vec![
+                DeckViolation::TooManyCards { size: 8, max: 6 },
+                DeckViolation::TooManyCopies {
+                    card: ArchetypeId::new("card"),
+                    count: 4,
+                    max: 3,
+                },
+                DeckViolation::TokenInDeck {
+                    card: ArchetypeId::new("tokn"),
+                },
+                DeckViolation::UnknownCard {
+                    card: ArchetypeId::new("none"),
+                },
+                DeckViolation::CardLimited {
+                    card: ArchetypeId::new("lmtd"),
+                    count: 2,
+                    max: 1,
+                },
+            ]
+        );
     }
 }
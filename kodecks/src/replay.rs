@@ -0,0 +1,215 @@
+use crate::{
+    action::Action,
+    catalog::Catalog,
+    env::{EndgameState, Environment, Report},
+    profile::GameProfile,
+};
+use bincode::{Decode, Encode};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use thiserror::Error;
+
+pub const REPLAY_VERSION: u16 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct Replay {
+    pub version: u16,
+    pub catalog_version: u64,
+    pub profile: GameProfile,
+    pub steps: Vec<ReplayStep>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct ReplayStep {
+    pub player: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<Action>,
+    pub timestamp: u32,
+    pub logs: u32,
+    pub endgame: EndgameState,
+}
+
+impl Replay {
+    /// Creates an empty replay for a game.
+    ///
+    /// A random `rng_seed` is assigned if the profile does not have one,
+    /// so the game must be started with `replay.profile` to be reproducible.
+    pub fn new(mut profile: GameProfile, catalog: &Catalog) -> Self {
+        profile.rng_seed.get_or_insert_with(rand::random);
+        Self {
+            version: REPLAY_VERSION,
+            catalog_version: catalog.version(),
+            profile,
+            steps: vec![],
+        }
+    }
+
+    pub fn record(&mut self, player: u8, action: Option<Action>, report: &Report) {
+        self.steps.push(ReplayStep {
+            player,
+            action,
+            timestamp: report.timestamp,
+            logs: report.logs.len() as u32,
+            endgame: report.endgame,
+        });
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        bincode::encode_to_vec(self, bincode::config::standard()).unwrap()
+    }
+
+    pub fn decode(data: &[u8]) -> Result<Self, ReplayError> {
+        let (replay, _): (Self, _) = bincode::decode_from_slice(data, bincode::config::standard())
+            .map_err(|err| ReplayError::InvalidFormat(err.to_string()))?;
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion {
+                version: replay.version,
+            });
+        }
+        Ok(replay)
+    }
+}
+
+#[derive(Debug, Error, Clone)]
+pub enum ReplayError {
+    #[error("Invalid replay format: {0}")]
+    InvalidFormat(String),
+    #[error("Unsupported replay version: {version}")]
+    UnsupportedVersion { version: u16 },
+    #[error("Catalog version mismatch: replay: {expected:016x} catalog: {actual:016x}")]
+    CatalogMismatch { expected: u64, actual: u64 },
+    #[error("Replay has no rng seed")]
+    MissingSeed,
+    #[error("Invalid action at step {step}: player: {player} action: {action:?}")]
+    InvalidAction {
+        step: usize,
+        player: u8,
+        action: Option<Action>,
+    },
+    #[error("Replay diverged at step {step}: expected: {expected} actual: {actual}")]
+    Diverged {
+        step: usize,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl Environment {
+    /// Replays a recorded game and calls `f` with every report in order.
+    pub fn replay<F>(replay: &Replay, catalog: Arc<Catalog>, mut f: F) -> Result<Self, ReplayError>
+    where
+        F: FnMut(usize, &Report),
+    {
+        if replay.version != REPLAY_VERSION {
+            return Err(ReplayError::UnsupportedVersion {
+                version: replay.version,
+            });
+        }
+        let actual = catalog.version();
+        if replay.catalog_version != actual {
+            return Err(ReplayError::CatalogMismatch {
+                expected: replay.catalog_version,
+                actual,
+            });
+        }
+        if replay.profile.rng_seed.is_none() {
+            return Err(ReplayError::MissingSeed);
+        }
+
+        let mut env = Environment::new(replay.profile.clone(), catalog);
+        for (step, input) in replay.steps.iter().enumerate() {
+            let report = if let Some(Action::Timeout) = input.action {
+                env.timeout(input.player)
+            } else {
+                if !env.accepts(input.player, input.action.as_ref()) {
+                    return Err(ReplayError::InvalidAction {
+                        step,
+                        player: input.player,
+                        action: input.action.clone(),
+                    });
+                }
+                env.process(input.player, input.action.clone())
+            };
+            let expected = (input.timestamp, input.logs, input.endgame);
+            let actual = (report.timestamp, report.logs.len() as u32, report.endgame);
+            if expected != actual {
+                return Err(ReplayError::Diverged {
+                    step,
+                    expected: format!(
+                        "timestamp: {} logs: {} endgame: {:?}",
+                        expected.0, expected.1, expected.2
+                    ),
+                    actual: format!(
+                        "timestamp: {} logs: {} endgame: {:?}",
+                        actual.0, actual.1, actual.2
+                    ),
+                });
+            }
+            f(step, &report);
+        }
+        Ok(env)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{player::PlayerConfig, profile::DebugConfig};
+
+    fn record_game(catalog: &Arc<Catalog>) -> Replay {
+        let profile = GameProfile {
+            debug: Some(DebugConfig {
+                no_deck_shuffle: false,
+                no_player_shuffle: false,
+                flags: Default::default(),
+            }),
+            players: vec![
+                PlayerConfig {
+                    deck: Default::default(),
+                },
+                PlayerConfig {
+                    deck: Default::default(),
+                },
+            ],
+            rng_seed: Some(1),
+            ..Default::default()
+        };
+        let mut replay = Replay::new(profile, catalog);
+        let mut env = Environment::new(replay.profile.clone(), catalog.clone());
+        let mut player = env.state.players.player_in_turn().unwrap().id;
+        let mut action = None;
+        for _ in 0..1000 {
+            if env.game_condition().is_ended() {
+                break;
+            }
+            let report = env.process(player, action.clone());
+            replay.record(player, action, &report);
+            action = None;
+            if let Some(available) = &report.available_actions {
+                player = available.player;
+                action = available.actions.default_action(&env);
+            }
+        }
+        replay
+    }
+
+    #[test]
+    fn test_replay() {
+        let catalog = Arc::new(Catalog::new(&[]));
+        let replay = record_game(&catalog);
+        assert!(!replay.steps.is_empty());
+
+        let replay = Replay::decode(&replay.encode()).unwrap();
+        let mut steps = 0;
+        let env = Environment::replay(&replay, catalog.clone(), |_, _| steps += 1).unwrap();
+        assert_eq!(steps, replay.steps.len());
+        assert!(env.game_condition().is_ended());
+
+        let mut broken = replay.clone();
+        broken.steps[0].timestamp += 1;
+        assert!(matches!(
+            Environment::replay(&broken, catalog, |_, _| {}),
+            Err(ReplayError::Diverged { step: 0, .. })
+        ));
+    }
+}
@@ -11,4 +11,5 @@ pub trait CardSequence: CardZone {
 
     fn remove_top(&mut self) -> Option<<Self as CardZone>::Item>;
     fn add_top(&mut self, card: <Self as CardZone>::Item);
+    fn add_bottom(&mut self, card: <Self as CardZone>::Item);
 }
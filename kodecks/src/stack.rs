@@ -1,13 +1,14 @@
-use crate::{effect::StackEffectHandler, id::ObjectId};
+use crate::{archetype::ArchetypeId, dsl::script::value::Value, effect::EffectId, id::ObjectId};
 use bincode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
 
-#[derive(Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize, Encode, Decode)]
 pub struct StackItem {
     pub source: ObjectId,
-    pub id: String,
-    pub handler: Arc<Box<StackEffectHandler>>,
+    pub archetype: ArchetypeId,
+    pub id: EffectId,
+    #[serde(default)]
+    pub args: Vec<Value>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash, Encode, Decode)]
@@ -20,7 +21,7 @@ impl From<StackItem> for LocalStackItem {
     fn from(item: StackItem) -> Self {
         Self {
             source: item.source,
-            id: item.id,
+            id: item.id.to_string(),
         }
     }
 }
@@ -0,0 +1,59 @@
+//! Catalogs and decks of dummy cards for tests.
+
+use crate::{
+    archetype::{ArchetypeId, CardArchetype},
+    card::CardEntry,
+    catalog::{CardSet, Catalog},
+    deck::{DeckItem, DeckList},
+};
+use std::{iter, sync::Arc};
+
+/// Returns an archetype without effects, named after its id.
+pub fn archetype(id: &str) -> CardArchetype {
+    CardArchetype {
+        id: ArchetypeId::new(id),
+        name: id.to_string(),
+        safe_name: id.to_string(),
+        ..Default::default()
+    }
+}
+
+/// Returns a card set of the archetypes.
+pub fn card_set<I>(archetypes: I) -> CardSet
+where
+    I: IntoIterator<Item = CardArchetype>,
+{
+    CardSet::from_archetypes(archetypes.into_iter().map(Arc::new).collect())
+}
+
+/// Returns a catalog whose core set contains the archetypes.
+pub fn catalog<I>(archetypes: I) -> Catalog
+where
+    I: IntoIterator<Item = CardArchetype>,
+{
+    Catalog {
+        sets: vec![card_set(archetypes)],
+    }
+}
+
+/// Returns a deck list with the given number of copies of each card id.
+pub fn deck(cards: &[(&str, usize)]) -> DeckList {
+    DeckList {
+        cards: cards
+            .iter()
+            .flat_map(|(id, count)| {
+                iter::repeat_n(
+                    DeckItem {
+                        card: CardEntry {
+                            archetype_id: ArchetypeId::new(id),
+                            style: 0,
+                        },
+                        base_id: None,
+                    },
+                    *count,
+                )
+            })
+            .collect(),
+        ..Default::default()
+    }
+}